@@ -0,0 +1,115 @@
+use ron::error::SpannedError;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Format of the jabu file of a project, given by its extension (*i.e.
+/// `jabu.toml`*).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ConfigFormat {
+    Ron,
+    Toml,
+    Json,
+}
+
+/// Every format a jabu file can be written in, the default one first.
+pub const CONFIG_FORMATS: [ConfigFormat; 3] =
+    [ConfigFormat::Ron, ConfigFormat::Toml, ConfigFormat::Json];
+
+impl ConfigFormat {
+    /// Returns the format with the given extension (*i.e. `toml`*), if any.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        CONFIG_FORMATS
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Ron => "ron",
+            Self::Toml => "toml",
+            Self::Json => "json",
+        }
+    }
+
+    /// Name of the jabu file of a project written in the format (*i.e. `jabu.json`*).
+    pub fn project_file_name(&self) -> String {
+        format!("jabu.{}", self.extension())
+    }
+
+    /// Parses the contents of a file written in the format.
+    pub fn deserialize<T: DeserializeOwned>(&self, contents: &str) -> Result<T, ParsingError> {
+        match self {
+            Self::Ron => Ok(ron::from_str(contents)?),
+            Self::Toml => toml::from_str(contents).map_err(|e| {
+                let offset = e.span().map_or(0, |span| span.start);
+                ParsingError::at_offset(e.message(), contents, offset)
+            }),
+            Self::Json => serde_json::from_str(contents).map_err(|e| {
+                // The message of the error ends with its location.
+                let message = e.to_string();
+                let message = message
+                    .rsplit_once(" at line ")
+                    .map_or(message.as_str(), |(message, _)| message);
+                ParsingError {
+                    message: message.to_string(),
+                    line: e.line(),
+                    column: e.column(),
+                }
+            }),
+        }
+    }
+
+    /// Writes the value in the format, as a human readable file.
+    pub fn serialize<T: Serialize>(&self, value: &T) -> Result<String, String> {
+        match self {
+            Self::Ron => ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+                .map_err(|e| e.to_string()),
+            Self::Toml => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+            Self::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// Error when parsing a file, along with the line and the column (*both starting
+/// at 1*) where it has been found.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParsingError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ParsingError {
+    fn at_offset(message: &str, contents: &str, offset: usize) -> Self {
+        let before = &contents[..offset.min(contents.len())];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        Self {
+            message: message.to_string(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl From<SpannedError> for ParsingError {
+    fn from(value: SpannedError) -> Self {
+        Self {
+            message: value.code.to_string(),
+            line: value.position.line,
+            column: value.position.col,
+        }
+    }
+}
+
+impl std::fmt::Display for ParsingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParsingError {}
@@ -1,4 +1,10 @@
-use crate::model::{JabuProject, JABU_FILE_NAME};
+use crate::format::{ConfigFormat, ParsingError, CONFIG_FORMATS};
+use crate::interpolation::interpolate_project;
+use crate::migration::migrate_project;
+use crate::model::{
+    JabuProject, LockFile, Workspace, CURRENT_FORMAT_VERSION, JABU_LOCK_FILE_NAME,
+    JABU_WORKSPACE_FILE_NAME,
+};
 use ron::error::SpannedError;
 use std::{
     fs::{create_dir_all, read_to_string, write},
@@ -13,7 +19,30 @@ pub enum ProjectLoadingError {
     IoError(std::io::Error),
 
     /// Error caused when parsing a malformed project.
-    FileParsingError(SpannedError),
+    FileParsingError(ParsingError),
+
+    /// The directory contains the jabu file of the project in several formats
+    /// (*i.e. `jabu.ron` and `jabu.toml`*). The variant holds their names.
+    SeveralProjectFiles(Vec<String>),
+
+    /// A string of the project contains a variable (*i.e. `${env.BUILD_NUMBER}`*)
+    /// which is undefined, and has no default.
+    UndefinedVariable {
+        /// The field containing the variable (*i.e. `header.version`*).
+        field: String,
+        variable: String,
+    },
+
+    /// A variable of the project is defined in terms of itself.
+    CircularVariable {
+        /// The field containing the variable (*i.e. `header.version`*).
+        field: String,
+        variable: String,
+    },
+
+    /// The project has a format version newer than the [`CURRENT_FORMAT_VERSION`]
+    /// (*it has been written by a newer version of jabu*).
+    UnsupportedFormatVersion(u32),
 }
 
 impl From<std::io::Error> for ProjectLoadingError {
@@ -24,6 +53,12 @@ impl From<std::io::Error> for ProjectLoadingError {
 
 impl From<SpannedError> for ProjectLoadingError {
     fn from(value: SpannedError) -> Self {
+        Self::FileParsingError(value.into())
+    }
+}
+
+impl From<ParsingError> for ProjectLoadingError {
+    fn from(value: ParsingError) -> Self {
         Self::FileParsingError(value)
     }
 }
@@ -37,6 +72,18 @@ impl std::fmt::Display for ProjectLoadingError {
             Self::FileParsingError(e) => {
                 format!("Project loading error when parsing the contents: {e}")
             }
+            Self::UndefinedVariable { field, variable } => {
+                format!("The variable '{variable}' used in the field '{field}' is undefined")
+            }
+            Self::CircularVariable { field, variable } => {
+                format!("The variable '{variable}' used in the field '{field}' is defined in terms of itself")
+            }
+            Self::SeveralProjectFiles(files) => {
+                format!("There are several jabu files ({}), only one of them can be kept", files.join(", "))
+            }
+            Self::UnsupportedFormatVersion(format_version) => {
+                format!("The format version {format_version} of the project is newer than the latest one supported ({CURRENT_FORMAT_VERSION}), jabu must be updated to load it")
+            }
         };
         write!(f, "{string}")
     }
@@ -98,7 +145,8 @@ pub fn create_project(
     let dirs = project_dirs(jabu_project);
 
     // Create all directories
-    dirs.into_iter().try_for_each(|dir| create_dir_all(dir))?;
+    dirs.into_iter()
+        .try_for_each(|dir| create_dir_all(base_dir.join(dir)))?;
 
     jabu_project
         .fs_schema
@@ -111,9 +159,35 @@ pub fn create_project(
         })
 }
 
+/// Returns the path to the jabu file of the project located in the given directory,
+/// along with its format: `jabu.ron`, `jabu.toml` or `jabu.json`. If there is none,
+/// the path to `jabu.ron` is returned.
+///
+/// Returns [`ProjectLoadingError::SeveralProjectFiles`] if there are several of them.
+pub fn project_file(base_directory: &Path) -> Result<(PathBuf, ConfigFormat), ProjectLoadingError> {
+    let mut files = CONFIG_FORMATS
+        .into_iter()
+        .map(|format| (base_directory.join(format.project_file_name()), format))
+        .filter(|(file, _)| file.is_file())
+        .collect::<Vec<(PathBuf, ConfigFormat)>>();
+    match files.len() {
+        0 => Ok((
+            base_directory.join(ConfigFormat::Ron.project_file_name()),
+            ConfigFormat::Ron,
+        )),
+        1 => Ok(files.remove(0)),
+        _ => Err(ProjectLoadingError::SeveralProjectFiles(
+            files
+                .iter()
+                .map(|(_, format)| format.project_file_name())
+                .collect(),
+        )),
+    }
+}
+
 /// Loads a project from a given path to a directory. This function calls
-/// the [`project_from_file`] function by passing it
-/// `base_directory.join(crate::model::JABU_FILE_NAME)`.
+/// the [`project_from_file`] function by passing it the jabu file of the
+/// directory (*see [`project_file`]*).
 ///
 /// # See
 /// * [`project_from_file`]
@@ -121,12 +195,215 @@ pub fn project_from_directory(
     base_directory: Option<PathBuf>,
 ) -> Result<JabuProject, ProjectLoadingError> {
     let base_directory = dir_or_cwd(base_directory);
-    project_from_file(base_directory.join(JABU_FILE_NAME))
+    project_from_file(project_file(&base_directory)?.0)
 }
 
-/// Loads the project from a file.
+/// Loads the project from a file, whose format is given by its extension (*see
+/// [`ConfigFormat`]*), being RON by default.
+///
+/// Once it's parsed, the project is upgraded to the current format version (*see
+/// [`migrate_project`]*) and the variables in its strings are replaced (*see
+/// [`interpolate_project`]*).
 pub fn project_from_file(filepath: PathBuf) -> Result<JabuProject, ProjectLoadingError> {
-    Ok(ron::from_str(&read_to_string(filepath)?)?)
+    let format = filepath
+        .extension()
+        .and_then(|extension| ConfigFormat::from_extension(&extension.to_string_lossy()))
+        .unwrap_or(ConfigFormat::Ron);
+    let mut project: JabuProject = format.deserialize(&read_to_string(filepath)?)?;
+    migrate_project(&mut project)?;
+    interpolate_project(&mut project, &|name| std::env::var(name).ok())?;
+    Ok(project)
+}
+
+/// Loads the lockfile located in the given directory. If there is no lockfile,
+/// `None` is returned.
+pub fn lockfile_from_directory(
+    base_directory: Option<PathBuf>,
+) -> Result<Option<LockFile>, ProjectLoadingError> {
+    let lockfile_path = dir_or_cwd(base_directory).join(JABU_LOCK_FILE_NAME);
+    if !lockfile_path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(ron::from_str(&read_to_string(lockfile_path)?)?))
+}
+
+/// Writes the lockfile into the given directory, replacing the previous one.
+pub fn write_lockfile(base_directory: Option<PathBuf>, lockfile: &LockFile) -> std::io::Result<()> {
+    let contents = ron::ser::to_string_pretty(lockfile, ron::ser::PrettyConfig::default())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    write(dir_or_cwd(base_directory).join(JABU_LOCK_FILE_NAME), contents)
+}
+
+/// Loads the workspace located in the given directory. If there is no workspace,
+/// `None` is returned.
+pub fn workspace_from_directory(
+    base_directory: Option<PathBuf>,
+) -> Result<Option<Workspace>, ProjectLoadingError> {
+    let workspace_path = dir_or_cwd(base_directory).join(JABU_WORKSPACE_FILE_NAME);
+    if !workspace_path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(ron::from_str(&read_to_string(workspace_path)?)?))
+}
+
+/// A project that is a member of a workspace.
+#[derive(Debug, PartialEq)]
+pub struct WorkspaceMember {
+    /// The (*canonical*) directory of the project.
+    pub directory: PathBuf,
+    pub project: JabuProject,
+}
+
+/// Represents an error while loading the members of a workspace.
+#[derive(Debug)]
+pub enum WorkspaceError {
+    /// A member couldn't be loaded, the variant holds the member as written in the
+    /// workspace.
+    Member(String, ProjectLoadingError),
+
+    /// A member is the directory of the workspace itself (*i.e. `.`*), the variant
+    /// holds the member as written in the workspace.
+    SelfMember(String),
+
+    /// Some members depend on each other, the variant holds the members involved,
+    /// where the first and the last ones are the same.
+    Cycle(Vec<String>),
+}
+
+impl std::fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Member(member, e) => write!(f, "Couldn't load the workspace member '{member}': {e}"),
+            Self::SelfMember(member) => write!(
+                f,
+                "The workspace member '{member}' is the directory of the workspace itself"
+            ),
+            Self::Cycle(members) => write!(
+                f,
+                "The workspace members depend on each other: {}",
+                members.join(" -> ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceError {}
+
+/// Loads the members of the workspace located in the given directory, sorted so
+/// that every member comes after the members it depends on by path. Members that
+/// don't depend on each other keep the order of the workspace.
+pub fn workspace_members(
+    base_directory: Option<PathBuf>,
+    workspace: &Workspace,
+) -> Result<Vec<WorkspaceMember>, WorkspaceError> {
+    let base_directory = dir_or_cwd(base_directory);
+    let workspace_directory = base_directory.canonicalize().ok();
+    let members = workspace
+        .members
+        .iter()
+        .map(|member| {
+            let directory = base_directory
+                .join(member)
+                .canonicalize()
+                .map_err(|e| WorkspaceError::Member(member.clone(), e.into()))?;
+            if Some(&directory) == workspace_directory.as_ref() {
+                return Err(WorkspaceError::SelfMember(member.clone()));
+            }
+            Ok(WorkspaceMember {
+                project: project_from_directory(Some(directory.clone()))
+                    .map_err(|e| WorkspaceError::Member(member.clone(), e))?,
+                directory,
+            })
+        })
+        .collect::<Result<Vec<WorkspaceMember>, WorkspaceError>>()?;
+
+    // Indices of the members each member depends on.
+    let dependencies: Vec<Vec<usize>> = members
+        .iter()
+        .map(|member| {
+            path_dependency_directories(&member.directory, &member.project)
+                .iter()
+                .filter_map(|directory| {
+                    members
+                        .iter()
+                        .position(|other| other.directory == *directory)
+                })
+                .collect()
+        })
+        .collect();
+
+    fn visit(
+        index: usize,
+        dependencies: &[Vec<usize>],
+        names: &[String],
+        visiting: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) -> Result<(), WorkspaceError> {
+        if order.contains(&index) {
+            return Ok(());
+        }
+        if let Some(start) = visiting.iter().position(|visited| *visited == index) {
+            let mut cycle: Vec<String> = visiting[start..]
+                .iter()
+                .map(|visited| names[*visited].clone())
+                .collect();
+            cycle.push(names[index].clone());
+            return Err(WorkspaceError::Cycle(cycle));
+        }
+
+        visiting.push(index);
+        dependencies[index]
+            .iter()
+            .try_for_each(|dependency| visit(*dependency, dependencies, names, visiting, order))?;
+        visiting.pop();
+        order.push(index);
+        Ok(())
+    }
+
+    let mut order = Vec::new();
+    (0..members.len()).try_for_each(|index| {
+        visit(index, &dependencies, &workspace.members, &mut Vec::new(), &mut order)
+    })?;
+
+    let mut members: Vec<Option<WorkspaceMember>> = members.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .filter_map(|index| members[index].take())
+        .collect())
+}
+
+/// Returns the (*canonical*) directories of the path dependencies of the project
+/// located in `project_directory`. Dependencies whose directory doesn't exist
+/// are skipped.
+fn path_dependency_directories(project_directory: &Path, jabu_project: &JabuProject) -> Vec<PathBuf> {
+    jabu_project
+        .dependencies
+        .path
+        .iter()
+        .filter_map(|path| project_directory.join(path).canonicalize().ok())
+        .collect()
+}
+
+/// Returns the classes directories of the path dependencies of the project located
+/// in the given directory, including the path dependencies of its dependencies.
+pub fn path_dependency_classes(
+    base_directory: Option<PathBuf>,
+    jabu_project: &JabuProject,
+) -> Result<Vec<PathBuf>, ProjectLoadingError> {
+    let mut visited = vec![dir_or_cwd(base_directory).canonicalize()?];
+    let mut pending = path_dependency_directories(&visited[0], jabu_project);
+    let mut classes = Vec::new();
+
+    while let Some(directory) = pending.pop() {
+        if visited.contains(&directory) {
+            continue;
+        }
+        let dependency = project_from_directory(Some(directory.clone()))?;
+        classes.push(directory.join(dependency.fs_schema.target_classes()));
+        pending.extend(path_dependency_directories(&directory, &dependency));
+        visited.push(directory);
+    }
+    Ok(classes)
 }
 
 /// Returns the paths to all .java files in the `sources` directory of the project.
@@ -141,6 +418,18 @@ pub fn java_sources(
     )
 }
 
+/// Returns the paths to all .java files in the `test` directory of the project.
+pub fn java_test_sources(
+    base_directory: Option<PathBuf>,
+    jabu_project: &JabuProject,
+) -> Vec<PathBuf> {
+    walkdir_find(
+        dir_or_cwd(base_directory).join(&jabu_project.fs_schema.test),
+        |file_name| file_name.extension().unwrap_or_default() == "java",
+        &[FSNodeType::File],
+    )
+}
+
 /// Returns the paths to all .jar files in the `lib` directory of the project.
 pub fn libs(base_directory: Option<PathBuf>, jabu_project: &JabuProject) -> Vec<PathBuf> {
     walkdir_find(
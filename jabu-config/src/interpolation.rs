@@ -0,0 +1,415 @@
+use crate::{
+    fsutils::ProjectLoadingError,
+    model::{
+        ArtifactSpec, ConfigHeader, CustomTask, CustomTaskAction, CustomTaskOption,
+        DependenciesConfig, DependenciesOverrides, FsSchema, FsSchemaOverrides, JabuProject,
+        JarManifest, JavaConfig, JavaConfigOverrides, Profile,
+    },
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Replaces the variables in the string fields of the project, once it has been
+/// parsed:
+///
+/// - `${env.NAME}`, the environment variable `NAME` (*given by `env`*).
+/// - `${header.FIELD}`, a field of the header of the project (*i.e.
+///   `${header.version}`*).
+/// - `${NAME}`, a property of the `properties` section of the project.
+///
+/// Any variable can have a default, used when it's undefined (*i.e.
+/// `${env.BUILD_NUMBER:-0}`*), and `$${` is written as a literal `${`. The header
+/// and the properties may contain variables as well.
+///
+/// Returns [`ProjectLoadingError::UndefinedVariable`] (*naming the field*) if a
+/// variable without default is undefined, or [`ProjectLoadingError::CircularVariable`]
+/// if it's defined in terms of itself.
+pub fn interpolate_project(
+    project: &mut JabuProject,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), ProjectLoadingError> {
+    let header = &project.header;
+    let mut variables = Variables {
+        header: HashMap::from([
+            ("project_name", header.project_name.clone()),
+            ("author", header.author.clone()),
+            ("description", header.description.clone()),
+            ("version", header.version.clone()),
+        ]),
+        properties: project.properties.clone(),
+        env,
+        resolving: Vec::new(),
+    };
+    if let Some(license) = &header.license {
+        variables.header.insert("license", license.clone());
+    }
+    project.interpolate("", &mut variables)
+}
+
+/// Variables of a project, resolved lazily so that they can be defined in terms
+/// of each other.
+struct Variables<'a> {
+    header: HashMap<&'static str, String>,
+    properties: BTreeMap<String, String>,
+    env: &'a dyn Fn(&str) -> Option<String>,
+
+    /// Variables being resolved, to detect the circular ones.
+    resolving: Vec<String>,
+}
+
+enum InterpolationError {
+    Undefined(String),
+    Circular(String),
+}
+
+impl Variables<'_> {
+    fn interpolate(&mut self, value: &str) -> Result<String, InterpolationError> {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find("${") {
+            if rest[..start].ends_with('$') {
+                result.push_str(&rest[..start - 1]);
+                result.push_str("${");
+                rest = &rest[start + 2..];
+                continue;
+            }
+            let Some(length) = rest[start..].find('}') else {
+                break;
+            };
+            result.push_str(&rest[..start]);
+
+            let expression = &rest[start + 2..start + length];
+            let (name, default) = match expression.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (expression, None),
+            };
+            match (self.variable(name.trim())?, default) {
+                (Some(value), _) => result.push_str(&value),
+                (None, Some(default)) => result.push_str(default),
+                (None, None) => return Err(InterpolationError::Undefined(name.to_string())),
+            }
+            rest = &rest[start + length + 1..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    fn variable(&mut self, name: &str) -> Result<Option<String>, InterpolationError> {
+        if let Some(env_name) = name.strip_prefix("env.") {
+            return Ok((self.env)(env_name));
+        }
+        let value = match name.strip_prefix("header.") {
+            Some(field) => self.header.get(field),
+            None => self.properties.get(name),
+        };
+        let Some(value) = value.cloned() else {
+            return Ok(None);
+        };
+
+        if self.resolving.iter().any(|resolving| resolving == name) {
+            return Err(InterpolationError::Circular(name.to_string()));
+        }
+        self.resolving.push(name.to_string());
+        let value = self.interpolate(&value);
+        self.resolving.pop();
+        value.map(Some)
+    }
+}
+
+/// Value whose strings can contain variables, `field` being its path inside of
+/// the project (*i.e. `fs_schema.target`*).
+trait Interpolate {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError>;
+}
+
+fn field_path(parent: &str, field: &str) -> String {
+    if parent.is_empty() {
+        field.to_string()
+    } else {
+        format!("{parent}.{field}")
+    }
+}
+
+impl Interpolate for String {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        if !self.contains("${") {
+            return Ok(());
+        }
+        *self = variables.interpolate(self).map_err(|e| match e {
+            InterpolationError::Undefined(variable) => ProjectLoadingError::UndefinedVariable {
+                field: field.to_string(),
+                variable,
+            },
+            InterpolationError::Circular(variable) => ProjectLoadingError::CircularVariable {
+                field: field.to_string(),
+                variable,
+            },
+        })?;
+        Ok(())
+    }
+}
+
+impl<T: Interpolate> Interpolate for Option<T> {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        match self {
+            Some(value) => value.interpolate(field, variables),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Interpolate> Interpolate for Vec<T> {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        self.iter_mut().enumerate().try_for_each(|(index, value)| {
+            value.interpolate(&format!("{field}[{index}]"), variables)
+        })
+    }
+}
+
+impl<T: Interpolate> Interpolate for BTreeMap<String, T> {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        self.iter_mut()
+            .try_for_each(|(key, value)| value.interpolate(&field_path(field, key), variables))
+    }
+}
+
+impl<T: Interpolate> Interpolate for HashMap<String, T> {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        self.iter_mut()
+            .try_for_each(|(key, value)| value.interpolate(&field_path(field, key), variables))
+    }
+}
+
+impl Interpolate for JabuProject {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.header.interpolate(&path("header"), variables)?;
+        self.java_config
+            .interpolate(&path("java_config"), variables)?;
+        self.manifest.interpolate(&path("manifest"), variables)?;
+        self.fs_schema.interpolate(&path("fs_schema"), variables)?;
+        self.dependencies
+            .interpolate(&path("dependencies"), variables)?;
+        self.default_tasks
+            .interpolate(&path("default_tasks"), variables)?;
+        self.tasks.interpolate(&path("tasks"), variables)?;
+        self.custom_tasks
+            .interpolate(&path("custom_tasks"), variables)?;
+        self.profiles.interpolate(&path("profiles"), variables)?;
+        self.properties.interpolate(&path("properties"), variables)
+    }
+}
+
+impl Interpolate for ConfigHeader {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.project_name
+            .interpolate(&path("project_name"), variables)?;
+        self.author.interpolate(&path("author"), variables)?;
+        self.description
+            .interpolate(&path("description"), variables)?;
+        self.license.interpolate(&path("license"), variables)?;
+        self.tags.interpolate(&path("tags"), variables)?;
+        self.version.interpolate(&path("version"), variables)
+    }
+}
+
+impl Interpolate for JavaConfig {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        self.compiler_args
+            .interpolate(&field_path(field, "compiler_args"), variables)
+    }
+}
+
+impl Interpolate for JarManifest {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        self.contents
+            .interpolate(&field_path(field, "contents"), variables)
+    }
+}
+
+impl Interpolate for FsSchema {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.source.interpolate(&path("source"), variables)?;
+        self.target.interpolate(&path("target"), variables)?;
+        self.lib.interpolate(&path("lib"), variables)?;
+        self.resources.interpolate(&path("resources"), variables)?;
+        self.filtered_resources
+            .interpolate(&path("filtered_resources"), variables)?;
+        self.scripts.interpolate(&path("scripts"), variables)?;
+        self.test.interpolate(&path("test"), variables)?;
+        self.other.interpolate(&path("other"), variables)?;
+        self.jar_name.interpolate(&path("jar_name"), variables)
+    }
+}
+
+impl Interpolate for DependenciesConfig {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.local.interpolate(&path("local"), variables)?;
+        self.remote.interpolate(&path("remote"), variables)?;
+        self.path.interpolate(&path("path"), variables)?;
+        self.maven.interpolate(&path("maven"), variables)?;
+        self.maven_repositories
+            .interpolate(&path("maven_repositories"), variables)?;
+        self.maven_exclusions
+            .interpolate(&path("maven_exclusions"), variables)
+    }
+}
+
+impl Interpolate for ArtifactSpec {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        self.author.interpolate(field, variables)?;
+        self.artifact_id.interpolate(field, variables)?;
+        self.version.interpolate(field, variables)
+    }
+}
+
+impl Interpolate for CustomTask {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.description
+            .interpolate(&path("description"), variables)?;
+        match &mut self.run {
+            CustomTaskAction::Script(value) | CustomTaskAction::Command(value) => {
+                value.interpolate(&path("run"), variables)?
+            }
+        }
+        self.options.interpolate(&path("options"), variables)?;
+        self.depends_on
+            .interpolate(&path("depends_on"), variables)?;
+        self.before.interpolate(&path("before"), variables)?;
+        self.after.interpolate(&path("after"), variables)?;
+        self.inputs.interpolate(&path("inputs"), variables)?;
+        self.outputs.interpolate(&path("outputs"), variables)
+    }
+}
+
+impl Interpolate for CustomTaskOption {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.description
+            .interpolate(&path("description"), variables)?;
+        self.default_value
+            .interpolate(&path("default_value"), variables)
+    }
+}
+
+impl Interpolate for Profile {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.java_config
+            .interpolate(&path("java_config"), variables)?;
+        self.manifest.interpolate(&path("manifest"), variables)?;
+        self.fs_schema.interpolate(&path("fs_schema"), variables)?;
+        self.dependencies
+            .interpolate(&path("dependencies"), variables)
+    }
+}
+
+impl Interpolate for JavaConfigOverrides {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        self.compiler_args
+            .interpolate(&field_path(field, "compiler_args"), variables)
+    }
+}
+
+impl Interpolate for FsSchemaOverrides {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.source.interpolate(&path("source"), variables)?;
+        self.target.interpolate(&path("target"), variables)?;
+        self.lib.interpolate(&path("lib"), variables)?;
+        self.resources.interpolate(&path("resources"), variables)?;
+        self.test.interpolate(&path("test"), variables)?;
+        self.jar_name.interpolate(&path("jar_name"), variables)
+    }
+}
+
+impl Interpolate for DependenciesOverrides {
+    fn interpolate(
+        &mut self,
+        field: &str,
+        variables: &mut Variables,
+    ) -> Result<(), ProjectLoadingError> {
+        let path = |name: &str| field_path(field, name);
+        self.local.interpolate(&path("local"), variables)?;
+        self.remote.interpolate(&path("remote"), variables)?;
+        self.path.interpolate(&path("path"), variables)?;
+        self.maven.interpolate(&path("maven"), variables)?;
+        self.maven_repositories
+            .interpolate(&path("maven_repositories"), variables)
+    }
+}
@@ -1,4 +1,8 @@
 pub mod model;
 pub mod prelude;
 
+pub mod format;
 pub mod fsutils;
+pub mod interpolation;
+pub mod migration;
+pub mod validation;
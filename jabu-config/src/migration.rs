@@ -0,0 +1,47 @@
+use crate::{
+    fsutils::ProjectLoadingError,
+    model::{JabuProject, CURRENT_FORMAT_VERSION},
+};
+
+/// Upgrade of the layout of a project from a format version to the next one.
+struct Migration {
+    /// What the migration changes, shown by `jabu config migrate`.
+    description: &'static str,
+    migrate: fn(&mut JabuProject),
+}
+
+/// Migrations of the projects, where the migration at index `n` upgrades the
+/// projects of format version `n` to `n + 1`.
+const MIGRATIONS: [Migration; CURRENT_FORMAT_VERSION as usize] = [Migration {
+    // The layout didn't change, besides the fields added before it was versioned,
+    // which are filled in with their defaults when loading it.
+    description: "Adds the 'format_version' of the file",
+    migrate: |_| (),
+}];
+
+/// Upgrades the project, loaded from a file of an older format version, to the
+/// [`CURRENT_FORMAT_VERSION`], applying every migration from its version on. The
+/// descriptions of the migrations applied are returned, in order.
+///
+/// Returns [`ProjectLoadingError::UnsupportedFormatVersion`] if the project comes
+/// from a newer version of jabu.
+pub fn migrate_project(
+    project: &mut JabuProject,
+) -> Result<Vec<&'static str>, ProjectLoadingError> {
+    let format_version = project.format_version;
+    if format_version > CURRENT_FORMAT_VERSION {
+        return Err(ProjectLoadingError::UnsupportedFormatVersion(
+            format_version,
+        ));
+    }
+
+    let migrations = &MIGRATIONS[format_version as usize..];
+    for migration in migrations {
+        (migration.migrate)(project);
+    }
+    project.format_version = CURRENT_FORMAT_VERSION;
+    Ok(migrations
+        .iter()
+        .map(|migration| migration.description)
+        .collect())
+}
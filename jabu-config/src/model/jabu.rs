@@ -1,18 +1,66 @@
-use super::{JarManifest, ProjectType};
+use super::{CustomTask, JarManifest, Profile, ProjectType};
 use ron::error::SpannedError;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 
 pub const JABU_FILE_NAME: &'static str = "jabu.ron";
 
+/// Version of the layout of the jabu files written by this version of jabu (*see
+/// [`crate::migration::migrate_project`]*).
+pub const CURRENT_FORMAT_VERSION: u32 = 1;
+
 /// Represents the configuration of a Java project.
+///
+/// Only the header of the project (*and its name*) is required, the rest of the
+/// fields take their default values when they aren't given.
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 pub struct JabuProject {
+    /// Version of the layout of the file, which is `0` for the files written
+    /// before it was versioned.
+    #[serde(default)]
+    pub format_version: u32,
+
     pub header: ConfigHeader,
+
+    #[serde(default)]
     pub java_config: JavaConfig,
+
+    #[serde(default)]
     pub manifest: JarManifest,
+
+    #[serde(default)]
     pub fs_schema: FsSchema,
+
+    #[serde(default)]
     pub dependencies: DependenciesConfig,
+
+    /// Tasks executed, in order, when jabu is invoked without a task inside
+    /// of the project.
+    #[serde(default)]
+    pub default_tasks: Vec<String>,
+
+    /// Aliases for running several tasks with a single name. Every task of an alias
+    /// is written as in the command line, along with its default args (*i.e.
+    /// `"ci": ["clean", "test --filter:Fast*", "jar"]`*).
+    #[serde(default)]
+    pub tasks: BTreeMap<String, Vec<String>>,
+
+    /// Tasks defined by the project, by their name.
+    #[serde(default)]
+    pub custom_tasks: BTreeMap<String, CustomTask>,
+
+    /// Overrides of the configuration selected with `--profile` (*i.e. `dev` and
+    /// `release`*), by their name.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// Values that can be used in the strings of the project as `${name}` (*see
+    /// [`crate::interpolation::interpolate_project`]*).
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
 }
 
 impl JabuProject {
@@ -23,18 +71,43 @@ impl JabuProject {
         )
     }
 
+    /// Name (*without extension*) of the jars created for the project (*see
+    /// [`FsSchema::jar_name`]*).
+    pub fn jar_name(&self) -> String {
+        self.fs_schema
+            .jar_name
+            .clone()
+            .unwrap_or_else(|| self.display_name())
+    }
+
     pub fn default_of_name(project_name: impl Into<String>, project_type: ProjectType) -> Self {
         let project_name = project_name.into();
         let mut manifest = HashMap::new();
-        manifest.insert("Main-Class".to_string(), "App".to_string());
+        if project_type.is_executable() {
+            manifest.insert("Main-Class".to_string(), "App".to_string());
+        }
         Self {
+            format_version: CURRENT_FORMAT_VERSION,
             header: ConfigHeader::of_proj_name(&project_name),
             manifest: JarManifest::from(manifest),
-            fs_schema: FsSchema::new(project_type),
+            fs_schema: FsSchema::new_of_name(&project_name, project_type),
             java_config: JavaConfig::default(),
             dependencies: DependenciesConfig::default(),
+            default_tasks: project_type.default_tasks(),
+            tasks: BTreeMap::new(),
+            custom_tasks: BTreeMap::new(),
+            profiles: BTreeMap::new(),
+            properties: BTreeMap::new(),
         }
     }
+
+    /// Returns the project with the overrides of the given profile applied, or
+    /// `None` if the project doesn't have such profile.
+    pub fn with_profile(mut self, profile_name: &str) -> Option<Self> {
+        let profile = self.profiles.get(profile_name)?.clone();
+        profile.apply(&mut self);
+        Some(self)
+    }
 }
 
 impl TryFrom<&str> for JabuProject {
@@ -53,18 +126,23 @@ pub struct ConfigHeader {
     pub project_name: String,
 
     /// Author of the project.
+    #[serde(default = "default_author")]
     pub author: String,
 
     /// Description of the project.
+    #[serde(default)]
     pub description: String,
 
     /// Project's license, this can be set to None if it doesn't have.
+    #[serde(default)]
     pub license: Option<String>,
 
     /// Tags set for the project (*ej, 'test', 'compile', 'web'*)
+    #[serde(default)]
     pub tags: Vec<String>,
 
     /// The version of the project.
+    #[serde(default = "default_version")]
     pub version: String,
 }
 
@@ -74,15 +152,23 @@ impl ConfigHeader {
     pub fn of_proj_name(proj_name: &str) -> ConfigHeader {
         Self {
             project_name: proj_name.to_string(),
-            author: String::from("anon"),
+            author: default_author(),
             description: String::from("A Java project."),
             license: None,
             tags: Vec::new(),
-            version: String::from("0.0.1"),
+            version: default_version(),
         }
     }
 }
 
+fn default_author() -> String {
+    String::from("anon")
+}
+
+fn default_version() -> String {
+    String::from("0.0.1")
+}
+
 impl std::fmt::Display for ConfigHeader {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}_{}_{}", self.author, self.project_name, self.version)
@@ -92,6 +178,7 @@ impl std::fmt::Display for ConfigHeader {
 /// Configuration about the jdk to be used on the
 /// project.
 #[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+#[serde(default)]
 pub struct JavaConfig {
     /// Minimum version of the jdk to be used
     /// while working on the project.
@@ -103,6 +190,10 @@ pub struct JavaConfig {
     /// Version that will be compatible with the
     /// produced class objects.
     pub target: u8,
+
+    /// Other arguments passed to the compiler (*i.e. `["-g", "-Werror"]`*).
+    #[serde(default)]
+    pub compiler_args: Vec<String>,
 }
 
 impl Default for JavaConfig {
@@ -111,12 +202,14 @@ impl Default for JavaConfig {
             java_version: 17,
             source: 17,
             target: 17,
+            compiler_args: Vec::new(),
         }
     }
 }
 
 /// Represents the file structure of the project.
 #[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+#[serde(default)]
 pub struct FsSchema {
     /// Directory containing all the source files of the project.
     pub source: String,
@@ -130,6 +223,12 @@ pub struct FsSchema {
     /// This directory contains the resources of the application.
     pub resources: String,
 
+    /// Glob patterns (*relative to the resources directory, i.e. `**/*.properties`*)
+    /// of the resources whose `${project.*}` placeholders (*i.e. `${project.version}`*)
+    /// are replaced with the values of the header when they are copied.
+    #[serde(default)]
+    pub filtered_resources: Vec<String>,
+
     /// Directory where all the scripts are stored.
     pub scripts: String,
 
@@ -148,16 +247,39 @@ pub struct FsSchema {
 
     /// Other directories to create.
     pub other: Vec<String>,
+
+    /// Name (*without extension*) of the jars created for the project, which
+    /// is [`JabuProject::display_name`] by default.
+    #[serde(default)]
+    pub jar_name: Option<String>,
+}
+
+impl Default for FsSchema {
+    /// The file structure of an application, without its sample sources.
+    fn default() -> Self {
+        Self {
+            generated_files: HashMap::new(),
+            ..Self::new(ProjectType::Application)
+        }
+    }
 }
 
 impl FsSchema {
-    pub fn new(_: ProjectType) -> Self {
-        // TODO: Use the ProjectType to generate different projects
-        // depending on it.
+    pub fn new(project_type: ProjectType) -> Self {
+        Self::new_of_name("app", project_type)
+    }
+
+    /// Creates the file structure of a project of the given type, along with its
+    /// sample sources. Libraries place their sources in a package named after
+    /// the project (*see [`java_package_name`]*).
+    pub fn new_of_name(project_name: &str, project_type: ProjectType) -> Self {
+        let package = java_package_name(project_name);
         let mut generated_files = HashMap::new();
-        generated_files.insert(
-            "./src/main/App.java".to_string(),
-            r#"
+        match project_type {
+            ProjectType::Application => {
+                generated_files.insert(
+                    "./src/main/App.java".to_string(),
+                    r#"
 /*
  * Auto-generated file by Jabu.
  */
@@ -167,16 +289,66 @@ public class App {
         System.out.println("Hello World from Jabu!");
     }
 }"#.to_string(),
-        );
+                );
+            }
+            ProjectType::Library => {
+                generated_files.insert(
+                    format!("./src/main/{package}/Library.java"),
+                    format!(
+                        r#"
+/*
+ * Auto-generated file by Jabu.
+ */
+
+package {package};
+
+public class Library {{
+    public static String greeting(String name) {{
+        return "Hello " + name + " from Jabu!";
+    }}
+}}"#
+                    ),
+                );
+            }
+            ProjectType::TestFixtures => {
+                generated_files.insert(
+                    format!("./src/main/{package}/Fixtures.java"),
+                    format!(
+                        r#"
+/*
+ * Auto-generated file by Jabu.
+ *
+ * Helpers to be shared between the tests of other projects, which
+ * depend on this one.
+ */
+
+package {package};
+
+import java.util.List;
+
+public class Fixtures {{
+    public static List<String> sampleNames() {{
+        return List.of("Alice", "Bob", "Carol");
+    }}
+}}"#
+                    ),
+                );
+            }
+            // The sources are in the members of the workspace.
+            ProjectType::MultiModule => (),
+        }
+
         Self {
             source: "./src/main".to_string(),
             target: "./target".to_string(),
             lib: "./lib".to_string(),
             resources: "./src/resources".to_string(),
+            filtered_resources: Vec::new(),
             scripts: "./scripts/".to_string(),
             test: "./src/test".to_string(),
             generated_files,
             other: Vec::new(),
+            jar_name: None,
         }
     }
 
@@ -185,6 +357,17 @@ public class App {
         PathBuf::from(&self.target).join("classes")
     }
 
+    /// Returns the path for the compiled test classes inside of the target dir.
+    pub fn target_test_classes(&self) -> PathBuf {
+        PathBuf::from(&self.target).join("test-classes")
+    }
+
+    /// Returns the path for the reports generated when running the tests
+    /// inside of the target dir.
+    pub fn target_test_reports(&self) -> PathBuf {
+        PathBuf::from(&self.target).join("test-reports")
+    }
+
     /// Returns the path for the generated binaries inside of the target
     /// directory.
     pub fn target_bin(&self) -> PathBuf {
@@ -202,6 +385,29 @@ public class App {
     pub fn target_self_contained(&self) -> PathBuf {
         PathBuf::from(&self.target).join("self-contained")
     }
+
+    /// Returns the path of the directory used by jabu to store its own
+    /// bookkeeping files (*i.e. the state of incremental builds*) inside
+    /// of the target directory.
+    pub fn target_jabu(&self) -> PathBuf {
+        PathBuf::from(&self.target).join(".jabu")
+    }
+}
+
+/// Returns the name of the Java package for the sources of the given project:
+/// the name in lowercase, with the characters that aren't allowed replaced by
+/// underscores (*i.e. `my-lib` becomes `my_lib`*).
+pub fn java_package_name(project_name: &str) -> String {
+    let package: String = project_name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if package.starts_with(|c: char| !c.is_ascii_alphabetic()) {
+        format!("_{package}")
+    } else {
+        package
+    }
 }
 
 /// Contains the specification of a jabu artifact, meaning that it holds
@@ -242,19 +448,46 @@ impl Into<String> for ArtifactSpec {
 
 impl TryFrom<&str> for ArtifactSpec {
     type Error = ();
+
+    /// Parses either a jabu specification (*`author_artifact_version`*) or Maven
+    /// coordinates (*`group:artifact:version`*).
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let splitted = value.splitn(3, '_').collect::<Vec<&str>>();
+        match spec_separators(value).1[..] {
+            [first, second, ..] => Ok(ArtifactSpec {
+                author: value[..first].to_string(),
+                artifact_id: value[first + 1..second].to_string(),
+                version: value[second + 1..].to_string(),
+            }),
+            _ => Err(()),
+        }
+    }
+}
 
-        if splitted.len() < 3 {
-            Err(())
-        } else {
-            Ok(ArtifactSpec {
-                author: splitted[0].to_string(),
-                artifact_id: splitted[1].to_string(),
-                version: splitted[2].to_string(),
-            })
+/// Returns the separator of an artifact specification and its positions: the
+/// colons of Maven coordinates, or else the underscores of a jabu specification.
+///
+/// The characters inside of interpolations are ignored, since they are replaced
+/// once the project is parsed (*i.e. `me_lib_${env.VERSION:-1.0}`*).
+fn spec_separators(value: &str) -> (char, Vec<usize>) {
+    let mut depth = 0;
+    let mut outside = Vec::new();
+    for (index, c) in value.char_indices() {
+        if value[index..].starts_with("${") {
+            depth += 1;
+        } else if depth > 0 && c == '}' {
+            depth -= 1;
+        } else if depth == 0 {
+            outside.push((index, c));
         }
     }
+
+    let separator = if outside.iter().any(|(_, c)| *c == ':') { ':' } else { '_' };
+    let positions = outside
+        .into_iter()
+        .filter(|(_, c)| *c == separator)
+        .map(|(index, _)| index)
+        .collect();
+    (separator, positions)
 }
 
 impl Serialize for ArtifactSpec {
@@ -262,9 +495,15 @@ impl Serialize for ArtifactSpec {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(
-            format!("{}_{}_{}", self.author, self.artifact_id, self.version).as_str(),
-        )
+        // Maven artifact ids may contain underscores (*i.e. `cats-core_2.13`*), which
+        // can only be parsed back from the coordinates.
+        if self.author.contains('_') || self.artifact_id.contains('_') {
+            serializer.serialize_str(&self.to_maven_coordinates())
+        } else {
+            serializer.serialize_str(
+                format!("{}_{}_{}", self.author, self.artifact_id, self.version).as_str(),
+            )
+        }
     }
 }
 
@@ -280,10 +519,20 @@ impl ArtifactSpec {
             version: version.into(),
         }
     }
+
+    /// Returns the specification as Maven coordinates (*`group:artifact:version`*),
+    /// where the author is the group id.
+    pub fn to_maven_coordinates(&self) -> String {
+        format!("{}:{}:{}", self.author, self.artifact_id, self.version)
+    }
 }
 
+/// URL of Maven Central, the default Maven repository.
+pub const MAVEN_CENTRAL_URL: &str = "https://repo.maven.apache.org/maven2";
+
 /// Represents the configuration of the project's dependencies.
-#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
 pub struct DependenciesConfig {
     /// List of local dependencies. Jabu will check that these exist in the `lib` dir
     /// with their corresponding name + .jar
@@ -293,4 +542,121 @@ pub struct DependenciesConfig {
     /// URL of the repository from where to fetch from**, and the value, being a list
     /// are the dependencies to fetch.
     pub remote: Vec<ArtifactSpec>,
+
+    /// Other jabu projects (*i.e. members of the same workspace*) the project depends on,
+    /// as paths relative to the directory of the project. Their classes are added to
+    /// the classpath.
+    #[serde(default)]
+    pub path: Vec<String>,
+
+    /// Dependencies fetched from the Maven repositories, written as Maven coordinates
+    /// (*`group:artifact:version`*).
+    #[serde(default, with = "maven_coordinates")]
+    pub maven: Vec<ArtifactSpec>,
+
+    /// URLs of the Maven-layout repositories (*`http(s)://` or `file://`*) from where
+    /// the Maven dependencies are fetched, in order of preference.
+    #[serde(default = "default_maven_repositories")]
+    pub maven_repositories: Vec<String>,
+
+    /// Transitive dependencies left out of the Maven dependencies, by the `group:artifact`
+    /// of the dependency they come from. They are written as `group:artifact` too, where
+    /// either part may be `*` to exclude every group or artifact.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub maven_exclusions: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for DependenciesConfig {
+    fn default() -> Self {
+        Self {
+            local: Vec::new(),
+            remote: Vec::new(),
+            path: Vec::new(),
+            maven: Vec::new(),
+            maven_repositories: default_maven_repositories(),
+            maven_exclusions: BTreeMap::new(),
+        }
+    }
+}
+
+impl DependenciesConfig {
+    /// Returns the exclusions (*see [`Self::maven_exclusions`]*) of the given dependency.
+    pub fn exclusions_of(&self, dependency: &ArtifactSpec) -> &[String] {
+        self.maven_exclusions
+            .get(&format!("{}:{}", dependency.author, dependency.artifact_id))
+            .map_or(&[], |exclusions| exclusions.as_slice())
+    }
+}
+
+fn default_maven_repositories() -> Vec<String> {
+    vec![MAVEN_CENTRAL_URL.to_string()]
+}
+
+/// The layout of the repository an artifact comes from.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RepositoryLayout {
+    /// A jabu repository, containing jars and jaburons.
+    #[default]
+    Jabu,
+
+    /// A Maven repository, containing jars and POMs.
+    Maven,
+}
+
+/// (De)serializes a list of [`ArtifactSpec`] as Maven coordinates
+/// (*`group:artifact:version`*), to be used with `#[serde(with = "...")]`.
+pub mod maven_coordinates {
+    use super::{spec_separators, ArtifactSpec};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(specs: &[ArtifactSpec], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(specs.iter().map(|spec| spec.to_maven_coordinates()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<ArtifactSpec>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|coordinates| {
+                Some(coordinates)
+                    .filter(|coordinates| {
+                        matches!(spec_separators(coordinates), (':', positions) if positions.len() == 2)
+                    })
+                    .and_then(|coordinates| ArtifactSpec::try_from(coordinates.as_str()).ok())
+                    .ok_or_else(|| {
+                        serde::de::Error::custom(format!(
+                            "Couldn't parse the Maven coordinates '{coordinates}' (group:artifact:version)"
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    /// Same as [`maven_coordinates`](super::maven_coordinates), for optional lists.
+    pub mod option {
+        use super::ArtifactSpec;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        #[derive(Serialize)]
+        #[serde(transparent)]
+        struct Coordinates<'a>(#[serde(with = "super")] &'a Vec<ArtifactSpec>);
+
+        #[derive(Deserialize)]
+        #[serde(transparent)]
+        struct OwnedCoordinates(#[serde(with = "super")] Vec<ArtifactSpec>);
+
+        pub fn serialize<S: Serializer>(
+            specs: &Option<Vec<ArtifactSpec>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            specs.as_ref().map(Coordinates).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<ArtifactSpec>>, D::Error> {
+            Ok(Option::<OwnedCoordinates>::deserialize(deserializer)?.map(|coordinates| coordinates.0))
+        }
+    }
 }
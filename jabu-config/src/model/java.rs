@@ -1,30 +1,102 @@
-use std::{collections::HashMap, path::Path};
-use serde::{Serialize, Deserialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+use serde::{Serialize, Serializer, Deserialize};
 
 /// Represents the type of Jabu projects for Java.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ProjectType {
     /// Project with the purpose of generating an executable output.
-    Binary,
+    Application,
+
+    /// Project with the purpose of being used by other projects, so its jar
+    /// has no main class.
+    Library,
+
+    /// Workspace grouping several projects (*a library and an application
+    /// depending on it*) that are built together.
+    MultiModule,
+
+    /// Library with helpers (*builders, fakes, sample data...*) meant to be used
+    /// from the tests of other projects.
+    TestFixtures,
+}
+
+impl ProjectType {
+    /// All the project types, in the order they are listed to the user.
+    pub const ALL: [ProjectType; 4] = [
+        Self::Application,
+        Self::Library,
+        Self::MultiModule,
+        Self::TestFixtures,
+    ];
+
+    /// Name of the project type, as given to `jabu new`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Application => "application",
+            Self::Library => "library",
+            Self::MultiModule => "multi-module",
+            Self::TestFixtures => "test-fixtures",
+        }
+    }
+
+    /// Returns `true` if the jar of the project can be executed, so it needs
+    /// a main class.
+    pub fn is_executable(&self) -> bool {
+        matches!(self, Self::Application)
+    }
+
+    /// The tasks executed when jabu is invoked without a task inside of a project
+    /// of this type.
+    pub fn default_tasks(&self) -> Vec<String> {
+        let tasks: &[&str] = match self {
+            Self::Application => &["run"],
+            Self::Library | Self::TestFixtures => &["jar"],
+            Self::MultiModule => &["build"],
+        };
+        tasks.iter().map(|task| task.to_string()).collect()
+    }
 }
 
 impl TryFrom<&str> for ProjectType {
     type Error = ();
     fn try_from(value: &str) -> Result<ProjectType, Self::Error> {
         match value.to_lowercase().as_str() {
-            "binary" | "executable" | "bin" => Ok(Self::Binary),
+            "application" | "app" | "binary" | "executable" | "bin" => Ok(Self::Application),
+            "library" | "lib" => Ok(Self::Library),
+            "multi-module" | "multimodule" | "workspace" => Ok(Self::MultiModule),
+            "test-fixtures" | "fixtures" => Ok(Self::TestFixtures),
             _ => Err(()),
         }
     }
 }
 
+impl std::fmt::Display for ProjectType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 /// Represents a manifest for Jar file, containing a `HashMap<String, String>`
 /// made of keys and values.
 #[derive(Default, PartialEq, Debug, Clone, Deserialize, Serialize)]
 pub struct JarManifest {
+    #[serde(default, serialize_with = "sorted_entries")]
     pub contents: HashMap<String, String>,
 }
 
+/// Serializes the entries of a map sorted by key, so the same entries are always
+/// written in the same order (*the order of a `HashMap` changes between executions*).
+/// To be used with `#[serde(serialize_with = "...")]`.
+pub fn sorted_entries<S: Serializer>(
+    map: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(map.iter().collect::<BTreeMap<&String, &String>>())
+}
+
 impl JarManifest {
     pub fn write_to_file(&self, file_path: impl AsRef<Path>) -> std::io::Result<()> {
         std::fs::write(file_path, self.to_string())
@@ -35,14 +107,52 @@ impl JarManifest {
     }
 }
 
+/// Version of the manifest specification, written when the manifest doesn't specify one.
+pub const MANIFEST_VERSION: &str = "1.0";
+
+/// Maximum length (*in bytes, without the line break*) of the lines of a manifest.
+const MANIFEST_LINE_LENGTH: usize = 72;
+
 impl ToString for JarManifest {
+    /// Returns the contents of the manifest as the jar specification defines them:
+    /// `Manifest-Version` first, the rest of the attributes sorted by name, and the
+    /// lines wrapped at 72 bytes, with `\r\n` as line break.
     fn to_string(&self) -> String {
-        self.contents
+        let version = self
+            .get("Manifest-Version")
+            .map(String::as_str)
+            .unwrap_or(MANIFEST_VERSION);
+        let mut attributes = self
+            .contents
             .iter()
-            .map(|(k, v)| format!("{k}: {v}"))
-            .collect::<Vec<String>>()
-            .join("\n") + "\n"
+            .filter(|(k, _)| k.as_str() != "Manifest-Version")
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect::<Vec<(&str, &str)>>();
+        attributes.sort();
+
+        std::iter::once(("Manifest-Version", version))
+            .chain(attributes)
+            .map(|(k, v)| wrap_manifest_line(&format!("{k}: {v}")))
+            .collect::<String>()
+            + "\r\n"
+    }
+}
+
+/// Splits the line into lines of at most [`MANIFEST_LINE_LENGTH`] bytes, where every
+/// continuation line starts with a space. Characters are never split.
+fn wrap_manifest_line(line: &str) -> String {
+    let mut wrapped = String::with_capacity(line.len() + 2);
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > MANIFEST_LINE_LENGTH {
+            wrapped.push_str("\r\n ");
+            length = 1;
+        }
+        wrapped.push(c);
+        length += c.len_utf8();
     }
+    wrapped + "\r\n"
 }
 
 impl From<HashMap<String, String>> for JarManifest {
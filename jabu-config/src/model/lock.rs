@@ -0,0 +1,57 @@
+use super::{maven_coordinates, ArtifactSpec, RepositoryLayout};
+use ron::error::SpannedError;
+use serde::{Deserialize, Serialize};
+
+pub const JABU_LOCK_FILE_NAME: &str = "jabu.lock";
+
+/// Represents the lockfile of a project (*`jabu.lock`*), which pins the
+/// whole resolved dependency graph so that every build uses exactly the
+/// same artifacts.
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Clone)]
+pub struct LockFile {
+    /// The remote dependencies declared by the project when the lockfile was
+    /// generated. If they change, the dependency graph has to be resolved again.
+    pub dependencies: Vec<ArtifactSpec>,
+
+    /// The Maven dependencies declared by the project when the lockfile was generated.
+    #[serde(default, with = "maven_coordinates")]
+    pub maven_dependencies: Vec<ArtifactSpec>,
+
+    /// Every resolved artifact (*direct and transitive*).
+    pub artifacts: Vec<LockedArtifact>,
+}
+
+impl TryFrom<&str> for LockFile {
+    type Error = SpannedError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ron::from_str(value)
+    }
+}
+
+impl LockFile {
+    /// Returns the locked artifact with the given specification, if any.
+    pub fn get(&self, spec: &ArtifactSpec) -> Option<&LockedArtifact> {
+        self.artifacts.iter().find(|artifact| artifact.spec == *spec)
+    }
+}
+
+/// An artifact pinned by the lockfile.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct LockedArtifact {
+    /// The resolved artifact.
+    pub spec: ArtifactSpec,
+
+    /// URL of the repository the artifact is fetched from.
+    pub source: String,
+
+    /// Layout of the repository the artifact is fetched from.
+    #[serde(default)]
+    pub layout: RepositoryLayout,
+
+    /// SHA-256 of the artifact's jar, as a lowercase hexadecimal string.
+    pub jar_sha256: String,
+
+    /// SHA-256 of the artifact's jaburon, as a lowercase hexadecimal string.
+    pub jaburon_sha256: String,
+}
@@ -1,5 +1,13 @@
 pub mod jabu;
 pub mod java;
+pub mod lock;
+pub mod profile;
+pub mod tasks;
+pub mod workspace;
 
 pub use jabu::*;
 pub use java::*;
+pub use lock::*;
+pub use profile::*;
+pub use tasks::*;
+pub use workspace::*;
@@ -0,0 +1,122 @@
+use super::{maven_coordinates, sorted_entries, ArtifactSpec, JabuProject};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Set of overrides of the configuration of a project (*i.e. a `release` profile
+/// with `-Werror` and no debug info*), declared in the `profiles` section of the
+/// project and selected with `--profile`.
+///
+/// The fields that a profile doesn't give keep the values of the project.
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Clone)]
+pub struct Profile {
+    #[serde(default)]
+    pub java_config: JavaConfigOverrides,
+
+    /// Entries added to the manifest of the project, replacing the ones with
+    /// the same key.
+    #[serde(default, serialize_with = "sorted_entries")]
+    pub manifest: HashMap<String, String>,
+
+    #[serde(default)]
+    pub fs_schema: FsSchemaOverrides,
+
+    #[serde(default)]
+    pub dependencies: DependenciesOverrides,
+}
+
+/// Overrides of [`super::JavaConfig`].
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Clone)]
+pub struct JavaConfigOverrides {
+    #[serde(default)]
+    pub java_version: Option<u8>,
+
+    #[serde(default)]
+    pub source: Option<u8>,
+
+    #[serde(default)]
+    pub target: Option<u8>,
+
+    #[serde(default)]
+    pub compiler_args: Option<Vec<String>>,
+}
+
+/// Overrides of [`super::FsSchema`].
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Clone)]
+pub struct FsSchemaOverrides {
+    #[serde(default)]
+    pub source: Option<String>,
+
+    #[serde(default)]
+    pub target: Option<String>,
+
+    #[serde(default)]
+    pub lib: Option<String>,
+
+    #[serde(default)]
+    pub resources: Option<String>,
+
+    #[serde(default)]
+    pub test: Option<String>,
+
+    #[serde(default)]
+    pub jar_name: Option<String>,
+}
+
+/// Overrides of [`super::DependenciesConfig`].
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Clone)]
+pub struct DependenciesOverrides {
+    #[serde(default)]
+    pub local: Option<Vec<ArtifactSpec>>,
+
+    #[serde(default)]
+    pub remote: Option<Vec<ArtifactSpec>>,
+
+    #[serde(default)]
+    pub path: Option<Vec<String>>,
+
+    /// Written as Maven coordinates (*`group:artifact:version`*).
+    #[serde(default, with = "maven_coordinates::option")]
+    pub maven: Option<Vec<ArtifactSpec>>,
+
+    #[serde(default)]
+    pub maven_repositories: Option<Vec<String>>,
+}
+
+impl Profile {
+    /// Applies the overrides of the profile to the given project.
+    pub fn apply(&self, project: &mut JabuProject) {
+        fn set<T: Clone>(field: &mut T, value: &Option<T>) {
+            if let Some(value) = value {
+                *field = value.clone();
+            }
+        }
+
+        let java_config = &mut project.java_config;
+        set(&mut java_config.java_version, &self.java_config.java_version);
+        set(&mut java_config.source, &self.java_config.source);
+        set(&mut java_config.target, &self.java_config.target);
+        set(&mut java_config.compiler_args, &self.java_config.compiler_args);
+
+        project.manifest.contents.extend(self.manifest.clone());
+
+        let fs_schema = &mut project.fs_schema;
+        set(&mut fs_schema.source, &self.fs_schema.source);
+        set(&mut fs_schema.target, &self.fs_schema.target);
+        set(&mut fs_schema.lib, &self.fs_schema.lib);
+        set(&mut fs_schema.resources, &self.fs_schema.resources);
+        set(&mut fs_schema.test, &self.fs_schema.test);
+        if self.fs_schema.jar_name.is_some() {
+            fs_schema.jar_name = self.fs_schema.jar_name.clone();
+        }
+
+        let dependencies = &mut project.dependencies;
+        set(&mut dependencies.local, &self.dependencies.local);
+        set(&mut dependencies.remote, &self.dependencies.remote);
+        set(&mut dependencies.path, &self.dependencies.path);
+        set(&mut dependencies.maven, &self.dependencies.maven);
+        set(
+            &mut dependencies.maven_repositories,
+            &self.dependencies.maven_repositories,
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// Task declared in the `custom_tasks` section of a project, which is executed
+/// like any other jabu task: it can have options, depend on other tasks, and be
+/// depended on.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct CustomTask {
+    /// Description of the task, shown by `jabu help`.
+    pub description: String,
+
+    /// What the task executes.
+    pub run: CustomTaskAction,
+
+    /// Options accepted by the task.
+    #[serde(default)]
+    pub options: Vec<CustomTaskOption>,
+
+    /// Tasks executed before this one, written as in the command line (*i.e.
+    /// `"build"` or `"test --filter:*IT"`*).
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+
+    /// Tasks (*i.e. `jar`*) before which this task is executed, whenever they
+    /// are executed.
+    #[serde(default)]
+    pub before: Vec<String>,
+
+    /// Tasks (*i.e. `build`*) after which this task is executed, whenever they
+    /// are executed.
+    #[serde(default)]
+    pub after: Vec<String>,
+
+    /// Files and directories read by the task. If they haven't changed since the
+    /// last execution of the task, and neither have its `outputs`, the task is
+    /// skipped. Tasks without inputs are always executed.
+    #[serde(default)]
+    pub inputs: Vec<String>,
+
+    /// Files and directories written by the task.
+    #[serde(default)]
+    pub outputs: Vec<String>,
+}
+
+/// What a [`CustomTask`] executes.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub enum CustomTaskAction {
+    /// A rhai script of the project's scripts directory, given by its name (*i.e.
+    /// `generate` for `scripts/generate.rhai`*).
+    Script(String),
+
+    /// A command line, executed in the directory of the project (*i.e.
+    /// `"protoc --java_out=src/main proto/model.proto"`*).
+    Command(String),
+}
+
+/// Option of a [`CustomTask`], given as `--name` or `--name:value`.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct CustomTaskOption {
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Whether the option takes a value.
+    #[serde(default)]
+    pub has_arg: bool,
+
+    #[serde(default)]
+    pub required: bool,
+
+    /// Value of the option when it's not given.
+    #[serde(default)]
+    pub default_value: Option<String>,
+}
@@ -0,0 +1,58 @@
+use super::{JabuProject, ProjectType};
+use ron::error::SpannedError;
+use serde::{Deserialize, Serialize};
+
+pub const JABU_WORKSPACE_FILE_NAME: &str = "jabu-workspace.ron";
+
+/// Represents a workspace (*`jabu-workspace.ron`*), which groups related jabu
+/// projects, so that they can be built together.
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Clone)]
+pub struct Workspace {
+    /// Directories of the member projects, relative to the directory of the workspace.
+    pub members: Vec<String>,
+
+    /// Tasks executed, in order, when jabu is invoked without a task inside
+    /// of the workspace.
+    #[serde(default)]
+    pub default_tasks: Vec<String>,
+}
+
+impl Workspace {
+    /// Creates the workspace of a new multi-module project along with its members:
+    /// a library (*`core`*) and an application (*`app`*) that depends on it.
+    pub fn new_multi_module() -> (Self, Vec<JabuProject>) {
+        let core = JabuProject::default_of_name("core", ProjectType::Library);
+        let mut app = JabuProject::default_of_name("app", ProjectType::Application);
+        app.dependencies.path.push("../core".to_string());
+        app.fs_schema.generated_files.insert(
+            "./src/main/App.java".to_string(),
+            r#"
+/*
+ * Auto-generated file by Jabu.
+ */
+
+import core.Library;
+
+public class App {
+    public static void main(String[] args) {
+        System.out.println(Library.greeting("World"));
+    }
+}"#
+            .to_string(),
+        );
+
+        let workspace = Self {
+            members: vec![core.header.project_name.clone(), app.header.project_name.clone()],
+            default_tasks: ProjectType::MultiModule.default_tasks(),
+        };
+        (workspace, vec![core, app])
+    }
+}
+
+impl TryFrom<&str> for Workspace {
+    type Error = SpannedError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ron::from_str(value)
+    }
+}
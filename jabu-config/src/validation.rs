@@ -0,0 +1,458 @@
+use crate::{
+    format::ConfigFormat,
+    fsutils::{project_file, ProjectLoadingError},
+    interpolation::interpolate_project,
+    migration::migrate_project,
+    model::{ArtifactSpec, JabuProject, CURRENT_FORMAT_VERSION},
+};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+/// How serious a [`Diagnostic`] is. Projects with errors can't be used by the
+/// tasks, while warnings are only reported by `jabu config check`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Position of a problem inside of the jabu file. Both the line and the column
+/// start at 1.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+
+    /// Number of characters marked from the column on.
+    pub length: usize,
+}
+
+/// A problem found in the configuration of a project.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+
+    /// Where the problem is in the jabu file, if it could be located.
+    pub location: Option<Location>,
+}
+
+/// The problems found in the jabu file of a project (*see [`check_project`]*).
+#[derive(Debug)]
+pub struct ProjectDiagnostics {
+    /// Path to the jabu file.
+    pub file: PathBuf,
+
+    /// Contents of the jabu file.
+    pub source: String,
+
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ProjectDiagnostics {
+    /// Returns whether any of the problems is an error.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    /// Renders the problem along with a snippet of the jabu file, marking where
+    /// it is with carets:
+    ///
+    /// ```text
+    /// error: the source version (21) is greater than the target version (17)
+    ///  --> jabu.ron:12:17
+    ///    |
+    /// 12 |         source: 21,
+    ///    |                 ^^
+    /// ```
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let header = format!("{}: {}", diagnostic.severity, diagnostic.message);
+        let file = self.file.to_string_lossy();
+        let Some(location) = diagnostic.location else {
+            return format!("{header}\n --> {file}");
+        };
+
+        let line = self
+            .source
+            .lines()
+            .nth(location.line - 1)
+            .unwrap_or_default();
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+        format!(
+            "{header}\n{gutter}--> {file}:{}:{}\n{gutter} |\n{number} | {line}\n{gutter} | {}{}",
+            location.line,
+            location.column,
+            " ".repeat(location.column - 1),
+            "^".repeat(location.length.max(1)),
+        )
+    }
+}
+
+impl std::fmt::Display for ProjectDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.diagnostics.len();
+        writeln!(
+            f,
+            "{count} problem{} found in '{}':",
+            if count == 1 { "" } else { "s" },
+            self.file.to_string_lossy()
+        )?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n{}\n", self.render(diagnostic))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ProjectDiagnostics {}
+
+/// Loads the jabu file of the project located in `directory` and collects all of
+/// its problems: the syntax errors, an unsupported format version, the undefined
+/// variables (*see [`interpolate_project`]*) and the semantic problems (*i.e. a
+/// main class that doesn't exist, or a dependency declared twice*), both in the
+/// project and in the project with each of its profiles applied. If a `profile` is
+/// given, only the project with that profile applied is checked instead.
+///
+/// Only fails if the jabu file can't be read, or if there are several of them
+/// (*see [`project_file`]*).
+pub fn check_project(
+    directory: &Path,
+    profile: Option<&str>,
+) -> Result<ProjectDiagnostics, ProjectLoadingError> {
+    let (file, format) = project_file(directory)?;
+    let source = std::fs::read_to_string(&file)?;
+    let diagnostics = project_diagnostics(&source, format, directory, profile);
+    Ok(ProjectDiagnostics {
+        file,
+        source,
+        diagnostics,
+    })
+}
+
+fn project_diagnostics(
+    source: &str,
+    format: ConfigFormat,
+    directory: &Path,
+    profile: Option<&str>,
+) -> Vec<Diagnostic> {
+    let locator = Locator {
+        source,
+        prefix: String::new(),
+    };
+    let mut project: JabuProject = match format.deserialize(source) {
+        Ok(project) => project,
+        Err(e) => {
+            return vec![Diagnostic {
+                severity: Severity::Error,
+                message: e.message,
+                location: Some(Location {
+                    line: e.line,
+                    column: e.column,
+                    length: 1,
+                }),
+            }]
+        }
+    };
+
+    if let Err(ProjectLoadingError::UnsupportedFormatVersion(format_version)) =
+        migrate_project(&mut project)
+    {
+        return vec![locator.error(
+            "format_version",
+            format!(
+                "the format version {format_version} is newer than the latest one supported ({CURRENT_FORMAT_VERSION})"
+            ),
+        )];
+    }
+
+    match interpolate_project(&mut project, &|name| std::env::var(name).ok()) {
+        Err(ProjectLoadingError::UndefinedVariable { field, variable }) => {
+            return vec![locator.error(&field, format!("the variable '{variable}' is undefined"))]
+        }
+        Err(ProjectLoadingError::CircularVariable { field, variable }) => {
+            return vec![locator.error(
+                &field,
+                format!("the variable '{variable}' is defined in terms of itself"),
+            )]
+        }
+        _ => (),
+    }
+
+    // With a profile, only the project with it applied is checked. A profile that
+    // doesn't exist is reported when the project is loaded.
+    let selected = profile.and_then(|name| project.profiles.get_key_value(name));
+    let mut diagnostics = match selected {
+        Some(_) => Vec::new(),
+        None => validate_project(&project, directory, &locator),
+    };
+    let profiles: Vec<_> = match (profile, selected) {
+        (_, Some(selected)) => vec![selected],
+        (Some(_), None) => Vec::new(),
+        (None, None) => project.profiles.iter().collect(),
+    };
+    for (name, profile) in profiles {
+        let mut profiled = project.clone();
+        profile.apply(&mut profiled);
+        let locator = Locator {
+            source,
+            prefix: format!("profiles.{name}."),
+        };
+        for mut diagnostic in validate_project(&profiled, directory, &locator) {
+            if diagnostics
+                .iter()
+                .all(|found| found.message != diagnostic.message)
+            {
+                diagnostic.message = format!("{} (with the profile '{name}')", diagnostic.message);
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Returns the semantic problems of the project located in `directory`, which
+/// are located in the jabu file by the `locator`.
+fn validate_project(project: &JabuProject, directory: &Path, locator: &Locator) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let java_config = &project.java_config;
+    if java_config.source > java_config.target {
+        diagnostics.push(locator.error(
+            "java_config.source",
+            format!(
+                "the source version ({}) is greater than the target version ({})",
+                java_config.source, java_config.target
+            ),
+        ));
+    }
+    if java_config.target > java_config.java_version {
+        diagnostics.push(locator.error(
+            "java_config.target",
+            format!(
+                "the target version ({}) is greater than the java version ({})",
+                java_config.target, java_config.java_version
+            ),
+        ));
+    }
+
+    if !is_valid_version(&project.header.version) {
+        diagnostics.push(locator.error(
+            "header.version",
+            format!(
+                "'{}' isn't a valid version (i.e. '1.2.0' or '2.0-beta')",
+                project.header.version
+            ),
+        ));
+    }
+
+    // The local dependencies are expected to be in the libraries directory, which
+    // is created by `deps fetch` for the rest.
+    let fs_schema = &project.fs_schema;
+    if !directory.join(&fs_schema.lib).is_dir() {
+        let message = format!("the libraries directory '{}' doesn't exist", fs_schema.lib);
+        diagnostics.push(if project.dependencies.local.is_empty() {
+            locator.warning("fs_schema.lib", message)
+        } else {
+            locator.error("fs_schema.lib", message)
+        });
+    }
+
+    if let Some(main_class) = project.manifest.get("Main-Class") {
+        // Nested classes (*i.e. `App$Main`*) are declared in the file of the outer class.
+        let outer_class = main_class.split('$').next().unwrap_or_default();
+        let class_file = directory
+            .join(&fs_schema.source)
+            .join(format!("{}.java", outer_class.replace('.', "/")));
+        if !class_file.is_file() {
+            diagnostics.push(locator.error(
+                "manifest.contents.Main-Class",
+                format!(
+                    "the main class '{main_class}' doesn't exist in '{}'",
+                    fs_schema.source
+                ),
+            ));
+        }
+    }
+
+    let dependencies = &project.dependencies;
+    let mut declared = HashSet::new();
+    for (field, specs) in [
+        ("dependencies.local", &dependencies.local),
+        ("dependencies.remote", &dependencies.remote),
+        ("dependencies.maven", &dependencies.maven),
+    ] {
+        for (index, spec) in specs.iter().enumerate() {
+            if !declared.insert((&spec.author, &spec.artifact_id)) {
+                let occurrence = specs[..index].iter().filter(|other| *other == spec).count();
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    message: format!(
+                        "the dependency '{}:{}' is declared more than once",
+                        spec.author, spec.artifact_id
+                    ),
+                    location: locator.locate_spec(field, spec, occurrence),
+                });
+            }
+        }
+    }
+
+    // An option declared twice would replace the first one.
+    for (name, task) in &project.custom_tasks {
+        let mut option_names = HashSet::new();
+        for option in &task.options {
+            if !option_names.insert(&option.name) {
+                diagnostics.push(locator.error(
+                    &format!("custom_tasks.{name}.options"),
+                    format!(
+                        "the option '{}' of the custom task '{name}' is declared more than once",
+                        option.name
+                    ),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Whether the version is a version as accepted by the repositories: up to three
+/// numbers separated by dots, optionally followed by a pre-release or build
+/// metadata (*i.e. `1.2.0`, `2` or `2.0-beta`*).
+fn is_valid_version(version: &str) -> bool {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let numbers = core.split('.').collect::<Vec<&str>>();
+    numbers.len() <= 3
+        && numbers
+            .iter()
+            .all(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        && version[core.len()..]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-+.".contains(c))
+        && !version.ends_with(['-', '+', '.'])
+}
+
+/// Finds the fields of a project in the source of its jabu file, by searching for
+/// each part of their path (*i.e. `java_config.source`*) after the previous one.
+struct Locator<'a> {
+    source: &'a str,
+
+    /// Path to which the fields are relative (*i.e. the one of a profile*). Fields
+    /// not found under it are searched in the root of the project.
+    prefix: String,
+}
+
+impl Locator<'_> {
+    fn error(&self, field: &str, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            message,
+            location: self.locate(field),
+        }
+    }
+
+    fn warning(&self, field: &str, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            message,
+            location: self.locate(field),
+        }
+    }
+
+    /// Returns the location of the value of the field.
+    fn locate(&self, field: &str) -> Option<Location> {
+        let end = self.field_end(field)?;
+        let value = &self.source[end..];
+        let start = end + (value.len() - value.trim_start().len());
+        let value = &self.source[start..];
+        let length = if let Some(quoted) = value.strip_prefix('"') {
+            quoted.find('"').map_or(1, |length| length + 2)
+        } else if value.starts_with(['(', '[', '{']) {
+            1
+        } else {
+            value
+                .find([',', ')', ']', '}', '\n'])
+                .unwrap_or(value.len())
+        };
+        Some(self.location(start, value[..length].trim_end().chars().count()))
+    }
+
+    /// Returns the location of an artifact of a list of dependencies, which may be
+    /// written as a jabu specification or as Maven coordinates. `occurrence` is the
+    /// number of times it's declared before in the list.
+    fn locate_spec(&self, field: &str, spec: &ArtifactSpec, occurrence: usize) -> Option<Location> {
+        let end = self.field_end(field)?;
+        [spec.to_string(), spec.to_maven_coordinates()]
+            .iter()
+            .find_map(|written| {
+                let quoted = format!("\"{written}\"");
+                let (start, _) = self.source[end..].match_indices(&quoted).nth(occurrence)?;
+                Some(self.location(end + start, quoted.chars().count()))
+            })
+    }
+
+    /// Returns the offset right after the colon following the field.
+    fn field_end(&self, field: &str) -> Option<usize> {
+        let prefixed = format!("{}{field}", self.prefix);
+        let find = |path: &str| {
+            path.split('.')
+                .map(|part| part.split('[').next().unwrap_or(part))
+                .try_fold(0, |offset, key| find_key(self.source, offset, key))
+        };
+        // The fields a profile doesn't override are written as `None`.
+        find(&prefixed)
+            .filter(|end| !self.source[*end..].trim_start().starts_with("None"))
+            .or_else(|| find(field))
+    }
+
+    fn location(&self, offset: usize, length: usize) -> Location {
+        let before = &self.source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        Location {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            length,
+        }
+    }
+}
+
+/// Returns the offset after the separator (*i.e. the colon*) of the first key (*an
+/// identifier, or a quoted string*) named `key` from `offset` on.
+fn find_key(source: &str, offset: usize, key: &str) -> Option<usize> {
+    let is_identifier = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    source[offset..]
+        .match_indices(key)
+        .map(|(index, _)| offset + index)
+        .find_map(|start| {
+            let end = start + key.len();
+            let before = source[..start].chars().next_back();
+            let after = source[end..].chars().next();
+            let (end, separators): (usize, &[char]) = match (before, after) {
+                (Some('"'), Some('"')) => (end + 1, &[':', '=']),
+                // Besides `key = value`, the keys of TOML are written in the headers
+                // of the tables (*i.e. `[profiles.release.java_config]`*).
+                (before, after)
+                    if !before.is_some_and(is_identifier) && !after.is_some_and(is_identifier) =>
+                {
+                    (end, &[':', '=', ']', '.'])
+                }
+                _ => return None,
+            };
+            let rest = source[end..].trim_start();
+            rest.starts_with(separators)
+                .then(|| source.len() - rest.len() + 1)
+        })
+}
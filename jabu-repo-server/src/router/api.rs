@@ -54,6 +54,7 @@ pub async fn get_artifact(
         let item_path = match file_type.as_str() {
             "jar" => app_state.config.jabu_repo.jar_path(&artifact_spec),
             "jaburon" => app_state.config.jabu_repo.jaburon_path(&artifact_spec),
+            "pom" => app_state.config.jabu_repo.pom_path(&artifact_spec),
             _ => return StatusCode::BAD_REQUEST.into_response(),
         };
 
@@ -345,6 +346,25 @@ pub async fn upload_artifact(
         }
     };
 
+    // The POM is optional, it's only sent when publishing with `--with-pom`.
+    let pom_contents = match multipart.next_field().await {
+        Ok(Some(pom_contents)) => match pom_contents.bytes().await {
+            Ok(pom_bytes) => Some(pom_bytes.into_iter().collect::<Vec<u8>>()),
+            Err(e) => {
+                let err_msg =
+                    format!("Couldn't read from the multipart bytes of artifact '{spec}': {e}");
+                log::error!("{err_msg}");
+                return ApiError::new(StatusCode::BAD_REQUEST, err_msg).into_response();
+            }
+        },
+        Ok(None) => None,
+        Err(e) => {
+            let error_msg = format!("Couldn't read from pom multipart for artifact '{spec}' due to the following error: {e}");
+            log::error!("{}", error_msg);
+            return (StatusCode::BAD_REQUEST, generate_api_error(error_msg)).into_response();
+        }
+    };
+
     let description = match JabuProject::try_from(
         String::from_utf8(jaburon_contents.clone())
             .unwrap_or_default()
@@ -362,7 +382,10 @@ pub async fn upload_artifact(
         &spec,
         jar_contents.as_slice(),
         jaburon_contents.as_slice(),
-    ) {
+    ).and_then(|_| match &pom_contents {
+        Some(pom_contents) => app_state.config.jabu_repo.save_pom(&spec, pom_contents),
+        None => Ok(()),
+    }) {
         Ok(_) => {
             if let Err(e) = sqlx::query(
                 "INSERT INTO artifacts (author, artifact_id, version, description)
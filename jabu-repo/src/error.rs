@@ -27,3 +27,97 @@ impl From<std::io::Error> for RepositoryOperationError {
 }
 
 impl std::error::Error for RepositoryOperationError {}
+
+/// An error while resolving the dependency graph of a project.
+#[derive(Debug)]
+pub enum ResolutionError {
+    /// An artifact of the graph couldn't be read from (*or fetched into*)
+    /// the repository.
+    Repository(RepositoryOperationError),
+
+    /// The jaburon of an artifact couldn't be parsed, so its dependencies
+    /// are unknown.
+    InvalidJaburon {
+        artifact: ArtifactSpec,
+        description: String,
+    },
+
+    /// None of the available versions of an artifact fulfills the version
+    /// requirement it has been declared with.
+    NoMatchingVersion {
+        author: String,
+        artifact_id: String,
+        requirement: String,
+        available: Vec<String>,
+    },
+
+    /// The dependency graph contains a cycle. The variant holds the path of the
+    /// cycle, where the first and the last artifact are the same one.
+    Cycle(Vec<ArtifactSpec>),
+}
+
+impl std::fmt::Display for ResolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Repository(e) => write!(f, "{e}"),
+            Self::InvalidJaburon {
+                artifact,
+                description,
+            } => write!(f, "The jaburon of '{artifact}' is invalid: {description}"),
+            Self::NoMatchingVersion {
+                author,
+                artifact_id,
+                requirement,
+                available,
+            } => write!(
+                f,
+                "No version of '{author}_{artifact_id}' matches '{requirement}' (available versions: [{}])",
+                available.join(", ")
+            ),
+            Self::Cycle(path) => write!(
+                f,
+                "Dependency cycle detected: {}",
+                path.iter()
+                    .map(|artifact| artifact.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" -> ")
+            ),
+        }
+    }
+}
+
+impl From<RepositoryOperationError> for ResolutionError {
+    fn from(value: RepositoryOperationError) -> Self {
+        Self::Repository(value)
+    }
+}
+
+impl From<std::io::Error> for ResolutionError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Repository(value.into())
+    }
+}
+
+impl std::error::Error for ResolutionError {}
+
+/// An error while parsing a Maven file (*i.e. a POM or a `maven-metadata.xml`*).
+#[derive(Debug)]
+pub struct MavenParsingError {
+    pub description: String,
+}
+
+impl std::fmt::Display for MavenParsingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid Maven file: {}", self.description)
+    }
+}
+
+impl From<roxmltree::Error> for MavenParsingError {
+    fn from(value: roxmltree::Error) -> Self {
+        Self {
+            description: value.to_string(),
+        }
+    }
+}
+
+impl std::error::Error for MavenParsingError {}
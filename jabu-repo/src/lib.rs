@@ -1,5 +1,8 @@
 pub mod repository;
 pub mod error;
+pub mod maven;
+pub mod resolver;
+pub mod version;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,1021 @@
+use crate::{error::MavenParsingError, version::VersionRequirement};
+use jabu_config::model::{
+    ArtifactSpec, DependenciesConfig, FsSchema, JabuProject, JarManifest, ProjectType,
+};
+use roxmltree::{Document, Node};
+use std::collections::BTreeMap;
+
+/// Maximum length of the chain of parents and imported BOMs of a POM, to avoid
+/// looping forever on malformed repositories.
+const MAX_POM_DEPTH: usize = 32;
+
+/// Returns the path, relative to the root of a Maven repository, of the file of the
+/// given artifact with the given extension (*i.e. `jar` or `pom`*). The author of the
+/// artifact is used as the group id.
+pub fn artifact_path(artifact: &ArtifactSpec, extension: &str) -> String {
+    format!(
+        "{}/{}/{}/{}-{}.{extension}",
+        artifact.author.replace('.', "/"),
+        artifact.artifact_id,
+        artifact.version,
+        artifact.artifact_id,
+        artifact.version
+    )
+}
+
+/// Returns the path, relative to the root of a Maven repository, of the
+/// `maven-metadata.xml` of the given artifact, which lists its versions.
+pub fn metadata_path(group_id: &str, artifact_id: &str) -> String {
+    format!("{}/{artifact_id}/maven-metadata.xml", group_id.replace('.', "/"))
+}
+
+/// Returns the versions listed in the contents of a `maven-metadata.xml`.
+pub fn parse_metadata_versions(xml: &str) -> Result<Vec<String>, MavenParsingError> {
+    let document = Document::parse(xml)?;
+    Ok(document
+        .descendants()
+        .filter(|node| node.has_tag_name("versions"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("version"))
+        .filter_map(|node| node.text())
+        .map(|version| version.trim().to_string())
+        .collect())
+}
+
+/// Translates a Maven version range (*i.e. `[1.0,2.0)`*) into a requirement understood
+/// by [`crate::version::VersionRequirement`]. Plain versions and unions of ranges are
+/// returned as they are.
+pub fn maven_version_requirement(version: &str) -> String {
+    let version = version.trim();
+    let (Some(lower_bound), Some(upper_bound)) = (version.chars().next(), version.chars().last())
+    else {
+        return version.to_string();
+    };
+    let inner = &version[1..version.len() - 1];
+    if !"[(".contains(lower_bound) || !"])".contains(upper_bound) || inner.contains(['[', '(']) {
+        return version.to_string();
+    }
+
+    match inner.split_once(',') {
+        None => inner.trim().to_string(),
+        Some((lower, upper)) => {
+            let mut comparators = Vec::new();
+            if !lower.trim().is_empty() {
+                let operator = if lower_bound == '[' { ">=" } else { ">" };
+                comparators.push(format!("{operator}{}", lower.trim()));
+            }
+            if !upper.trim().is_empty() {
+                let operator = if upper_bound == ']' { "<=" } else { "<" };
+                comparators.push(format!("{operator}{}", upper.trim()));
+            }
+            comparators.join(", ")
+        }
+    }
+}
+
+/// A dependency declared in a POM.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PomDependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    pub dependency_type: Option<String>,
+    pub classifier: Option<String>,
+    pub optional: bool,
+
+    /// The excluded transitive dependencies, as `group:artifact`.
+    pub exclusions: Vec<String>,
+}
+
+impl PomDependency {
+    fn parse(node: Node) -> Self {
+        Self {
+            group_id: child_text(node, "groupId").unwrap_or_default(),
+            artifact_id: child_text(node, "artifactId").unwrap_or_default(),
+            version: child_text(node, "version"),
+            scope: child_text(node, "scope"),
+            dependency_type: child_text(node, "type"),
+            classifier: child_text(node, "classifier"),
+            optional: child_text(node, "optional").is_some_and(|optional| optional == "true"),
+            exclusions: children(node, "exclusions", "exclusion")
+                .map(|exclusion| {
+                    format!(
+                        "{}:{}",
+                        child_text(exclusion, "groupId").unwrap_or_default(),
+                        child_text(exclusion, "artifactId").unwrap_or_default()
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn key(&self) -> (&str, &str) {
+        (&self.group_id, &self.artifact_id)
+    }
+
+    /// Checks if the dependency is required at runtime by the dependents of the
+    /// artifact declaring it, this is, if it's a non optional jar in the `compile`
+    /// or `runtime` scope.
+    pub fn is_transitive(&self) -> bool {
+        matches!(self.scope.as_deref(), None | Some("compile") | Some("runtime"))
+            && !self.optional
+            && matches!(self.dependency_type.as_deref(), None | Some("jar"))
+            && self.classifier.is_none()
+    }
+
+    /// Checks if the dependency is a BOM imported into the dependency management.
+    pub fn is_import(&self) -> bool {
+        self.scope.as_deref() == Some("import") && self.dependency_type.as_deref() == Some("pom")
+    }
+
+    fn interpolate(&mut self, properties: &BTreeMap<String, String>) {
+        self.group_id = interpolate(&self.group_id, properties);
+        self.artifact_id = interpolate(&self.artifact_id, properties);
+        self.version = self
+            .version
+            .as_ref()
+            .map(|version| interpolate(version, properties));
+        self.scope = self.scope.as_ref().map(|scope| interpolate(scope, properties));
+    }
+}
+
+/// The parts of a Maven POM (*`pom.xml`*) used by jabu.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Pom {
+    /// The group id, if it's not declared it's inherited from the parent.
+    pub group_id: Option<String>,
+    pub artifact_id: String,
+
+    /// The version, if it's not declared it's inherited from the parent.
+    pub version: Option<String>,
+    pub packaging: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+
+    /// The coordinates of the parent POM, where the author is the group id.
+    pub parent: Option<ArtifactSpec>,
+    pub properties: BTreeMap<String, String>,
+    pub dependencies: Vec<PomDependency>,
+    pub dependency_management: Vec<PomDependency>,
+
+    /// The names of the licenses.
+    pub licenses: Vec<String>,
+
+    /// The modules of a multi-module project.
+    pub modules: Vec<String>,
+
+    /// The URLs of the repositories declared by the POM.
+    pub repositories: Vec<String>,
+    pub build: PomBuild,
+}
+
+/// The `<build>` section of a POM.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PomBuild {
+    pub source_directory: Option<String>,
+    pub test_source_directory: Option<String>,
+    pub resource_directories: Vec<String>,
+
+    /// The output directory (*`target` by default*).
+    pub directory: Option<String>,
+    pub plugins: Vec<PomPlugin>,
+}
+
+/// A plugin declared in the `<build>` section of a POM.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PomPlugin {
+    pub group_id: Option<String>,
+    pub artifact_id: String,
+
+    /// The leaves of the `<configuration>` of the plugin, where the key is the path
+    /// to the leaf (*i.e. `archive.manifest.mainClass`*).
+    pub configuration: BTreeMap<String, String>,
+}
+
+impl PomPlugin {
+    fn parse(node: Node) -> Self {
+        let mut configuration = BTreeMap::new();
+        if let Some(node) = child(node, "configuration") {
+            flatten_configuration(node, "", &mut configuration);
+        }
+        Self {
+            group_id: child_text(node, "groupId"),
+            artifact_id: child_text(node, "artifactId").unwrap_or_default(),
+            configuration,
+        }
+    }
+}
+
+impl PomBuild {
+    fn inherit(&mut self, parent: PomBuild) {
+        self.source_directory = self.source_directory.take().or(parent.source_directory);
+        self.test_source_directory = self
+            .test_source_directory
+            .take()
+            .or(parent.test_source_directory);
+        self.directory = self.directory.take().or(parent.directory);
+        if self.resource_directories.is_empty() {
+            self.resource_directories = parent.resource_directories;
+        }
+        parent.plugins.into_iter().for_each(|plugin| {
+            if !self
+                .plugins
+                .iter()
+                .any(|declared| declared.artifact_id == plugin.artifact_id)
+            {
+                self.plugins.push(plugin);
+            }
+        });
+    }
+
+    fn interpolate(&mut self, properties: &BTreeMap<String, String>) {
+        self.source_directory
+            .iter_mut()
+            .chain(self.test_source_directory.iter_mut())
+            .chain(self.directory.iter_mut())
+            .chain(self.resource_directories.iter_mut())
+            .chain(
+                self.plugins
+                    .iter_mut()
+                    .flat_map(|plugin| plugin.configuration.values_mut()),
+            )
+            .for_each(|value| *value = interpolate(value, properties));
+    }
+
+    /// Returns the plugin with the given artifact id, if it's declared.
+    pub fn plugin(&self, artifact_id: &str) -> Option<&PomPlugin> {
+        self.plugins
+            .iter()
+            .find(|plugin| plugin.artifact_id == artifact_id)
+    }
+}
+
+fn flatten_configuration(node: Node, prefix: &str, configuration: &mut BTreeMap<String, String>) {
+    node.children()
+        .filter(|child| child.is_element())
+        .for_each(|child| {
+            let key = format!("{prefix}{}", child.tag_name().name());
+            if child.children().any(|grandchild| grandchild.is_element()) {
+                flatten_configuration(child, &format!("{key}."), configuration);
+            } else {
+                configuration.insert(key, child.text().unwrap_or_default().trim().to_string());
+            }
+        });
+}
+
+impl TryFrom<&str> for Pom {
+    type Error = MavenParsingError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let document = Document::parse(value)?;
+        let project = document.root_element();
+        if !project.has_tag_name("project") {
+            return Err(MavenParsingError {
+                description: "the root element of a POM must be <project>".to_string(),
+            });
+        }
+
+        let parent = child(project, "parent").map(|parent| {
+            ArtifactSpec::new(
+                child_text(parent, "groupId").unwrap_or_default(),
+                child_text(parent, "artifactId").unwrap_or_default(),
+                child_text(parent, "version").unwrap_or_default(),
+            )
+        });
+        let properties = child(project, "properties")
+            .into_iter()
+            .flat_map(|properties| properties.children())
+            .filter(|property| property.is_element())
+            .map(|property| {
+                (
+                    property.tag_name().name().to_string(),
+                    property.text().unwrap_or_default().trim().to_string(),
+                )
+            })
+            .collect();
+        let dependencies = |node: Option<Node>| -> Vec<PomDependency> {
+            node.into_iter()
+                .flat_map(|node| children(node, "dependencies", "dependency"))
+                .map(PomDependency::parse)
+                .collect()
+        };
+        let texts = |list: &str, item: &str| -> Vec<String> {
+            children(project, list, item)
+                .filter_map(|node| node.text())
+                .map(|text| text.trim().to_string())
+                .collect()
+        };
+        let build = child(project, "build")
+            .map(|build| PomBuild {
+                source_directory: child_text(build, "sourceDirectory"),
+                test_source_directory: child_text(build, "testSourceDirectory"),
+                resource_directories: children(build, "resources", "resource")
+                    .filter_map(|resource| child_text(resource, "directory"))
+                    .collect(),
+                directory: child_text(build, "directory"),
+                plugins: children(build, "plugins", "plugin")
+                    .map(PomPlugin::parse)
+                    .collect(),
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            group_id: child_text(project, "groupId"),
+            artifact_id: child_text(project, "artifactId").ok_or_else(|| MavenParsingError {
+                description: "the POM doesn't declare an <artifactId>".to_string(),
+            })?,
+            version: child_text(project, "version"),
+            packaging: child_text(project, "packaging"),
+            name: child_text(project, "name"),
+            description: child_text(project, "description"),
+            parent,
+            properties,
+            dependencies: dependencies(Some(project)),
+            dependency_management: dependencies(child(project, "dependencyManagement")),
+            licenses: children(project, "licenses", "license")
+                .filter_map(|license| child_text(license, "name"))
+                .collect(),
+            modules: texts("modules", "module"),
+            repositories: children(project, "repositories", "repository")
+                .filter_map(|repository| child_text(repository, "url"))
+                .collect(),
+            build,
+        })
+    }
+}
+
+impl Pom {
+    /// Returns the coordinates of the POM (*with the group id as author*), inheriting
+    /// the group id and the version from the parent if they aren't declared.
+    pub fn spec(&self) -> ArtifactSpec {
+        let parent = self.parent.clone().unwrap_or(ArtifactSpec::new("", "", ""));
+        ArtifactSpec::new(
+            self.group_id.clone().unwrap_or(parent.author),
+            self.artifact_id.clone(),
+            self.version.clone().unwrap_or(parent.version),
+        )
+    }
+
+    /// Builds the effective POM, this is, the POM after inheriting from its parents,
+    /// importing the BOMs of its dependency management, interpolating the properties
+    /// and completing its dependencies with the dependency management.
+    ///
+    /// `load` is called with the coordinates of every parent and BOM needed.
+    pub fn effective<E, F>(self, mut load: F) -> Result<Pom, E>
+    where
+        E: From<MavenParsingError>,
+        F: FnMut(&ArtifactSpec) -> Result<Pom, E>,
+    {
+        self.effective_with_depth(&mut load, 0)
+    }
+
+    fn effective_with_depth<E, F>(mut self, load: &mut F, depth: usize) -> Result<Pom, E>
+    where
+        E: From<MavenParsingError>,
+        F: FnMut(&ArtifactSpec) -> Result<Pom, E>,
+    {
+        if depth > MAX_POM_DEPTH {
+            return Err(MavenParsingError {
+                description: format!(
+                    "the chain of parents and imports of '{}' is too long (is there a cycle?)",
+                    self.artifact_id
+                ),
+            }
+            .into());
+        }
+
+        if let Some(parent_spec) = self.parent.clone() {
+            let parent = load(&parent_spec)?.effective_with_depth(load, depth + 1)?;
+            self.inherit(parent);
+        }
+
+        let spec = self.spec();
+        self.group_id = Some(spec.author.clone());
+        self.version = Some(spec.version.clone());
+        let mut properties = self.properties.clone();
+        for (prefix, spec) in [("project.", Some(&spec)), ("project.parent.", self.parent.as_ref())]
+            .into_iter()
+            .filter_map(|(prefix, spec)| spec.map(|spec| (prefix, spec)))
+        {
+            properties.insert(format!("{prefix}groupId"), spec.author.clone());
+            properties.insert(format!("{prefix}artifactId"), spec.artifact_id.clone());
+            properties.insert(format!("{prefix}version"), spec.version.clone());
+        }
+        // Paths are relative to the directory of the POM.
+        properties.insert("project.basedir".to_string(), ".".to_string());
+        properties.insert("basedir".to_string(), ".".to_string());
+
+        self.dependencies
+            .iter_mut()
+            .chain(self.dependency_management.iter_mut())
+            .for_each(|dependency| dependency.interpolate(&properties));
+        self.build.interpolate(&properties);
+        self.properties
+            .values_mut()
+            .for_each(|value| *value = interpolate(value, &properties));
+
+        let imports: Vec<PomDependency> = self
+            .dependency_management
+            .iter()
+            .filter(|managed| managed.is_import())
+            .cloned()
+            .collect();
+        self.dependency_management.retain(|managed| !managed.is_import());
+        for import in imports {
+            let bom_spec = ArtifactSpec::new(
+                import.group_id.clone(),
+                import.artifact_id.clone(),
+                import.version.clone().unwrap_or_default(),
+            );
+            let bom = load(&bom_spec)?.effective_with_depth(load, depth + 1)?;
+            bom.dependency_management.into_iter().for_each(|managed| {
+                if !self.manages(managed.key()) {
+                    self.dependency_management.push(managed);
+                }
+            });
+        }
+
+        let dependency_management = self.dependency_management.clone();
+        self.dependencies.iter_mut().for_each(|dependency| {
+            if let Some(managed) = dependency_management
+                .iter()
+                .find(|managed| managed.key() == dependency.key())
+            {
+                if dependency.version.is_none() {
+                    dependency.version = managed.version.clone();
+                }
+                if dependency.scope.is_none() {
+                    dependency.scope = managed.scope.clone();
+                }
+            }
+        });
+
+        Ok(self)
+    }
+
+    fn manages(&self, key: (&str, &str)) -> bool {
+        self.dependency_management
+            .iter()
+            .any(|managed| managed.key() == key)
+    }
+
+    /// Merges the given (*effective*) parent into the POM, where the declarations of
+    /// the POM take precedence over the ones of the parent.
+    fn inherit(&mut self, parent: Pom) {
+        self.group_id = self.group_id.take().or(parent.group_id);
+        self.version = self.version.take().or(parent.version);
+
+        let mut properties = parent.properties;
+        properties.append(&mut self.properties);
+        self.properties = properties;
+
+        parent
+            .dependency_management
+            .into_iter()
+            .for_each(|managed| {
+                if !self.manages(managed.key()) {
+                    self.dependency_management.push(managed);
+                }
+            });
+
+        let mut dependencies: Vec<PomDependency> = parent
+            .dependencies
+            .into_iter()
+            .filter(|inherited| {
+                !self
+                    .dependencies
+                    .iter()
+                    .any(|dependency| dependency.key() == inherited.key())
+            })
+            .collect();
+        dependencies.append(&mut self.dependencies);
+        self.dependencies = dependencies;
+
+        if self.licenses.is_empty() {
+            self.licenses = parent.licenses;
+        }
+        parent.repositories.into_iter().for_each(|repository| {
+            if !self.repositories.contains(&repository) {
+                self.repositories.push(repository);
+            }
+        });
+        self.build.inherit(parent.build);
+    }
+
+    /// Returns the dependencies required at runtime by the dependents of the artifact
+    /// (*see [`PomDependency::is_transitive`]*), with the group id as author.
+    ///
+    /// # Note
+    /// Their exclusions are given by [`Self::transitive_exclusions`], and applied when
+    /// the dependencies of the dependents are resolved.
+    pub fn transitive_dependencies(&self) -> Vec<ArtifactSpec> {
+        self.dependencies
+            .iter()
+            .filter(|dependency| dependency.is_transitive())
+            .filter_map(|dependency| {
+                dependency.version.as_ref().map(|version| {
+                    ArtifactSpec::new(
+                        dependency.group_id.clone(),
+                        dependency.artifact_id.clone(),
+                        maven_version_requirement(version),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the exclusions of the transitive dependencies (*see
+    /// [`DependenciesConfig::maven_exclusions`]*).
+    pub fn transitive_exclusions(&self) -> BTreeMap<String, Vec<String>> {
+        self.dependencies
+            .iter()
+            .filter(|dependency| dependency.is_transitive() && !dependency.exclusions.is_empty())
+            .map(|dependency| {
+                (
+                    format!("{}:{}", dependency.group_id, dependency.artifact_id),
+                    dependency.exclusions.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Generates the jaburon stored in the local repository along the jar of the
+    /// artifact, where its transitive dependencies are Maven dependencies.
+    pub fn to_jabu_project(&self) -> JabuProject {
+        let spec = self.spec();
+        let mut project = JabuProject::default_of_name(&spec.artifact_id, ProjectType::Library);
+        project.header.author = spec.author;
+        project.header.version = spec.version;
+        project.header.description = self
+            .description
+            .clone()
+            .or(self.name.clone())
+            .unwrap_or_default();
+        project.manifest = JarManifest::default();
+        project.dependencies = DependenciesConfig {
+            maven: self.transitive_dependencies(),
+            maven_exclusions: self.transitive_exclusions(),
+            ..DependenciesConfig::default()
+        };
+        project
+    }
+}
+
+/// The result of importing a POM as a jabu project.
+#[derive(Debug, PartialEq)]
+pub struct PomImport {
+    pub project: JabuProject,
+
+    /// Descriptions of the parts of the POM that couldn't be translated, or
+    /// were translated with a different meaning.
+    pub untranslated: Vec<String>,
+}
+
+/// Plugins whose configuration is translated when importing a POM.
+const TRANSLATED_PLUGINS: [&str; 2] = ["maven-compiler-plugin", "maven-jar-plugin"];
+
+impl Pom {
+    /// Translates the (*effective*) POM into an equivalent jabu project:
+    /// - The coordinates, description and license are mapped to the header.
+    /// - The `maven.compiler` properties (*or the configuration of the
+    ///   `maven-compiler-plugin`*) are mapped to the java config.
+    /// - The build directories are mapped to the fs schema.
+    /// - The dependencies are mapped to Maven dependencies, except the ones in the
+    ///   `system` scope, which are mapped to local dependencies.
+    /// - The configuration of the `maven-jar-plugin` is mapped to the manifest, projects
+    ///   without a main class are imported as libraries.
+    pub fn import(&self) -> PomImport {
+        let mut untranslated = Vec::new();
+        let spec = self.spec();
+        let mut project = JabuProject::default_of_name(&spec.artifact_id, ProjectType::Application);
+
+        project.header.author = spec.author;
+        project.header.version = spec.version;
+        project.header.description = self
+            .description
+            .clone()
+            .or(self.name.clone())
+            .unwrap_or_default();
+        project.header.license = self.licenses.first().cloned();
+        if self.licenses.len() > 1 {
+            untranslated.push(format!(
+                "Only the first license is kept, the rest ({}) are dropped.",
+                self.licenses[1..].join(", ")
+            ));
+        }
+        if let Some(packaging) = self.packaging.as_deref().filter(|packaging| *packaging != "jar") {
+            untranslated.push(format!(
+                "The '{packaging}' packaging isn't supported, the project is imported as a jar."
+            ));
+        }
+        if !self.modules.is_empty() {
+            untranslated.push(format!(
+                "The modules ({}) aren't imported, each one has to be imported on its own.",
+                self.modules.join(", ")
+            ));
+        }
+
+        // Java config
+        let compiler = self.build.plugin("maven-compiler-plugin");
+        let compiler_setting = |name: &str| {
+            compiler
+                .and_then(|compiler| compiler.configuration.get(name))
+                .or(self.properties.get(&format!("maven.compiler.{name}")))
+        };
+        let release = compiler_setting("release");
+        for (name, setting, value) in [
+            ("source", compiler_setting("source").or(release), &mut project.java_config.source),
+            ("target", compiler_setting("target").or(release), &mut project.java_config.target),
+        ] {
+            match setting.map(|setting| (setting, parse_java_version(setting))) {
+                Some((_, Some(version))) => *value = version,
+                Some((setting, None)) => untranslated.push(format!(
+                    "The compiler {name} '{setting}' isn't a Java version, {value} is used instead."
+                )),
+                None => untranslated.push(format!(
+                    "There is no compiler {name}, {value} is used by default."
+                )),
+            }
+        }
+        project.java_config.java_version = project
+            .java_config
+            .source
+            .max(project.java_config.target);
+
+        // Fs schema
+        let directory = |directory: Option<&String>, default: &str| {
+            let directory = directory.map(String::as_str).unwrap_or(default);
+            if directory.starts_with('.') || directory.starts_with('/') {
+                directory.to_string()
+            } else {
+                format!("./{directory}")
+            }
+        };
+        project.fs_schema = FsSchema {
+            source: directory(self.build.source_directory.as_ref(), MAVEN_SOURCE_DIRECTORY),
+            test: directory(self.build.test_source_directory.as_ref(), MAVEN_TEST_SOURCE_DIRECTORY),
+            resources: directory(self.build.resource_directories.first(), MAVEN_RESOURCES_DIRECTORY),
+            target: directory(self.build.directory.as_ref(), "target"),
+            generated_files: Default::default(),
+            ..project.fs_schema
+        };
+        if self.build.resource_directories.len() > 1 {
+            untranslated.push(format!(
+                "Only the first resources directory is kept, the rest ({}) are dropped.",
+                self.build.resource_directories[1..].join(", ")
+            ));
+        }
+
+        // Dependencies
+        let mut dependencies = DependenciesConfig::default();
+        self.dependencies.iter().for_each(|dependency| {
+            let coordinates = format!("{}:{}", dependency.group_id, dependency.artifact_id);
+            if !matches!(dependency.dependency_type.as_deref(), None | Some("jar"))
+                || dependency.classifier.is_some()
+            {
+                untranslated.push(format!(
+                    "The dependency {coordinates} isn't a plain jar (type or classifier), it's dropped."
+                ));
+                return;
+            }
+            let Some(version) = &dependency.version else {
+                untranslated.push(format!(
+                    "The dependency {coordinates} has no version (is its parent or BOM missing?), it's dropped."
+                ));
+                return;
+            };
+            if !dependency.exclusions.is_empty() {
+                dependencies
+                    .maven_exclusions
+                    .insert(coordinates.clone(), dependency.exclusions.clone());
+            }
+
+            let artifact = ArtifactSpec::new(
+                dependency.group_id.clone(),
+                dependency.artifact_id.clone(),
+                maven_version_requirement(version),
+            );
+            match dependency.scope.as_deref() {
+                None | Some("compile") | Some("runtime") => dependencies.maven.push(artifact),
+                Some("system") => {
+                    untranslated.push(format!(
+                        "The system dependency {coordinates} is now a local dependency, its jar has to be copied into the lib directory as '{artifact}.jar'."
+                    ));
+                    dependencies.local.push(artifact);
+                }
+                Some(scope) => {
+                    untranslated.push(format!(
+                        "The dependency {coordinates} is in the '{scope}' scope, which isn't supported, so it's a regular dependency."
+                    ));
+                    dependencies.maven.push(artifact);
+                }
+            }
+        });
+        self.repositories.iter().for_each(|repository| {
+            if !dependencies.maven_repositories.contains(repository) {
+                dependencies.maven_repositories.push(repository.clone());
+            }
+        });
+        project.dependencies = dependencies;
+
+        // Manifest
+        let mut manifest = std::collections::HashMap::new();
+        if let Some(jar) = self.build.plugin("maven-jar-plugin") {
+            jar.configuration.iter().for_each(|(key, value)| {
+                if key == "archive.manifest.mainClass" {
+                    manifest.insert("Main-Class".to_string(), value.clone());
+                } else if let Some(entry) = key.strip_prefix("archive.manifestEntries.") {
+                    manifest.insert(entry.to_string(), value.clone());
+                }
+            });
+        }
+        if !manifest.contains_key("Main-Class") {
+            untranslated.push(
+                "There is no main class in the configuration of the maven-jar-plugin, so the project is imported as a library.".to_string(),
+            );
+            project.default_tasks = ProjectType::Library.default_tasks();
+        }
+        project.manifest = JarManifest::from(manifest);
+
+        self.build
+            .plugins
+            .iter()
+            .filter(|plugin| !TRANSLATED_PLUGINS.contains(&plugin.artifact_id.as_str()))
+            .for_each(|plugin| {
+                untranslated.push(format!("The plugin {} isn't supported.", plugin.artifact_id))
+            });
+
+        PomImport {
+            project,
+            untranslated,
+        }
+    }
+}
+
+/// Parses a Java version as written in the compiler settings (*i.e. `1.8` or `17`*).
+fn parse_java_version(version: &str) -> Option<u8> {
+    version
+        .trim()
+        .strip_prefix("1.")
+        .unwrap_or(version.trim())
+        .parse()
+        .ok()
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+/// Returns the `item` children of the `list` child of the node (*i.e. the
+/// `<dependency>` elements of `<dependencies>`*).
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    list: &'a str,
+    item: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    child(node, list)
+        .into_iter()
+        .flat_map(|list| list.children())
+        .filter(move |node| node.has_tag_name(item))
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    child(node, name)
+        .and_then(|child| child.text())
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// Replaces the `${property}` placeholders of the value with the given properties.
+/// Unknown properties are left as they are.
+fn interpolate(value: &str, properties: &BTreeMap<String, String>) -> String {
+    let mut result = value.to_string();
+    // Properties may reference other properties, but not forever.
+    for _ in 0..MAX_POM_DEPTH {
+        let mut replaced = false;
+        let mut output = String::new();
+        let mut rest = result.as_str();
+        while let Some(start) = rest.find("${") {
+            let Some(length) = rest[start..].find('}') else {
+                break;
+            };
+            let name = &rest[start + 2..start + length];
+            output.push_str(&rest[..start]);
+            match properties.get(name) {
+                Some(property) => {
+                    output.push_str(property);
+                    replaced = true;
+                }
+                None => output.push_str(&rest[start..start + length + 1]),
+            }
+            rest = &rest[start + length + 1..];
+        }
+        output.push_str(rest);
+        result = output;
+        if !replaced {
+            break;
+        }
+    }
+    result
+}
+
+/// Directories of the Maven standard layout, which don't have to be declared in a POM.
+const MAVEN_SOURCE_DIRECTORY: &str = "src/main/java";
+const MAVEN_TEST_SOURCE_DIRECTORY: &str = "src/test/java";
+const MAVEN_RESOURCES_DIRECTORY: &str = "src/main/resources";
+
+/// Generates a `pom.xml` equivalent to the given project, so that it can be built
+/// with Maven or published along its jar:
+/// - The header is mapped to the coordinates (*with the author as group id*), the
+///   description and the license.
+/// - The java config is mapped to the `maven.compiler` properties.
+/// - The source, test and resources directories are declared if they don't follow
+///   the Maven standard layout.
+/// - The manifest is mapped to the configuration of the `maven-jar-plugin`.
+/// - Remote and Maven dependencies are declared with their version requirements
+///   translated into Maven ranges, and local dependencies in the `system` scope.
+pub fn generate_pom(project: &JabuProject) -> String {
+    let mut pom = XmlWriter::default();
+    pom.line(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    pom.open_with_attributes(
+        "project",
+        r#"xmlns="http://maven.apache.org/POM/4.0.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd""#,
+    );
+    pom.element("modelVersion", "4.0.0");
+    pom.blank();
+    pom.element("groupId", &project.header.author);
+    pom.element("artifactId", &project.header.project_name);
+    pom.element("version", &project.header.version);
+    pom.element("packaging", "jar");
+    pom.element("name", &project.header.project_name);
+    pom.element("description", &project.header.description);
+    if let Some(license) = &project.header.license {
+        pom.open("licenses");
+        pom.open("license");
+        pom.element("name", license);
+        pom.close("license");
+        pom.close("licenses");
+    }
+
+    pom.blank();
+    pom.open("properties");
+    pom.element("project.build.sourceEncoding", "UTF-8");
+    pom.element("maven.compiler.source", &project.java_config.source.to_string());
+    pom.element("maven.compiler.target", &project.java_config.target.to_string());
+    pom.close("properties");
+
+    let dependencies = &project.dependencies;
+    if !dependencies.local.is_empty() || !dependencies.remote.is_empty() || !dependencies.maven.is_empty() {
+        pom.blank();
+        pom.open("dependencies");
+        dependencies
+            .remote
+            .iter()
+            .chain(dependencies.maven.iter())
+            .for_each(|dependency| {
+                pom.open("dependency");
+                pom.element("groupId", &dependency.author);
+                pom.element("artifactId", &dependency.artifact_id);
+                pom.element(
+                    "version",
+                    &VersionRequirement::from(dependency.version.as_str()).to_maven_version(),
+                );
+                let exclusions = dependencies.exclusions_of(dependency);
+                if !exclusions.is_empty() {
+                    pom.open("exclusions");
+                    exclusions.iter().for_each(|exclusion| {
+                        let (group_id, artifact_id) =
+                            exclusion.split_once(':').unwrap_or((exclusion, "*"));
+                        pom.open("exclusion");
+                        pom.element("groupId", group_id);
+                        pom.element("artifactId", artifact_id);
+                        pom.close("exclusion");
+                    });
+                    pom.close("exclusions");
+                }
+                pom.close("dependency");
+            });
+        dependencies.local.iter().for_each(|dependency| {
+            pom.open("dependency");
+            pom.element("groupId", &dependency.author);
+            pom.element("artifactId", &dependency.artifact_id);
+            pom.element("version", &dependency.version);
+            pom.element("scope", "system");
+            pom.element(
+                "systemPath",
+                &format!(
+                    "${{project.basedir}}/{}/{dependency}.jar",
+                    normalize_directory(&project.fs_schema.lib)
+                ),
+            );
+            pom.close("dependency");
+        });
+        pom.close("dependencies");
+    }
+
+    pom.blank();
+    pom.open("build");
+    let fs_schema = &project.fs_schema;
+    [
+        ("sourceDirectory", &fs_schema.source, MAVEN_SOURCE_DIRECTORY),
+        ("testSourceDirectory", &fs_schema.test, MAVEN_TEST_SOURCE_DIRECTORY),
+    ]
+    .into_iter()
+    .map(|(tag, directory, standard)| (tag, normalize_directory(directory), standard))
+    .filter(|(_, directory, standard)| directory != standard)
+    .for_each(|(tag, directory, _)| pom.element(tag, &directory));
+    let resources = normalize_directory(&fs_schema.resources);
+    if resources != MAVEN_RESOURCES_DIRECTORY {
+        pom.open("resources");
+        pom.open("resource");
+        pom.element("directory", &resources);
+        pom.close("resource");
+        pom.close("resources");
+    }
+
+    let mut manifest: Vec<(&String, &String)> = project.manifest.contents.iter().collect();
+    manifest.sort();
+    if !manifest.is_empty() {
+        pom.open("plugins");
+        pom.open("plugin");
+        pom.element("groupId", "org.apache.maven.plugins");
+        pom.element("artifactId", "maven-jar-plugin");
+        pom.open("configuration");
+        pom.open("archive");
+        if let Some((_, main_class)) = manifest.iter().find(|(key, _)| *key == "Main-Class") {
+            pom.open("manifest");
+            pom.element("mainClass", main_class);
+            pom.close("manifest");
+        }
+        let entries: Vec<_> = manifest.iter().filter(|(key, _)| *key != "Main-Class").collect();
+        if !entries.is_empty() {
+            pom.open("manifestEntries");
+            entries.iter().for_each(|(key, value)| pom.element(key, value));
+            pom.close("manifestEntries");
+        }
+        pom.close("archive");
+        pom.close("configuration");
+        pom.close("plugin");
+        pom.close("plugins");
+    }
+    pom.close("build");
+    pom.close("project");
+    pom.output
+}
+
+/// Normalizes a directory of the fs schema (*i.e. `./src/main/`*) into a path
+/// relative to the project directory (*i.e. `src/main`*).
+fn normalize_directory(directory: &str) -> String {
+    let directory = directory.trim_end_matches('/');
+    directory
+        .strip_prefix("./")
+        .unwrap_or(directory)
+        .to_string()
+}
+
+/// Minimal writer of indented XML documents.
+#[derive(Default)]
+struct XmlWriter {
+    output: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    fn line(&mut self, line: &str) {
+        self.output.push_str(&"  ".repeat(self.depth));
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+
+    fn blank(&mut self) {
+        self.output.push('\n');
+    }
+
+    fn open(&mut self, tag: &str) {
+        self.line(&format!("<{tag}>"));
+        self.depth += 1;
+    }
+
+    fn open_with_attributes(&mut self, tag: &str, attributes: &str) {
+        self.line(&format!("<{tag} {attributes}>"));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{tag}>"));
+    }
+
+    fn element(&mut self, tag: &str, text: &str) {
+        self.line(&format!("<{tag}>{}</{tag}>", escape_xml(text)));
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
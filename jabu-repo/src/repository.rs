@@ -75,6 +75,45 @@ impl Repository {
         path
     }
 
+    /// Returns the path to the POM published along the given artifact, if any.
+    ///
+    /// # Note
+    /// This method doesn't check if the given artifact exists in the
+    /// repository, it only formats the path to the pom.
+    pub fn pom_path(&self, artifact: &ArtifactSpec) -> PathBuf {
+        self.artifact_as_dirname(artifact)
+            .join(format!("{}.pom", artifact.version))
+    }
+
+    /// Writes the POM published along the given artifact to the repository.
+    pub fn save_pom(&self, artifact: &ArtifactSpec, pom_content: impl AsRef<[u8]>) -> std::io::Result<()> {
+        std::fs::create_dir_all(self.artifact_as_dirname(artifact))?;
+        std::fs::write(self.pom_path(artifact), pom_content)
+    }
+
+    /// Returns the path to the file storing the URL of the repository the given
+    /// artifact was fetched from.
+    ///
+    /// # Note
+    /// This method doesn't check if the given artifact exists in the
+    /// repository, it only formats the path to the file.
+    pub fn source_path(&self, artifact: &ArtifactSpec) -> PathBuf {
+        self.artifact_as_dirname(artifact)
+            .join(format!("{}.source", artifact.version))
+    }
+
+    /// Returns the URL of the repository the given artifact was fetched from, if
+    /// it has been recorded.
+    pub fn source(&self, artifact: &ArtifactSpec) -> Option<String> {
+        std::fs::read_to_string(self.source_path(artifact)).ok()
+    }
+
+    /// Records the URL of the repository the given artifact was fetched from.
+    pub fn save_source(&self, artifact: &ArtifactSpec, source: impl AsRef<str>) -> std::io::Result<()> {
+        std::fs::create_dir_all(self.artifact_as_dirname(artifact))?;
+        std::fs::write(self.source_path(artifact), source.as_ref())
+    }
+
     /// Checks if the user exists in the repo.
     pub fn author_exists(&self, author: impl AsRef<str>) -> bool {
         self.base_path.join(author.as_ref()).exists()
@@ -198,7 +237,6 @@ impl Repository {
     ) -> Option<Vec<String>> {
         // Creates a an artifact with a random version (it gets ignored)
         let artifact = ArtifactSpec::new(author_name.as_ref(), artifact_name.as_ref(), "0.0.0" );
-        dbg!(&self.artifact_as_dirname(&artifact));
         Some(
             read_dir(self.artifact_as_dirname(&artifact))
                 .ok()?
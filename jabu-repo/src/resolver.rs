@@ -0,0 +1,451 @@
+use crate::{
+    error::{RepositoryOperationError, ResolutionError},
+    repository::Repository,
+    version::{compare_versions, VersionRequirement},
+};
+use jabu_config::model::{ArtifactSpec, DependenciesConfig, JabuProject, RepositoryLayout};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Identifies an artifact regardless of its version, this is, its author
+/// and its artifact id.
+pub type ArtifactKey = (String, String);
+
+fn key_of(artifact: &ArtifactSpec) -> ArtifactKey {
+    (artifact.author.clone(), artifact.artifact_id.clone())
+}
+
+/// A dependency declared by a project, along with the layout of the repositories
+/// it has to be fetched from and its exclusions (*see
+/// [`DependenciesConfig::maven_exclusions`]*).
+type DeclaredDependency = (ArtifactSpec, RepositoryLayout, Vec<String>);
+
+/// Returns the remote dependencies of the given configuration.
+fn declared_dependencies(dependencies: &DependenciesConfig) -> Vec<DeclaredDependency> {
+    dependencies
+        .remote
+        .iter()
+        .map(|dependency| (dependency.clone(), RepositoryLayout::Jabu, Vec::new()))
+        .chain(dependencies.maven.iter().map(|dependency| {
+            (
+                dependency.clone(),
+                RepositoryLayout::Maven,
+                dependencies.exclusions_of(dependency).to_vec(),
+            )
+        }))
+        .collect()
+}
+
+/// Checks if the artifact matches any of the exclusions, written as `group:artifact`
+/// where either part may be `*`.
+fn is_excluded(exclusions: &BTreeSet<String>, artifact: &ArtifactSpec) -> bool {
+    exclusions.iter().any(|exclusion| {
+        let (author, artifact_id) = exclusion.split_once(':').unwrap_or((exclusion, "*"));
+        (author == "*" || author == artifact.author)
+            && (artifact_id == "*" || artifact_id == artifact.artifact_id)
+    })
+}
+
+/// Provides the [`DependencyResolver`] with what it can't find in the
+/// local repository, usually from a remote repository.
+pub trait ArtifactSource {
+    type Error: From<ResolutionError>;
+
+    /// Returns the versions of the given artifact available in the source, looking
+    /// for them in the repositories with the given layout.
+    fn versions(
+        &mut self,
+        layout: RepositoryLayout,
+        author: &str,
+        artifact_id: &str,
+    ) -> Result<Vec<String>, Self::Error>;
+
+    /// Saves the given artifact into the local repository, fetching it from
+    /// the repositories with the given layout.
+    fn fetch(&mut self, layout: RepositoryLayout, artifact: &ArtifactSpec) -> Result<(), Self::Error>;
+}
+
+/// Different versions of the same artifact requested within the dependency graph.
+#[derive(Debug, PartialEq, Clone)]
+pub struct VersionConflict {
+    pub author: String,
+    pub artifact_id: String,
+
+    /// The requested versions, each one with the artifacts that requested it.
+    pub requested: BTreeMap<String, BTreeSet<String>>,
+
+    /// The version that has been picked.
+    pub selected: String,
+}
+
+impl std::fmt::Display for VersionConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let requested = self
+            .requested
+            .iter()
+            .map(|(version, requesters)| {
+                format!(
+                    "{version} (by {})",
+                    requesters.iter().cloned().collect::<Vec<String>>().join(", ")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(
+            f,
+            "{}_{}: requested {requested}, selected {}",
+            self.author, self.artifact_id, self.selected
+        )
+    }
+}
+
+/// Result of resolving the dependency graph of a project.
+#[derive(Debug, Default, PartialEq)]
+pub struct Resolution {
+    /// Every artifact of the graph (*direct and transitive*), sorted by name.
+    pub artifacts: Vec<ArtifactSpec>,
+
+    /// The artifacts that were requested with different versions, and
+    /// the version that has been picked for each one of them.
+    pub conflicts: Vec<VersionConflict>,
+
+    /// The artifacts that come from Maven repositories.
+    pub maven_artifacts: HashSet<ArtifactSpec>,
+}
+
+impl Resolution {
+    /// Returns the layout of the repositories the given artifact comes from.
+    pub fn layout_of(&self, artifact: &ArtifactSpec) -> RepositoryLayout {
+        if self.maven_artifacts.contains(artifact) {
+            RepositoryLayout::Maven
+        } else {
+            RepositoryLayout::Jabu
+        }
+    }
+}
+
+/// Resolves the transitive dependencies of a project by reading the
+/// `dependencies.remote` and `dependencies.maven` of the jaburons stored in
+/// a [`Repository`].
+///
+/// # Version requirements
+/// Dependencies can be declared with a version requirement instead of a concrete
+/// version (*see [`VersionRequirement`]*). Each requirement is pinned to the highest
+/// matching version available either in the repository or in the [`ArtifactSource`].
+///
+/// # Version conflicts
+/// When an artifact is requested with different versions, the version declared by
+/// the project itself always wins. Otherwise, the highest requested version
+/// (*see [`compare_versions`]*) is picked.
+///
+/// # Exclusions
+/// The transitive dependencies excluded by a Maven dependency (*see
+/// [`DependenciesConfig::maven_exclusions`]*) are left out of everything reached
+/// through it.
+///
+/// # Cycles
+/// If an artifact depends (*directly or transitively*) on itself, or on the project
+/// being resolved, the resolution fails with [`ResolutionError::Cycle`].
+pub struct DependencyResolver<'a> {
+    repository: &'a Repository,
+}
+
+impl<'a> DependencyResolver<'a> {
+    pub fn new(repository: &'a Repository) -> Self {
+        Self { repository }
+    }
+
+    /// Resolves the dependency graph of the `root` project, whose direct dependencies
+    /// are the remote and Maven ones of `dependencies`.
+    ///
+    /// Every time an artifact that isn't in the repository is needed, it gets fetched
+    /// from the `source`.
+    pub fn resolve<S: ArtifactSource>(
+        &self,
+        root: &ArtifactSpec,
+        dependencies: &DependenciesConfig,
+        source: &mut S,
+    ) -> Result<Resolution, S::Error> {
+        let root_key = key_of(root);
+        let direct_dependencies = declared_dependencies(dependencies);
+        let mut layouts: HashMap<ArtifactKey, RepositoryLayout> = HashMap::new();
+        let mut pinned_versions: HashMap<ArtifactSpec, String> = HashMap::new();
+        let mut direct_versions: HashMap<ArtifactKey, String> = HashMap::new();
+        for (dependency, layout, _) in &direct_dependencies {
+            let layout = *layouts.entry(key_of(dependency)).or_insert(*layout);
+            let version = self.pin(dependency, layout, source, &mut pinned_versions)?;
+            direct_versions
+                .entry(key_of(dependency))
+                .or_insert(version);
+        }
+
+        let mut dependencies_cache: HashMap<ArtifactSpec, Vec<DeclaredDependency>> = HashMap::new();
+        // Versions requested by the artifacts of the graph, with the versions selected
+        // in the previous iteration. They are gathered again on every iteration, so the
+        // versions requested only by replaced versions are dropped. If the selection goes
+        // back to a previous one, the candidates are kept from then on instead, so the
+        // selection converges as they only grow and the highest version is picked.
+        let mut candidates: HashMap<ArtifactKey, BTreeSet<String>> = HashMap::new();
+        let mut previous_selections: Vec<HashMap<ArtifactKey, String>> = Vec::new();
+        let mut keep_candidates = false;
+
+        loop {
+            let selected = Self::select_versions(&direct_versions, &candidates);
+            if !keep_candidates {
+                candidates.clear();
+            }
+            let mut requests: BTreeMap<ArtifactKey, BTreeMap<String, BTreeSet<String>>> =
+                BTreeMap::new();
+            let mut edges: BTreeMap<ArtifactKey, BTreeSet<ArtifactKey>> = BTreeMap::new();
+
+            direct_dependencies.iter().for_each(|(dependency, _, _)| {
+                requests
+                    .entry(key_of(dependency))
+                    .or_default()
+                    .entry(dependency.version.clone())
+                    .or_default()
+                    .insert(root.to_string());
+                edges
+                    .entry(root_key.clone())
+                    .or_default()
+                    .insert(key_of(dependency));
+            });
+
+            // Each artifact is visited along with the exclusions of the dependencies
+            // leading to it, which apply to everything below it.
+            let mut visited: BTreeSet<ArtifactKey> = BTreeSet::new();
+            let mut queue: VecDeque<(ArtifactSpec, BTreeSet<String>)> = direct_dependencies
+                .iter()
+                .filter(|(dependency, _, _)| visited.insert(key_of(dependency)))
+                .map(|(dependency, _, exclusions)| {
+                    let key = key_of(dependency);
+                    (
+                        ArtifactSpec::new(&key.0, &key.1, &direct_versions[&key]),
+                        exclusions.iter().cloned().collect(),
+                    )
+                })
+                .collect();
+            while let Some((artifact, exclusions)) = queue.pop_front() {
+                let key = key_of(&artifact);
+                if !dependencies_cache.contains_key(&artifact) {
+                    let dependencies = self.read_dependencies(&artifact, layouts[&key], source)?;
+                    dependencies_cache.insert(artifact.clone(), dependencies);
+                }
+
+                for (dependency, layout, dependency_exclusions) in
+                    dependencies_cache[&artifact].clone()
+                {
+                    if is_excluded(&exclusions, &dependency) {
+                        continue;
+                    }
+                    let dependency_key = key_of(&dependency);
+                    let layout = *layouts.entry(dependency_key.clone()).or_insert(layout);
+                    let pinned_version =
+                        self.pin(&dependency, layout, source, &mut pinned_versions)?;
+                    requests
+                        .entry(dependency_key.clone())
+                        .or_default()
+                        .entry(dependency.version.clone())
+                        .or_default()
+                        .insert(artifact.to_string());
+                    candidates
+                        .entry(dependency_key.clone())
+                        .or_default()
+                        .insert(pinned_version.clone());
+                    edges
+                        .entry(key.clone())
+                        .or_default()
+                        .insert(dependency_key.clone());
+
+                    // The project itself isn't fetched, it can only be part of a cycle.
+                    if dependency_key != root_key && visited.insert(dependency_key.clone()) {
+                        // Artifacts found for the first time are visited with the requested
+                        // version, the selection is reviewed in the next iteration.
+                        let version = selected.get(&dependency_key).unwrap_or(&pinned_version);
+                        let mut exclusions = exclusions.clone();
+                        exclusions.extend(dependency_exclusions);
+                        queue.push_back((
+                            ArtifactSpec::new(&dependency.author, &dependency.artifact_id, version),
+                            exclusions,
+                        ));
+                    }
+                }
+            }
+
+            let next_selection = Self::select_versions(&direct_versions, &candidates);
+            if next_selection != selected {
+                keep_candidates |= previous_selections.contains(&next_selection);
+                previous_selections.push(selected);
+                continue;
+            }
+
+            let version_of = |key: &ArtifactKey| {
+                if *key == root_key {
+                    root.version.clone()
+                } else {
+                    selected[key].clone()
+                }
+            };
+            if let Some(cycle) = Self::find_cycle(&root_key, &edges) {
+                return Err(ResolutionError::Cycle(
+                    cycle
+                        .iter()
+                        .map(|key| ArtifactSpec::new(&key.0, &key.1, &version_of(key)))
+                        .collect(),
+                )
+                .into());
+            }
+
+            let mut artifacts: Vec<ArtifactSpec> = visited
+                .iter()
+                .map(|key| ArtifactSpec::new(&key.0, &key.1, &selected[key]))
+                .collect();
+            artifacts.sort_by_key(|artifact| artifact.to_string());
+
+            let maven_artifacts = artifacts
+                .iter()
+                .filter(|artifact| layouts[&key_of(artifact)] == RepositoryLayout::Maven)
+                .cloned()
+                .collect();
+
+            let conflicts = requests
+                .into_iter()
+                .filter(|(key, versions)| *key != root_key && versions.len() > 1)
+                .map(|(key, requested)| VersionConflict {
+                    selected: selected[&key].clone(),
+                    author: key.0,
+                    artifact_id: key.1,
+                    requested,
+                })
+                .collect();
+
+            return Ok(Resolution {
+                artifacts,
+                conflicts,
+                maven_artifacts,
+            });
+        }
+    }
+
+    /// Picks the version of every artifact: the one declared by the project if
+    /// it's a direct dependency, or the highest candidate otherwise.
+    fn select_versions(
+        direct_versions: &HashMap<ArtifactKey, String>,
+        candidates: &HashMap<ArtifactKey, BTreeSet<String>>,
+    ) -> HashMap<ArtifactKey, String> {
+        let mut selected: HashMap<ArtifactKey, String> = candidates
+            .iter()
+            .filter_map(|(key, versions)| {
+                versions
+                    .iter()
+                    .max_by(|a, b| compare_versions(a, b))
+                    .map(|version| (key.clone(), version.clone()))
+            })
+            .collect();
+        selected.extend(direct_versions.clone());
+        selected
+    }
+
+    /// Returns the concrete version the given dependency resolves to. If it has been
+    /// declared with a version requirement, the highest matching version available in
+    /// the repository or in the source is picked.
+    fn pin<S: ArtifactSource>(
+        &self,
+        dependency: &ArtifactSpec,
+        layout: RepositoryLayout,
+        source: &mut S,
+        pinned_versions: &mut HashMap<ArtifactSpec, String>,
+    ) -> Result<String, S::Error> {
+        let requirement = VersionRequirement::from(dependency.version.as_str());
+        if let VersionRequirement::Exact(version) = requirement {
+            return Ok(version);
+        }
+        if let Some(version) = pinned_versions.get(dependency) {
+            return Ok(version.clone());
+        }
+
+        let mut available = self
+            .repository
+            .get_artifact_versions(&dependency.author, &dependency.artifact_id)
+            .unwrap_or_default();
+        available.extend(source.versions(layout, &dependency.author, &dependency.artifact_id)?);
+        available.sort_by(|a, b| compare_versions(a, b));
+        available.dedup();
+
+        match requirement.select(&available) {
+            Some(version) => {
+                pinned_versions.insert(dependency.clone(), version.clone());
+                Ok(version)
+            }
+            None => Err(ResolutionError::NoMatchingVersion {
+                author: dependency.author.clone(),
+                artifact_id: dependency.artifact_id.clone(),
+                requirement: requirement.to_string(),
+                available,
+            }
+            .into()),
+        }
+    }
+
+    /// Returns the remote and Maven dependencies declared in the jaburon of the given
+    /// artifact, fetching it first if it's not in the repository.
+    fn read_dependencies<S: ArtifactSource>(
+        &self,
+        artifact: &ArtifactSpec,
+        layout: RepositoryLayout,
+        source: &mut S,
+    ) -> Result<Vec<DeclaredDependency>, S::Error> {
+        if !self.repository.exists(artifact) {
+            source.fetch(layout, artifact)?;
+            if !self.repository.exists(artifact) {
+                return Err(ResolutionError::Repository(
+                    RepositoryOperationError::ArtifactNotFound(artifact.clone()),
+                )
+                .into());
+            }
+        }
+
+        let jaburon = std::fs::read_to_string(self.repository.jaburon_path(artifact))
+            .map_err(ResolutionError::from)?;
+        let project =
+            JabuProject::try_from(jaburon.as_str()).map_err(|e| ResolutionError::InvalidJaburon {
+                artifact: artifact.clone(),
+                description: e.to_string(),
+            })?;
+        Ok(declared_dependencies(&project.dependencies))
+    }
+
+    /// Looks for a cycle in the graph reachable from `root`, returning its path
+    /// (*starting and ending with the same artifact*) if there is any.
+    fn find_cycle(
+        root: &ArtifactKey,
+        edges: &BTreeMap<ArtifactKey, BTreeSet<ArtifactKey>>,
+    ) -> Option<Vec<ArtifactKey>> {
+        fn visit(
+            node: &ArtifactKey,
+            edges: &BTreeMap<ArtifactKey, BTreeSet<ArtifactKey>>,
+            path: &mut Vec<ArtifactKey>,
+            finished: &mut BTreeSet<ArtifactKey>,
+        ) -> Option<Vec<ArtifactKey>> {
+            if let Some(position) = path.iter().position(|visiting| visiting == node) {
+                let mut cycle = path[position..].to_vec();
+                cycle.push(node.clone());
+                return Some(cycle);
+            }
+            if finished.contains(node) {
+                return None;
+            }
+
+            path.push(node.clone());
+            let cycle = edges
+                .get(node)
+                .into_iter()
+                .flatten()
+                .find_map(|next| visit(next, edges, path, finished));
+            path.pop();
+            finished.insert(node.clone());
+            cycle
+        }
+
+        visit(root, edges, &mut Vec::new(), &mut BTreeSet::new())
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    error::MavenParsingError,
+    maven::{
+        artifact_path, generate_pom, maven_version_requirement, metadata_path,
+        parse_metadata_versions, Pom,
+    },
+};
+use jabu_config::model::{ArtifactSpec, JabuProject, ProjectType};
+
+const PARENT_POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <groupId>org.example</groupId>
+  <artifactId>parent</artifactId>
+  <version>2.0.0</version>
+  <packaging>pom</packaging>
+  <properties>
+    <slf4j.version>2.0.9</slf4j.version>
+  </properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>org.slf4j</groupId>
+        <artifactId>slf4j-api</artifactId>
+        <version>${slf4j.version}</version>
+      </dependency>
+      <dependency>
+        <groupId>org.example</groupId>
+        <artifactId>bom</artifactId>
+        <version>1.0</version>
+        <type>pom</type>
+        <scope>import</scope>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+</project>"#;
+
+const BOM_POM: &str = r#"<project>
+  <groupId>org.example</groupId>
+  <artifactId>bom</artifactId>
+  <version>1.0</version>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>org.example</groupId>
+        <artifactId>managed</artifactId>
+        <version>[1.0,2.0)</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+</project>"#;
+
+const CHILD_POM: &str = r#"<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <parent>
+    <groupId>org.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>2.0.0</version>
+  </parent>
+  <artifactId>child</artifactId>
+  <description>A child project</description>
+  <dependencies>
+    <dependency>
+      <groupId>org.slf4j</groupId>
+      <artifactId>slf4j-api</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>org.example</groupId>
+      <artifactId>managed</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>${project.groupId}</groupId>
+      <artifactId>sibling</artifactId>
+      <version>${project.version}</version>
+      <scope>runtime</scope>
+      <exclusions>
+        <exclusion>
+          <groupId>commons-logging</groupId>
+          <artifactId>commons-logging</artifactId>
+        </exclusion>
+      </exclusions>
+    </dependency>
+    <dependency>
+      <groupId>org.junit.jupiter</groupId>
+      <artifactId>junit-jupiter</artifactId>
+      <version>5.10.0</version>
+      <scope>test</scope>
+    </dependency>
+    <dependency>
+      <groupId>org.example</groupId>
+      <artifactId>optional</artifactId>
+      <version>1.0.0</version>
+      <optional>true</optional>
+    </dependency>
+  </dependencies>
+</project>"#;
+
+fn spec(value: &str) -> ArtifactSpec {
+    ArtifactSpec::try_from(value).unwrap()
+}
+
+fn load(spec: &ArtifactSpec) -> Result<Pom, MavenParsingError> {
+    match spec.artifact_id.as_str() {
+        "parent" => Pom::try_from(PARENT_POM),
+        "bom" => Pom::try_from(BOM_POM),
+        other => panic!("Unexpected POM requested: {other}"),
+    }
+}
+
+#[test]
+fn repository_paths() {
+    assert_eq!(
+        "com/google/guava/guava/31.1-jre/guava-31.1-jre.jar",
+        artifact_path(&spec("com.google.guava:guava:31.1-jre"), "jar")
+    );
+    assert_eq!(
+        "org/slf4j/slf4j-api/maven-metadata.xml",
+        metadata_path("org.slf4j", "slf4j-api")
+    );
+}
+
+#[test]
+fn metadata_versions() {
+    let metadata = r#"<metadata>
+      <groupId>org.slf4j</groupId>
+      <artifactId>slf4j-api</artifactId>
+      <versioning>
+        <latest>2.0.9</latest>
+        <versions>
+          <version>1.7.36</version>
+          <version>2.0.9</version>
+        </versions>
+      </versioning>
+    </metadata>"#;
+
+    assert_eq!(vec!["1.7.36", "2.0.9"], parse_metadata_versions(metadata).unwrap());
+}
+
+#[test]
+fn version_ranges() {
+    assert_eq!("1.0", maven_version_requirement("1.0"));
+    assert_eq!("1.0", maven_version_requirement("[1.0]"));
+    assert_eq!(">=1.0, <2.0", maven_version_requirement("[1.0,2.0)"));
+    assert_eq!(">1.0", maven_version_requirement("(1.0,)"));
+    assert_eq!("<=1.5", maven_version_requirement("(,1.5]"));
+    assert_eq!("[1,2),[3,4)", maven_version_requirement("[1,2),[3,4)"));
+}
+
+#[test]
+fn effective_pom() {
+    let pom = Pom::try_from(CHILD_POM).unwrap().effective(load).unwrap();
+
+    assert_eq!(spec("org.example:child:2.0.0"), pom.spec());
+    let expected = vec![
+        spec("org.slf4j:slf4j-api:2.0.9"),
+        spec("org.example:managed:>=1.0, <2.0"),
+        spec("org.example:sibling:2.0.0"),
+    ];
+    assert_eq!(expected, pom.transitive_dependencies());
+
+    let project = pom.to_jabu_project();
+    assert_eq!("org.example", project.header.author);
+    assert_eq!("A child project", project.header.description);
+    assert_eq!(expected, project.dependencies.maven);
+    assert_eq!(
+        ["commons-logging:commons-logging".to_string()],
+        project
+            .dependencies
+            .exclusions_of(&spec("org.example:sibling:2.0.0"))
+    );
+    assert!(project
+        .dependencies
+        .exclusions_of(&spec("org.slf4j:slf4j-api:2.0.9"))
+        .is_empty());
+}
+
+#[test]
+fn invalid_pom() {
+    assert!(Pom::try_from("<metadata/>").is_err());
+    assert!(Pom::try_from("<project><artifactId>a</artifactId>").is_err());
+}
+
+#[test]
+fn generated_pom() {
+    let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+    project.header.author = "org.example".to_string();
+    project.header.license = Some("MIT".to_string());
+    project.header.description = "Tom & Jerry's app".to_string();
+    project.dependencies.remote = vec![spec("me_liba_^1.2")];
+    project.dependencies.maven = vec![spec("org.slf4j:slf4j-api:2.0.9")];
+    project.dependencies.local = vec![spec("me_local_1.0.0")];
+
+    let xml = generate_pom(&project);
+    assert!(xml.contains("<maven.compiler.source>17</maven.compiler.source>"));
+    assert!(xml.contains("<sourceDirectory>src/main</sourceDirectory>"));
+    assert!(xml.contains("<mainClass>App</mainClass>"));
+    assert!(xml.contains("<name>MIT</name>"));
+    assert!(xml.contains("<systemPath>${project.basedir}/lib/me_local_1.0.0.jar</systemPath>"));
+
+    // The generated POM must be valid, and readable by jabu itself.
+    let pom = Pom::try_from(xml.as_str()).unwrap();
+    assert_eq!(spec("org.example:app:0.0.1"), pom.spec());
+    assert_eq!(Some("Tom & Jerry's app".to_string()), pom.description);
+    assert_eq!(
+        vec![spec("me_liba_>=1.2.0, <2.0.0"), spec("org.slf4j:slf4j-api:2.0.9")],
+        pom.transitive_dependencies()
+    );
+}
+
+#[test]
+fn imported_pom() {
+    let import = Pom::try_from(CHILD_POM)
+        .unwrap()
+        .effective(load)
+        .unwrap()
+        .import();
+
+    assert_eq!("org.example", import.project.header.author);
+    assert_eq!("child", import.project.header.project_name);
+    assert_eq!(
+        vec![
+            spec("org.slf4j:slf4j-api:2.0.9"),
+            spec("org.example:managed:>=1.0, <2.0"),
+            spec("org.example:sibling:2.0.0"),
+            spec("org.junit.jupiter:junit-jupiter:5.10.0"),
+            spec("org.example:optional:1.0.0"),
+        ],
+        import.project.dependencies.maven
+    );
+    assert_eq!(1, import.project.dependencies.maven_exclusions.len());
+    // The test scope, the compiler versions and the main class are reported.
+    assert_eq!(4, import.untranslated.len());
+}
+
+#[test]
+fn exported_pom_is_imported_back() {
+    let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+    project.header.author = "org.example".to_string();
+    project.java_config.source = 11;
+    project.java_config.target = 11;
+    project.java_config.java_version = 11;
+    project.dependencies.maven = vec![spec("org.slf4j:slf4j-api:2.0.9")];
+    project.dependencies.maven_exclusions.insert(
+        "org.slf4j:slf4j-api".to_string(),
+        vec!["org.example:*".to_string()],
+    );
+
+    let import = Pom::try_from(generate_pom(&project).as_str())
+        .unwrap()
+        .effective(|_| -> Result<Pom, MavenParsingError> { unreachable!() })
+        .unwrap()
+        .import();
+
+    assert!(import.untranslated.is_empty(), "{:?}", import.untranslated);
+    assert_eq!(project.header, import.project.header);
+    assert_eq!(project.java_config, import.project.java_config);
+    assert_eq!(project.manifest, import.project.manifest);
+    assert_eq!(project.dependencies, import.project.dependencies);
+    assert_eq!(project.fs_schema.source, import.project.fs_schema.source);
+    assert_eq!(project.fs_schema.resources, import.project.fs_schema.resources);
+}
@@ -1,2 +1,8 @@
 #[cfg(test)]
 mod repository_testing;
+#[cfg(test)]
+mod resolver_testing;
+#[cfg(test)]
+mod version_testing;
+#[cfg(test)]
+mod maven_testing;
@@ -0,0 +1,344 @@
+use crate::{
+    error::ResolutionError,
+    repository::Repository,
+    resolver::{ArtifactSource, DependencyResolver},
+};
+use jabu_config::model::{
+    ArtifactSpec, DependenciesConfig, JabuProject, ProjectType, RepositoryLayout,
+};
+
+fn create_temp_repository() -> std::io::Result<Repository> {
+    let tmp_dir = tempdir::TempDir::new("jaburesolver")?.path().to_path_buf();
+    Ok(Repository::new(tmp_dir))
+}
+
+fn spec(value: &str) -> ArtifactSpec {
+    ArtifactSpec::try_from(value).unwrap()
+}
+
+/// Saves an artifact into the repository whose jaburon depends on `dependencies`.
+fn save(repo: &Repository, artifact: &str, dependencies: &[&str]) {
+    let artifact = spec(artifact);
+    let mut project = JabuProject::default_of_name(&artifact.artifact_id, ProjectType::Application);
+    project.dependencies.remote = dependencies.iter().map(|dep| spec(dep)).collect();
+    repo.save_artifact(&artifact, "".to_string(), ron::to_string(&project).unwrap())
+        .unwrap();
+}
+
+/// Source serving the artifacts saved into another repository.
+struct TestSource {
+    remote: Repository,
+    local: Repository,
+    fetched: Vec<ArtifactSpec>,
+}
+
+impl TestSource {
+    /// A source without artifacts, for when everything is in the local repository.
+    fn empty(local: &Repository) -> Self {
+        Self {
+            remote: create_temp_repository().unwrap(),
+            local: local.clone(),
+            fetched: Vec::new(),
+        }
+    }
+}
+
+impl ArtifactSource for TestSource {
+    type Error = ResolutionError;
+
+    fn versions(
+        &mut self,
+        _: RepositoryLayout,
+        author: &str,
+        artifact_id: &str,
+    ) -> Result<Vec<String>, Self::Error> {
+        Ok(self
+            .remote
+            .get_artifact_versions(author, artifact_id)
+            .unwrap_or_default())
+    }
+
+    fn fetch(&mut self, _: RepositoryLayout, artifact: &ArtifactSpec) -> Result<(), Self::Error> {
+        self.fetched.push(artifact.clone());
+        self.local.save_artifact(
+            artifact,
+            std::fs::read(self.remote.jar_path(artifact))?,
+            std::fs::read(self.remote.jaburon_path(artifact))?,
+        )?;
+        Ok(())
+    }
+}
+
+/// Dependencies config declaring the given remote dependencies.
+fn remote(dependencies: &[ArtifactSpec]) -> DependenciesConfig {
+    DependenciesConfig {
+        remote: dependencies.to_vec(),
+        ..DependenciesConfig::default()
+    }
+}
+
+fn root() -> ArtifactSpec {
+    spec("me_app_1.0.0")
+}
+
+#[test]
+fn transitive_closure() {
+    let repo = create_temp_repository().unwrap();
+    save(&repo, "a_liba_1.0.0", &["b_libb_2.0.0"]);
+    save(&repo, "b_libb_2.0.0", &["c_libc_0.1.0"]);
+    save(&repo, "c_libc_0.1.0", &[]);
+
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(&root(), &remote(&[spec("a_liba_1.0.0")]), &mut TestSource::empty(&repo))
+        .unwrap();
+
+    let expected = vec![spec("a_liba_1.0.0"), spec("b_libb_2.0.0"), spec("c_libc_0.1.0")];
+    assert_eq!(expected, resolution.artifacts);
+    assert!(resolution.conflicts.is_empty());
+}
+
+#[test]
+fn highest_transitive_version_wins() {
+    let repo = create_temp_repository().unwrap();
+    save(&repo, "a_liba_1.0.0", &["c_libc_1.2.0"]);
+    save(&repo, "b_libb_1.0.0", &["c_libc_1.10.0"]);
+    save(&repo, "c_libc_1.2.0", &["d_libd_1.0.0"]);
+    save(&repo, "c_libc_1.10.0", &[]);
+    save(&repo, "d_libd_1.0.0", &[]);
+
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(
+            &root(),
+            &remote(&[spec("a_liba_1.0.0"), spec("b_libb_1.0.0")]),
+            &mut TestSource::empty(&repo),
+        )
+        .unwrap();
+
+    // `d` is only required by the version of `c` that hasn't been picked.
+    let expected = vec![spec("a_liba_1.0.0"), spec("b_libb_1.0.0"), spec("c_libc_1.10.0")];
+    assert_eq!(expected, resolution.artifacts);
+    assert_eq!(1, resolution.conflicts.len());
+    assert_eq!("1.10.0", resolution.conflicts[0].selected);
+    assert_eq!(2, resolution.conflicts[0].requested.len());
+}
+
+#[test]
+fn replaced_versions_dont_request_versions() {
+    let repo = create_temp_repository().unwrap();
+    save(&repo, "a_liba_1.0.0", &["c_libc_1.0.0"]);
+    save(&repo, "b_libb_1.0.0", &["c_libc_2.0.0", "d_libd_1.0.0"]);
+    save(&repo, "c_libc_1.0.0", &["d_libd_2.0.0"]);
+    save(&repo, "c_libc_2.0.0", &[]);
+    save(&repo, "d_libd_1.0.0", &[]);
+    save(&repo, "d_libd_2.0.0", &[]);
+
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(
+            &root(),
+            &remote(&[spec("a_liba_1.0.0"), spec("b_libb_1.0.0")]),
+            &mut TestSource::empty(&repo),
+        )
+        .unwrap();
+
+    // `d` 2.0.0 is only requested by the version of `c` that has been replaced.
+    let expected = vec![
+        spec("a_liba_1.0.0"),
+        spec("b_libb_1.0.0"),
+        spec("c_libc_2.0.0"),
+        spec("d_libd_1.0.0"),
+    ];
+    assert_eq!(expected, resolution.artifacts);
+    assert_eq!(1, resolution.conflicts.len());
+}
+
+#[test]
+fn alternating_selections_converge() {
+    let repo = create_temp_repository().unwrap();
+    save(&repo, "a_liba_1.0.0", &["b_libb_1.0.0", "c_libc_1.0.0"]);
+    save(&repo, "b_libb_1.0.0", &[]);
+    save(&repo, "b_libb_2.0.0", &["c_libc_2.0.0"]);
+    save(&repo, "c_libc_1.0.0", &["b_libb_2.0.0"]);
+    save(&repo, "c_libc_2.0.0", &[]);
+
+    // Picking `c` 2.0.0 drops the request of `b` 2.0.0, which drops the one of
+    // `c` 2.0.0, so the highest versions ever requested are picked instead.
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(&root(), &remote(&[spec("a_liba_1.0.0")]), &mut TestSource::empty(&repo))
+        .unwrap();
+
+    let expected = vec![spec("a_liba_1.0.0"), spec("b_libb_2.0.0"), spec("c_libc_2.0.0")];
+    assert_eq!(expected, resolution.artifacts);
+}
+
+#[test]
+fn direct_version_wins() {
+    let repo = create_temp_repository().unwrap();
+    save(&repo, "a_liba_1.0.0", &["c_libc_2.0.0"]);
+    save(&repo, "c_libc_1.0.0", &[]);
+
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(
+            &root(),
+            &remote(&[spec("a_liba_1.0.0"), spec("c_libc_1.0.0")]),
+            &mut TestSource::empty(&repo),
+        )
+        .unwrap();
+
+    assert_eq!(vec![spec("a_liba_1.0.0"), spec("c_libc_1.0.0")], resolution.artifacts);
+    assert_eq!("1.0.0", resolution.conflicts[0].selected);
+}
+
+#[test]
+fn cycles_are_reported() {
+    let repo = create_temp_repository().unwrap();
+    save(&repo, "a_liba_1.0.0", &["b_libb_1.0.0"]);
+    save(&repo, "b_libb_1.0.0", &["a_liba_1.0.0"]);
+
+    let result = DependencyResolver::new(&repo).resolve(
+        &root(),
+        &remote(&[spec("a_liba_1.0.0")]),
+        &mut TestSource::empty(&repo),
+    );
+
+    match result {
+        Err(ResolutionError::Cycle(path)) => assert_eq!(
+            vec![spec("a_liba_1.0.0"), spec("b_libb_1.0.0"), spec("a_liba_1.0.0")],
+            path
+        ),
+        other => panic!("Expected a cycle, got {other:?}"),
+    }
+}
+
+#[test]
+fn missing_artifacts_are_fetched() {
+    let repo = create_temp_repository().unwrap();
+    let mut source = TestSource::empty(&repo);
+    save(&repo, "a_liba_1.0.0", &["b_libb_1.0.0"]);
+    save(&source.remote, "b_libb_1.0.0", &[]);
+
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(&root(), &remote(&[spec("a_liba_1.0.0")]), &mut source)
+        .unwrap();
+
+    assert_eq!(vec![spec("b_libb_1.0.0")], source.fetched);
+    assert_eq!(2, resolution.artifacts.len());
+}
+
+#[test]
+fn version_requirements_pick_the_highest_match() {
+    let repo = create_temp_repository().unwrap();
+    let mut source = TestSource::empty(&repo);
+    save(&repo, "a_liba_1.2.0", &["b_libb_~1.4.0"]);
+    save(&source.remote, "a_liba_1.10.1", &["b_libb_~1.4.0"]);
+    save(&source.remote, "a_liba_2.0.0", &[]);
+    save(&source.remote, "b_libb_1.4.7", &[]);
+    save(&source.remote, "b_libb_1.5.0", &[]);
+    save(&source.remote, "c_libc_1.0.0", &[]);
+    save(&source.remote, "c_libc_2.0.0-beta.1", &[]);
+
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(
+            &root(),
+            &remote(&[spec("a_liba_^1.2"), spec("c_libc_latest")]),
+            &mut source,
+        )
+        .unwrap();
+
+    let expected = vec![spec("a_liba_1.10.1"), spec("b_libb_1.4.7"), spec("c_libc_1.0.0")];
+    assert_eq!(expected, resolution.artifacts);
+}
+
+#[test]
+fn unfulfilled_version_requirement() {
+    let repo = create_temp_repository().unwrap();
+    save(&repo, "a_liba_1.0.0", &[]);
+
+    let result = DependencyResolver::new(&repo).resolve(
+        &root(),
+        &remote(&[spec("a_liba_>=2, <3")]),
+        &mut TestSource::empty(&repo),
+    );
+
+    match result {
+        Err(ResolutionError::NoMatchingVersion { available, .. }) => {
+            assert_eq!(vec!["1.0.0".to_string()], available)
+        }
+        other => panic!("Expected no matching version, got {other:?}"),
+    }
+}
+
+#[test]
+fn maven_dependencies_keep_their_layout() {
+    let repo = create_temp_repository().unwrap();
+    let mut source = TestSource::empty(&repo);
+    save(&repo, "a_liba_1.0.0", &[]);
+    let mut guava = JabuProject::default_of_name("guava", ProjectType::Application);
+    guava.dependencies.maven = vec![spec("com.google.guava:failureaccess:1.0.1")];
+    source
+        .remote
+        .save_artifact(
+            &spec("com.google.guava:guava:31.1-jre"),
+            "".to_string(),
+            ron::to_string(&guava).unwrap(),
+        )
+        .unwrap();
+    save(&source.remote, "com.google.guava:failureaccess:1.0.1", &[]);
+
+    let dependencies = DependenciesConfig {
+        maven: vec![spec("com.google.guava:guava:31.1-jre")],
+        ..remote(&[spec("a_liba_1.0.0")])
+    };
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(&root(), &dependencies, &mut source)
+        .unwrap();
+
+    assert_eq!(3, resolution.artifacts.len());
+    assert_eq!(RepositoryLayout::Jabu, resolution.layout_of(&spec("a_liba_1.0.0")));
+    assert_eq!(
+        RepositoryLayout::Maven,
+        resolution.layout_of(&spec("com.google.guava:failureaccess:1.0.1"))
+    );
+}
+
+#[test]
+fn maven_exclusions_apply_below_the_dependency() {
+    let repo = create_temp_repository().unwrap();
+    let save_maven = |artifact: &str, dependencies: &[&str], exclusions: &[(&str, &str)]| {
+        let artifact = spec(artifact);
+        let mut project = JabuProject::default_of_name(&artifact.artifact_id, ProjectType::Library);
+        project.dependencies.maven = dependencies.iter().map(|dep| spec(dep)).collect();
+        exclusions.iter().for_each(|(dependency, exclusion)| {
+            project
+                .dependencies
+                .maven_exclusions
+                .insert(dependency.to_string(), vec![exclusion.to_string()]);
+        });
+        repo.save_artifact(&artifact, "".to_string(), ron::to_string(&project).unwrap())
+            .unwrap();
+    };
+    save_maven("g:a:1.0", &["g:b:1.0", "g:c:1.0"], &[("g:b", "g:d")]);
+    save_maven("g:b:1.0", &["g:d:1.0", "g:e:1.0"], &[]);
+    save_maven("g:c:1.0", &["x:y:1.0"], &[]);
+    save_maven("g:d:1.0", &[], &[]);
+    save_maven("g:e:1.0", &["x:z:1.0"], &[]);
+
+    // `g:d` is excluded by `g:a` for `g:b`, and `x:*` by the project for `g:a`.
+    let mut dependencies = DependenciesConfig {
+        maven: vec![spec("g:a:1.0")],
+        ..DependenciesConfig::default()
+    };
+    dependencies
+        .maven_exclusions
+        .insert("g:a".to_string(), vec!["x:*".to_string()]);
+    let resolution = DependencyResolver::new(&repo)
+        .resolve(&root(), &dependencies, &mut TestSource::empty(&repo))
+        .unwrap();
+
+    let expected = vec![
+        spec("g:a:1.0"),
+        spec("g:b:1.0"),
+        spec("g:c:1.0"),
+        spec("g:e:1.0"),
+    ];
+    assert_eq!(expected, resolution.artifacts);
+}
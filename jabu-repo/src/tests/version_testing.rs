@@ -0,0 +1,63 @@
+use crate::version::{compare_versions, parse_version, VersionRequirement};
+use std::cmp::Ordering;
+
+fn versions(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn version_ordering() {
+    assert_eq!(Ordering::Greater, compare_versions("1.10.0", "1.9.0"));
+    assert_eq!(Ordering::Less, compare_versions("1.0.0-alpha", "1.0.0"));
+    assert_eq!(Ordering::Less, compare_versions("1.0.0-alpha.2", "1.0.0-beta"));
+    assert_eq!(Ordering::Greater, compare_versions("2.10", "2.9.1"));
+    assert_eq!(Ordering::Greater, compare_versions("0.0.1", "snapshot"));
+}
+
+#[test]
+fn lenient_parsing() {
+    assert_eq!(semver::Version::new(2, 10, 0), parse_version("2.10").unwrap());
+    assert_eq!("1.0.0-beta", parse_version("1-beta").unwrap().to_string());
+    assert!(parse_version("1.2.3.4").is_none());
+    assert!(parse_version("snapshot").is_none());
+}
+
+#[test]
+fn parse_requirements() {
+    assert_eq!(VersionRequirement::Exact("1.2.3".to_string()), "1.2.3".into());
+    assert_eq!(VersionRequirement::Exact("snapshot".to_string()), "snapshot".into());
+    assert_eq!(VersionRequirement::Exact("1.0".to_string()), "1.0".into());
+    assert_eq!(VersionRequirement::Exact("2.10".to_string()), "2.10".into());
+    assert_eq!(VersionRequirement::Exact("31.1-jre".to_string()), "31.1-jre".into());
+    assert_eq!(VersionRequirement::Latest, "latest".into());
+    assert!(matches!(VersionRequirement::from("^1.2"), VersionRequirement::Range(_)));
+    assert!(matches!(VersionRequirement::from(">=2, <3"), VersionRequirement::Range(_)));
+    assert!(matches!(VersionRequirement::from("1.x"), VersionRequirement::Range(_)));
+}
+
+#[test]
+fn select_highest_compatible() {
+    let available = versions(&["1.2.0", "1.4.2", "1.4.10", "1.5.0", "2.0.0", "2.1.0-rc.1"]);
+
+    let select = |requirement: &str| VersionRequirement::from(requirement).select(&available);
+    assert_eq!(Some("1.5.0".to_string()), select("^1.2"));
+    assert_eq!(Some("1.4.10".to_string()), select("~1.4.0"));
+    assert_eq!(Some("2.0.0".to_string()), select(">=2, <3"));
+    assert_eq!(Some("2.0.0".to_string()), select("latest"));
+    assert_eq!(Some("2.1.0-rc.1".to_string()), select(">=2.1.0-rc.1"));
+    assert_eq!(Some("1.2.0".to_string()), select("1.2.0"));
+    assert_eq!(None, select("^3"));
+}
+
+#[test]
+fn requirements_as_maven_versions() {
+    let maven = |requirement: &str| VersionRequirement::from(requirement).to_maven_version();
+    assert_eq!("1.2.3", maven("1.2.3"));
+    assert_eq!("[0,)", maven("latest"));
+    assert_eq!("[1.2.0,2.0.0)", maven("^1.2"));
+    assert_eq!("[0.2.3,0.3.0)", maven("^0.2.3"));
+    assert_eq!("[1.4.0,1.5.0)", maven("~1.4.0"));
+    assert_eq!("[2.0.0,3.0.0)", maven(">=2, <3"));
+    assert_eq!("(1.0.0,)", maven(">1.0.0"));
+    assert_eq!("[1.2.3]", maven("=1.2.3"));
+}
@@ -0,0 +1,197 @@
+use semver::{Version, VersionReq};
+use std::cmp::Ordering;
+
+/// Parses a version leniently: besides regular semver versions, versions with
+/// missing minor or patch numbers (*i.e. `1` or `2.10-beta`*) are accepted too, and
+/// treated as if the missing numbers were `0`.
+pub fn parse_version(version: &str) -> Option<Version> {
+    if let Ok(version) = Version::parse(version) {
+        return Some(version);
+    }
+
+    let (core, rest) = version.split_at(version.find(['-', '+']).unwrap_or(version.len()));
+    let mut numbers: Vec<&str> = core.split('.').collect();
+    if numbers.len() >= 3
+        || numbers
+            .iter()
+            .any(|number| number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    numbers.resize(3, "0");
+    Version::parse(&format!("{}{rest}", numbers.join("."))).ok()
+}
+
+/// Compares two versions. Versions following semver (*see [`parse_version`]*) are
+/// compared according to its precedence rules, so pre-releases come before their
+/// release, and are considered higher than the ones that don't, which are compared
+/// lexicographically between them.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (parse_version(a), parse_version(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// Requirement over the version of an artifact, as written in the version part of
+/// an [`jabu_config::model::ArtifactSpec`] declared as a dependency.
+#[derive(Debug, PartialEq, Clone)]
+pub enum VersionRequirement {
+    /// A concrete version (*i.e. `1.2.3` or `2.10`*). This is also used for versions
+    /// that don't follow semver (*i.e. `snapshot`*), so they have to match exactly.
+    Exact(String),
+
+    /// The highest release (*pre-releases excluded*), written as `latest`.
+    Latest,
+
+    /// A semver requirement, such as `^1.2`, `~1.4.0` or `>=2, <3`. Pre-releases
+    /// only match if a comparator of the requirement is a pre-release of the same
+    /// version (*i.e. `>=1.0.0-beta`*).
+    Range(VersionReq),
+}
+
+impl From<&str> for VersionRequirement {
+    fn from(value: &str) -> Self {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("latest") {
+            Self::Latest
+        } else if Version::parse(value).is_ok() || is_plain_version(value) {
+            Self::Exact(value.to_string())
+        } else if let Ok(requirement) = VersionReq::parse(value) {
+            Self::Range(requirement)
+        } else {
+            Self::Exact(value.to_string())
+        }
+    }
+}
+
+/// Checks if the value is a version without operators nor wildcards (*i.e. `2.10`
+/// or `31.1-jre`*), which semver would otherwise read as a caret requirement.
+fn is_plain_version(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit())
+        && !value.contains([',', '*', ' '])
+        && !value.split(['.', '-', '+']).any(|part| part.eq_ignore_ascii_case("x"))
+}
+
+impl std::fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(version) => write!(f, "{version}"),
+            Self::Latest => write!(f, "latest"),
+            Self::Range(requirement) => write!(f, "{requirement}"),
+        }
+    }
+}
+
+impl VersionRequirement {
+    /// Returns `true` if the requirement can only be fulfilled by one version,
+    /// so there is no need to know the available versions.
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Self::Exact(_))
+    }
+
+    /// Checks if the given version fulfills the requirement.
+    pub fn matches(&self, version: &str) -> bool {
+        match self {
+            Self::Exact(exact) => exact == version,
+            Self::Latest => parse_version(version).is_some_and(|version| version.pre.is_empty()),
+            Self::Range(requirement) => {
+                parse_version(version).is_some_and(|version| requirement.matches(&version))
+            }
+        }
+    }
+
+    /// Picks the highest version that fulfills the requirement between the
+    /// given ones, if any.
+    pub fn select<'a>(&self, versions: impl IntoIterator<Item = &'a String>) -> Option<String> {
+        versions
+            .into_iter()
+            .filter(|version| self.matches(version))
+            .max_by(|a, b| compare_versions(a, b))
+            .cloned()
+    }
+}
+
+/// Bound of a Maven version range: the version and whether it's inclusive.
+type RangeBound = Option<(String, bool)>;
+
+impl VersionRequirement {
+    /// Translates the requirement into a Maven version (*i.e. `^1.2` becomes the range
+    /// `[1.2.0,2.0.0)`*), the inverse of [`crate::maven::maven_version_requirement`].
+    pub fn to_maven_version(&self) -> String {
+        let requirement = match self {
+            Self::Exact(version) => return version.clone(),
+            Self::Latest => return "[0,)".to_string(),
+            Self::Range(requirement) => requirement,
+        };
+
+        let (mut lower, mut upper): (RangeBound, RangeBound) = (None, None);
+        requirement.comparators.iter().for_each(|comparator| {
+            let (comparator_lower, comparator_upper) = comparator_bounds(comparator);
+            lower = comparator_lower.or(lower.take());
+            upper = comparator_upper.or(upper.take());
+        });
+
+        match (lower, upper) {
+            (Some((lower, true)), Some((upper, true))) if lower == upper => format!("[{lower}]"),
+            (lower, upper) => format!(
+                "{}{},{}{}",
+                if lower.as_ref().is_some_and(|(_, inclusive)| *inclusive) { '[' } else { '(' },
+                lower.as_ref().map(|(version, _)| version.as_str()).unwrap_or_default(),
+                upper.as_ref().map(|(version, _)| version.as_str()).unwrap_or_default(),
+                if upper.as_ref().is_some_and(|(_, inclusive)| *inclusive) { ']' } else { ')' },
+            ),
+        }
+    }
+}
+
+/// Returns the lower and upper bounds of the versions matched by the comparator.
+fn comparator_bounds(comparator: &semver::Comparator) -> (RangeBound, RangeBound) {
+    use semver::Op;
+
+    let major = comparator.major;
+    let base = format!(
+        "{major}.{}.{}{}",
+        comparator.minor.unwrap_or(0),
+        comparator.patch.unwrap_or(0),
+        if comparator.pre.is_empty() { String::new() } else { format!("-{}", comparator.pre) }
+    );
+    // The first version after the ones matched by a partial version (*i.e. `1.3.0` for `1.2`*).
+    let next = match (comparator.minor, comparator.patch) {
+        (None, _) => format!("{}.0.0", major + 1),
+        (Some(minor), None) => format!("{major}.{}.0", minor + 1),
+        (Some(minor), Some(patch)) => format!("{major}.{minor}.{}", patch + 1),
+    };
+    let is_partial = comparator.patch.is_none();
+
+    match comparator.op {
+        Op::Exact | Op::Wildcard if !is_partial => {
+            (Some((base.clone(), true)), Some((base, true)))
+        }
+        Op::Exact | Op::Wildcard => (Some((base, true)), Some((next, false))),
+        Op::Greater if is_partial => (Some((next, true)), None),
+        Op::Greater => (Some((base, false)), None),
+        Op::GreaterEq => (Some((base, true)), None),
+        Op::Less => (None, Some((base, false))),
+        Op::LessEq if is_partial => (None, Some((next, false))),
+        Op::LessEq => (None, Some((base, true))),
+        Op::Tilde => {
+            let upper = match comparator.minor {
+                Some(minor) => format!("{major}.{}.0", minor + 1),
+                None => format!("{}.0.0", major + 1),
+            };
+            (Some((base, true)), Some((upper, false)))
+        }
+        Op::Caret => {
+            let upper = match (major, comparator.minor, comparator.patch) {
+                (0, Some(0), Some(patch)) => format!("0.0.{}", patch + 1),
+                (0, Some(minor), _) => format!("0.{}.0", minor + 1),
+                _ => format!("{}.0.0", major + 1),
+            };
+            (Some((base, true)), Some((upper, false)))
+        }
+        _ => (None, None),
+    }
+}
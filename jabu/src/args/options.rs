@@ -147,7 +147,7 @@ impl ParOptionBuilder {
         ParOption {
             name: self.name.unwrap_or_default(),
             description: self.description,
-            short: self.short.unwrap_or_default(),
+            short: self.short,
             has_arg: self.has_arg,
             required: self.required,
             default_value: self.default_value
@@ -157,7 +157,7 @@ impl ParOptionBuilder {
 
 pub struct ParOption {
     pub name: String,
-    pub short: char,
+    pub short: Option<char>,
     pub description: Option<String>,
     pub has_arg: bool,
     pub required: bool,
@@ -173,7 +173,7 @@ impl ParOption {
             name: name.to_string(),
             has_arg,
             description: description.map_or_else(|| None, |desc| Some(desc.to_string())),
-            short: name.chars().next().unwrap(),
+            short: name.chars().next(),
             default_value: None,
             required,
         }
@@ -193,23 +193,26 @@ impl ParOption {
     /// *ParOption name: output-type, short: 'o', has_arg: true*
     /// 
     /// "`--output-type:{value}, -o:{value}`"
+    ///
+    /// The short version is left out when the option doesn't have one.
     pub fn display_name(&self) -> String {
         let long_part = format!("--{}", self.name);
-        let short_part = format!("-{}", self.short);
         let arg_specification = if self.has_arg {
             format!(":{{value}}")
         } else {
             String::new()
         };
 
-
-        format!("{0}{2},\n   {1}{2}", long_part, short_part, arg_specification)
+        match self.short {
+            Some(short) => format!("{0}{2},\n   -{1}{2}", long_part, short, arg_specification),
+            None => format!("{0}{1}", long_part, arg_specification),
+        }
     }
 }
 
 impl PartialEq for ParOption {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name 
-            || self.short == other.short
+            || (self.short.is_some() && self.short == other.short)
     }
 }
@@ -1,6 +1,8 @@
-use std::process::exit;
+use std::{path::PathBuf, process::exit};
 
-use tasks::{TaskError, GeneralTaskManager};
+use jabu_config::fsutils;
+
+use tasks::{take_profile, TaskError, GeneralTaskManager, PlannedTask};
 use chrono;
 
 use crate::tools::JavaHome;
@@ -26,20 +28,32 @@ fn main() {
         .to_string();
 
     args.next();
-    let task_name = match args.next() {
-        Some(task_name) => task_name,
-        None => {
-            eprintln!("No task specified!");
-            exit(1);
-        }
-    };
+    // Along with the custom tasks of the project in the current directory, if there's one.
+    let mut general_task_manager = GeneralTaskManager::top_level_default(&cwd);
 
-    let general_task_manager = GeneralTaskManager::new(
-        tasks::JabuTaskManager::top_level_default(),
-        tasks::TaskManager::top_level_default()
-    );
+    let mut args = args.collect::<Vec<String>>();
+    match take_profile(&mut args) {
+        Ok(profile) => general_task_manager.set_profile(profile),
+        Err(e) => handle_error(e),
+    }
 
-    let result = general_task_manager.execute(&task_name, args.collect(), &cwd);
+    let result = if !args.is_empty() {
+        general_task_manager.execute_command(args, &cwd)
+    } else {
+        match default_tasks(&cwd) {
+            Some(default_tasks) => general_task_manager.execute_all(
+                default_tasks
+                    .iter()
+                    .filter_map(|task| PlannedTask::parse(task))
+                    .collect(),
+                &cwd,
+            ),
+            None => {
+                eprintln!("No task specified!");
+                exit(1);
+            }
+        }
+    };
     let _end_timestamp = chrono::offset::Local::now();
     match result {
         Err(e) => {
@@ -49,6 +63,17 @@ fn main() {
     }
 }
 
+/// Returns the default tasks of the workspace or project in the given directory,
+/// if there are any.
+fn default_tasks(directory: &str) -> Option<Vec<String>> {
+    let directory = Some(PathBuf::from(directory));
+    let default_tasks = match fsutils::workspace_from_directory(directory.clone()) {
+        Ok(Some(workspace)) => workspace.default_tasks,
+        _ => fsutils::project_from_directory(directory).ok()?.default_tasks,
+    };
+    Some(default_tasks).filter(|default_tasks| !default_tasks.is_empty())
+}
+
 fn handle_error(e: TaskError) -> ! {
     eprintln!("Failure:\n{e}");
 
@@ -1,18 +1,32 @@
 use crate::{
     args::parser::ParsedArguments,
-    tasks::JabuTask,
+    tasks::{
+        impls::{CompilePlan, CompileState, COMPILE_STATE_FILE_NAME},
+        JabuTask, TaskError,
+    },
     tools::{JavaHome, JavacConfig},
     utils,
-    utils::FSNodeType,
+    utils::{
+        classfile::ClassFileInfo,
+        hashing::{sha256_file, sha256_hex},
+        FSNodeType,
+    },
+};
+use jabu_config::{
+    fsutils::{java_sources, path_dependency_classes},
+    prelude::*,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
 };
-use jabu_config::{fsutils::java_sources, prelude::*};
 
 #[derive(Default)]
 pub struct BuildJabuTask {}
 
 impl JabuTask for BuildJabuTask {
     fn description(&self) -> String {
-        "Builds the current project.".to_string()
+        "Builds the current project (only recompiling what has changed).".to_string()
     }
 
     fn execute(
@@ -22,39 +36,76 @@ impl JabuTask for BuildJabuTask {
         jabu_config: &JabuProject,
         java_home: &JavaHome,
     ) -> crate::tasks::TaskResult {
-        let sources = java_sources(None, &jabu_config);
+        let source_dir = PathBuf::from(&jabu_config.fs_schema.source);
+        let classes_dir = jabu_config.fs_schema.target_classes();
+        let state_path = jabu_config
+            .fs_schema
+            .target_jabu()
+            .join(COMPILE_STATE_FILE_NAME);
+
+        let current_sources = Self::hash_sources(jabu_config, &source_dir)?;
+
+        // Add the jars under the lib directory of the project, and the classes of
+        // the projects it depends on by path.
+        let classpath: Vec<String> = crate::utils::walkdir_find(
+            &jabu_config.fs_schema.lib,
+            |entry| entry.extension().unwrap_or_default() == "jar",
+            &[FSNodeType::File, FSNodeType::SymLink],
+        )
+        .into_iter()
+        .chain(path_dependency_classes(None, jabu_config)?)
+        .map(|entry| entry.to_string_lossy().to_string())
+        .collect();
+        let settings_fingerprint = Self::settings_fingerprint(jabu_config, &classpath)?;
 
-        println!("Sources to compile: ");
-        sources
+        let mut compile_state = CompileState::load(&state_path).unwrap_or_default();
+        let plan = compile_state.plan(&current_sources, &settings_fingerprint);
+
+        if plan.is_empty() {
+            println!("Nothing to compile, all classes are up to date.");
+            return Ok(());
+        }
+
+        Self::remove_stale_classes(&compile_state, &plan, &classes_dir)?;
+        compile_state.mark_pending(&plan, &current_sources);
+        compile_state.settings_fingerprint = settings_fingerprint;
+        compile_state.save(&state_path)?;
+
+        if plan.to_compile.is_empty() {
+            println!("No sources left to compile, stale classes removed.");
+            return Ok(());
+        }
+
+        println!(
+            "Sources to compile ({}, {} in total): ",
+            if plan.full_rebuild {
+                "full build"
+            } else {
+                "incremental build"
+            },
+            current_sources.len()
+        );
+        plan.to_compile
             .iter()
             .enumerate()
             .for_each(|(index, source)| println!("{}: {source:?}", index + 1));
-        println!("");
+        println!();
 
         let mut javac_config = JavacConfig::new(
-            sources
+            plan.to_compile
                 .iter()
-                .map(|source| source.to_string_lossy().to_string())
+                .map(|source| source_dir.join(source).to_string_lossy().to_string())
                 .collect(),
-            Some(
-                jabu_config
-                    .fs_schema
-                    .target_classes()
-                    .to_string_lossy()
-                    .to_string(),
-            ),
+            Some(classes_dir.to_string_lossy().to_string()),
             Some(jabu_config.java_config.clone()),
         );
-
-        // Add the jars under the lib directory of the project.
-        javac_config.classpath = crate::utils::walkdir_find(
-            &jabu_config.fs_schema.lib,
-            |entry| entry.extension().unwrap_or_default() == "jar",
-            &[FSNodeType::File, FSNodeType::SymLink],
-        )
-        .iter()
-        .map(|jar_file| jar_file.to_string_lossy().to_string())
-        .collect();
+        javac_config.classpath = classpath;
+        if !plan.full_rebuild {
+            // The classes of the sources that are not recompiled are already there.
+            javac_config
+                .classpath
+                .insert(0, classes_dir.to_string_lossy().to_string());
+        }
 
         let javac_args = javac_config.into_args();
         let javac_path = java_home
@@ -86,16 +137,141 @@ impl JabuTask for BuildJabuTask {
         };
 
         if exit_status != 0 {
-            Err(crate::tasks::TaskError::CommandFailed {
+            return Err(crate::tasks::TaskError::CommandFailed {
                 command: "javac".to_string(),
                 description: exit_status.to_string(),
-            })
-        } else {
-            Ok(())
+            });
         }
+
+        let generated_classes = Self::read_generated_classes(&compile_state, &plan, &classes_dir)?;
+        compile_state.record_compilation(&plan.to_compile, &current_sources, generated_classes);
+        compile_state.save(&state_path)?;
+        Ok(())
     }
 
     fn required_tools(&self) -> &[&'static str] {
         &["javac"]
     }
+
+    fn inputs(&self, jabu_config: &JabuProject) -> Vec<PathBuf> {
+        let mut inputs = vec![
+            PathBuf::from(&jabu_config.fs_schema.source),
+            PathBuf::from(&jabu_config.fs_schema.lib),
+        ];
+        inputs.extend(path_dependency_classes(None, jabu_config).unwrap_or_default());
+        inputs
+    }
+
+    fn outputs(&self, jabu_config: &JabuProject) -> Vec<PathBuf> {
+        vec![jabu_config.fs_schema.target_classes()]
+    }
+}
+
+impl BuildJabuTask {
+    /// Returns a map with the path of every source (*relative to the source
+    /// directory*) and the hash of its contents.
+    fn hash_sources(
+        jabu_config: &JabuProject,
+        source_dir: &Path,
+    ) -> Result<BTreeMap<String, String>, TaskError> {
+        let absolute_source_dir = std::env::current_dir()?.join(source_dir);
+        java_sources(None, jabu_config)
+            .into_iter()
+            .map(|source| {
+                let relative_path = source
+                    .strip_prefix(&absolute_source_dir)
+                    .unwrap_or(&source)
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                Ok((relative_path, sha256_file(&source)?))
+            })
+            .collect()
+    }
+
+    /// Fingerprint of the settings that affect the compilation of every source, this
+    /// is, the project's `JavaConfig` and the contents of the jars in the classpath.
+    fn settings_fingerprint(
+        jabu_config: &JabuProject,
+        classpath: &[String],
+    ) -> Result<String, TaskError> {
+        let mut fingerprint = format!("{:?}", jabu_config.java_config);
+        let mut entries = classpath.to_vec();
+        entries.sort();
+        for entry in entries {
+            if Path::new(&entry).is_dir() {
+                // The classes of a project depended on by path.
+                let mut class_files = Self::class_files_in(Path::new(&entry));
+                class_files.sort();
+                for class_file in class_files {
+                    fingerprint.push_str(&format!(
+                        "\n{}={}",
+                        class_file.to_string_lossy(),
+                        sha256_file(&class_file)?
+                    ));
+                }
+            } else {
+                fingerprint.push_str(&format!("\n{entry}={}", sha256_file(&entry)?));
+            }
+        }
+        Ok(sha256_hex(fingerprint))
+    }
+
+    /// Removes the class files generated by the stale sources of the plan. If the
+    /// whole project has to be rebuilt, every class file in the classes directory
+    /// is removed instead.
+    fn remove_stale_classes(
+        compile_state: &CompileState,
+        plan: &CompilePlan,
+        classes_dir: &Path,
+    ) -> std::io::Result<()> {
+        let class_files = if plan.full_rebuild {
+            Self::class_files_in(classes_dir)
+        } else {
+            compile_state.class_files(&plan.stale, classes_dir)
+        };
+
+        class_files
+            .into_iter()
+            .filter(|class_file| class_file.exists())
+            .try_for_each(std::fs::remove_file)
+    }
+
+    /// Parses the class files in the classes directory that weren't generated by
+    /// any of the sources left untouched by the plan.
+    fn read_generated_classes(
+        compile_state: &CompileState,
+        plan: &CompilePlan,
+        classes_dir: &Path,
+    ) -> std::io::Result<Vec<ClassFileInfo>> {
+        let untouched_classes: BTreeSet<String> = compile_state
+            .classes_except(&plan.to_compile)
+            .into_iter()
+            .map(|class| format!("{class}.class"))
+            .collect();
+
+        Self::class_files_in(classes_dir)
+            .into_iter()
+            .filter(|class_file| {
+                let relative_path = class_file
+                    .strip_prefix(classes_dir)
+                    .unwrap_or(class_file)
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                !untouched_classes.contains(&relative_path)
+            })
+            .map(ClassFileInfo::from_file)
+            .collect()
+    }
+
+    fn class_files_in(classes_dir: &Path) -> Vec<PathBuf> {
+        jabu_config::fsutils::walkdir_find(
+            classes_dir,
+            |path| path.extension().unwrap_or_default() == "class",
+            &[jabu_config::fsutils::FSNodeType::File],
+        )
+    }
 }
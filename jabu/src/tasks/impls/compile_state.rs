@@ -0,0 +1,274 @@
+use crate::utils::classfile::ClassFileInfo;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    path::{Path, PathBuf},
+};
+
+/// Name of the file (*inside of [`jabu_config::model::FsSchema::target_jabu`]*)
+/// where the state of the last compilation is stored.
+pub const COMPILE_STATE_FILE_NAME: &str = "compile-state.ron";
+
+/// State of the last compilation of a project, used by the `build` task
+/// to recompile only those sources that have changed (*and the ones that
+/// depend on them*).
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CompileState {
+    /// Fingerprint of everything that affects the compilation of every source,
+    /// such as the classpath and the project's `JavaConfig`. If this changes, all
+    /// the sources are recompiled.
+    pub settings_fingerprint: String,
+
+    /// The compiled sources, with their path relative to the source directory as key.
+    pub sources: BTreeMap<String, SourceState>,
+}
+
+/// Fingerprint and compilation output of a single source file.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SourceState {
+    /// Hash of the contents of the source. An empty hash marks the source
+    /// as pending of compilation.
+    pub hash: String,
+
+    /// Internal names of the classes generated from the source.
+    pub classes: BTreeSet<String>,
+
+    /// Internal names of the classes referenced by the generated classes.
+    pub dependencies: BTreeSet<String>,
+
+    /// `true` if any of the generated classes declares constants (*see
+    /// [`ClassFileInfo::declares_constants`]*).
+    #[serde(default)]
+    pub declares_constants: bool,
+}
+
+/// Result of comparing a [`CompileState`] with the current sources of the project.
+#[derive(Debug, Default, PartialEq)]
+pub struct CompilePlan {
+    /// `true` if every source has to be compiled from scratch.
+    pub full_rebuild: bool,
+
+    /// Sources (*relative to the source directory*) that have to be compiled.
+    pub to_compile: BTreeSet<String>,
+
+    /// Sources whose previously generated classes are outdated and have to be
+    /// removed before compiling (*this includes deleted sources*).
+    pub stale: BTreeSet<String>,
+}
+
+impl CompilePlan {
+    pub fn is_empty(&self) -> bool {
+        self.to_compile.is_empty() && self.stale.is_empty()
+    }
+}
+
+impl CompileState {
+    /// Loads the compile state from the given file. If the file doesn't exist or
+    /// it cannot be parsed, `None` is returned.
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        ron::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    }
+
+    /// Writes the compile state into the given file, creating its parent
+    /// directory if necessary.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = ron::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, contents)
+    }
+
+    /// Compares the state with the given sources (*a map of the relative path of each
+    /// source to the hash of its contents*) and settings fingerprint, returning what
+    /// has to be recompiled.
+    ///
+    /// Besides the changed and new sources, every source that depends (*directly or
+    /// transitively*) on a changed or deleted source is recompiled too. If a changed
+    /// or deleted source declares constants, the whole project is rebuilt, since the
+    /// classes using them cannot be found through their references.
+    pub fn plan(
+        &self,
+        current_sources: &BTreeMap<String, String>,
+        settings_fingerprint: &str,
+    ) -> CompilePlan {
+        let mut affected: BTreeSet<String> = self
+            .sources
+            .iter()
+            .filter(|(source, state)| current_sources.get(*source) != Some(&state.hash))
+            .map(|(source, _)| source.clone())
+            .collect();
+
+        if self.settings_fingerprint != settings_fingerprint
+            || affected
+                .iter()
+                .any(|source| self.sources[source].declares_constants)
+        {
+            return CompilePlan {
+                full_rebuild: true,
+                to_compile: current_sources.keys().cloned().collect(),
+                stale: self.sources.keys().cloned().collect(),
+            };
+        }
+
+        // Walk the reverse dependency graph from the affected sources.
+        let dependents = self.dependents();
+        let mut queue: VecDeque<String> = affected.iter().cloned().collect();
+        while let Some(source) = queue.pop_front() {
+            if let Some(source_dependents) = dependents.get(source.as_str()) {
+                source_dependents
+                    .iter()
+                    .filter(|dependent| affected.insert(dependent.to_string()))
+                    .for_each(|dependent| queue.push_back(dependent.to_string()));
+            }
+        }
+
+        let to_compile = current_sources
+            .keys()
+            .filter(|source| affected.contains(*source) || !self.sources.contains_key(*source))
+            .cloned()
+            .collect();
+
+        CompilePlan {
+            full_rebuild: false,
+            to_compile,
+            stale: affected,
+        }
+    }
+
+    /// Returns a map containing, for each source, the sources whose classes
+    /// reference any of its classes.
+    fn dependents(&self) -> HashMap<&str, BTreeSet<&str>> {
+        let class_owners: HashMap<&str, &str> = self
+            .sources
+            .iter()
+            .flat_map(|(source, state)| {
+                state
+                    .classes
+                    .iter()
+                    .map(move |class| (class.as_str(), source.as_str()))
+            })
+            .collect();
+
+        let mut dependents: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        self.sources.iter().for_each(|(source, state)| {
+            state
+                .dependencies
+                .iter()
+                .filter_map(|dependency| class_owners.get(dependency.as_str()))
+                .filter(|owner| **owner != source.as_str())
+                .for_each(|owner| {
+                    dependents.entry(owner).or_default().insert(source.as_str());
+                });
+        });
+        dependents
+    }
+
+    /// Marks the sources of the plan as pending of compilation, forgetting those
+    /// that no longer exist. The classes of pending sources are kept so that their
+    /// dependents can still be found if the compilation fails.
+    pub fn mark_pending(&mut self, plan: &CompilePlan, current_sources: &BTreeMap<String, String>) {
+        if plan.full_rebuild {
+            self.sources.clear();
+            return;
+        }
+        plan.stale.iter().for_each(|source| {
+            if current_sources.contains_key(source) {
+                if let Some(state) = self.sources.get_mut(source) {
+                    state.hash.clear();
+                }
+            } else {
+                self.sources.remove(source);
+            }
+        });
+    }
+
+    /// Returns the paths of the class files generated from the given sources.
+    pub fn class_files<'a>(
+        &self,
+        sources: impl IntoIterator<Item = &'a String>,
+        classes_dir: &Path,
+    ) -> Vec<PathBuf> {
+        sources
+            .into_iter()
+            .filter_map(|source| self.sources.get(source))
+            .flat_map(|state| state.classes.iter())
+            .map(|class| classes_dir.join(format!("{class}.class")))
+            .collect()
+    }
+
+    /// Registers the classes generated by the compilation of `compiled_sources`.
+    ///
+    /// Each class is assigned to the source located at the directory of its
+    /// package with the name of its `SourceFile` attribute. If the sources
+    /// don't follow the package structure, the class is assigned to the only
+    /// compiled source with such file name, if there is exactly one.
+    pub fn record_compilation(
+        &mut self,
+        compiled_sources: &BTreeSet<String>,
+        current_sources: &BTreeMap<String, String>,
+        classes: Vec<ClassFileInfo>,
+    ) {
+        compiled_sources.iter().for_each(|source| {
+            self.sources.insert(
+                source.clone(),
+                SourceState {
+                    hash: current_sources.get(source).cloned().unwrap_or_default(),
+                    ..SourceState::default()
+                },
+            );
+        });
+
+        classes.into_iter().for_each(|class| {
+            let source_file = match &class.source_file {
+                Some(source_file) => source_file,
+                None => return,
+            };
+            let expected_source = if class.package().is_empty() {
+                source_file.clone()
+            } else {
+                format!("{}/{source_file}", class.package())
+            };
+            let owner = if compiled_sources.contains(&expected_source) {
+                Some(expected_source)
+            } else {
+                let mut candidates = compiled_sources.iter().filter(|source| {
+                    Path::new(source).file_name().map(|name| name.to_string_lossy())
+                        == Some(source_file.as_str().into())
+                });
+                match (candidates.next(), candidates.next()) {
+                    (Some(candidate), None) => Some(candidate.clone()),
+                    _ => None,
+                }
+            };
+
+            if let Some(state) = owner.and_then(|owner| self.sources.get_mut(&owner)) {
+                state.dependencies.extend(class.referenced_classes);
+                state.classes.insert(class.class_name);
+                state.declares_constants |= class.declares_constants;
+            }
+        });
+
+        // A source doesn't depend on itself.
+        self.sources.values_mut().for_each(|state| {
+            let SourceState {
+                classes,
+                dependencies,
+                ..
+            } = state;
+            dependencies.retain(|dependency| !classes.contains(dependency));
+        });
+    }
+
+    /// Returns the internal names of the classes generated from every source
+    /// except the `excluded` ones.
+    pub fn classes_except(&self, excluded: &BTreeSet<String>) -> BTreeSet<&str> {
+        self.sources
+            .iter()
+            .filter(|(source, _)| !excluded.contains(*source))
+            .flat_map(|(_, state)| state.classes.iter().map(|class| class.as_str()))
+            .collect()
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{Task, TaskError, TaskResult},
+};
+use jabu_config::validation::check_project;
+
+/// Reports every problem of the project's jabu file, without executing any task.
+/// The errors are checked as well before executing any jabu task.
+#[derive(Debug, Default)]
+pub struct CheckConfigTask;
+
+impl Task for CheckConfigTask {
+    fn execute(&self, _: Vec<String>, _: Option<ParsedArguments>) -> TaskResult {
+        let diagnostics = check_project(&std::env::current_dir()?, None)?;
+        if diagnostics.has_errors() {
+            return Err(TaskError::InvalidConfig(Box::new(diagnostics)));
+        }
+
+        if diagnostics.diagnostics.is_empty() {
+            println!(
+                "==> No problems found in '{}'.",
+                diagnostics.file.to_string_lossy()
+            );
+        } else {
+            print!("{diagnostics}");
+        }
+        Ok(())
+    }
+
+    fn description(&self) -> String {
+        "Checks the project's configuration, reporting its errors and warnings.".to_string()
+    }
+}
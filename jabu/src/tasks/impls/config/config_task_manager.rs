@@ -0,0 +1,10 @@
+use super::{CheckConfigTask, ConvertConfigTask, MigrateConfigTask};
+use crate::tasks::GeneralTaskManager;
+
+pub fn get_config_task_manager() -> GeneralTaskManager {
+    let mut config_taskmanager = GeneralTaskManager::default();
+    config_taskmanager.register_task("check", Box::new(CheckConfigTask));
+    config_taskmanager.register_task("migrate", Box::new(MigrateConfigTask));
+    config_taskmanager.register_task("convert", Box::new(ConvertConfigTask));
+    config_taskmanager
+}
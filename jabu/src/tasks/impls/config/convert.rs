@@ -0,0 +1,81 @@
+use crate::{
+    args::{
+        options::{Options, ParOptionBuilder},
+        parser::ParsedArguments,
+    },
+    tasks::{Task, TaskError, TaskResult},
+};
+use jabu_config::{
+    format::{ConfigFormat, CONFIG_FORMATS},
+    fsutils::{project_file, ProjectLoadingError},
+    model::JabuProject,
+};
+
+/// Rewrites the project's jabu file in another format (*i.e. `jabu.ron` as
+/// `jabu.toml`*), keeping the previous file as a backup (*`jabu.ron.bak`*).
+#[derive(Debug, Default)]
+pub struct ConvertConfigTask;
+
+impl Task for ConvertConfigTask {
+    fn execute(&self, _: Vec<String>, parsed_args: Option<ParsedArguments>) -> TaskResult {
+        // Safe to unwrap, since the trait method `Task::options` returns `Some()`
+        let parsed_args = parsed_args.unwrap();
+        // Safe to unwrap, since the option is required and has an argument.
+        let extension = parsed_args
+            .get_option_value("to")
+            .cloned()
+            .flatten()
+            .unwrap();
+        let Some(format) = ConfigFormat::from_extension(&extension) else {
+            let formats = CONFIG_FORMATS.map(|format| format.to_string());
+            return Err(TaskError::Generic(format!(
+                "Unknown format '{extension}' (it can be {}).",
+                formats.join(", ")
+            )));
+        };
+
+        let directory = std::env::current_dir()?;
+        let (file, current_format) = project_file(&directory)?;
+        let file_name = current_format.project_file_name();
+        if current_format == format {
+            println!("==> The project's jabu file is already '{file_name}'.");
+            return Ok(());
+        }
+
+        // The project isn't interpolated nor migrated, so that it's kept as it is.
+        let source = std::fs::read_to_string(&file)?;
+        let project: JabuProject = current_format
+            .deserialize(&source)
+            .map_err(ProjectLoadingError::from)?;
+        let contents = format.serialize(&project).map_err(TaskError::Generic)?;
+
+        let backup = file.with_file_name(format!("{file_name}.bak"));
+        std::fs::write(directory.join(format.project_file_name()), contents)?;
+        std::fs::rename(&file, &backup)?;
+        println!(
+            "==> '{file_name}' converted into '{}' (backup in '{}').",
+            format.project_file_name(),
+            backup.to_string_lossy()
+        );
+        Ok(())
+    }
+
+    fn description(&self) -> String {
+        "Rewrites the project's jabu file in another format (ron, toml or json), keeping a backup."
+            .to_string()
+    }
+
+    fn options(&self) -> Option<Options> {
+        let mut options = Options::default();
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("to")
+                .short('t')
+                .description("Format the jabu file is converted into (ron, toml or json).")
+                .has_arg(true)
+                .required(true)
+                .build(),
+        );
+        Some(options)
+    }
+}
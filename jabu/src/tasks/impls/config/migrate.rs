@@ -0,0 +1,55 @@
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{Task, TaskError, TaskResult},
+};
+use jabu_config::{
+    fsutils::{project_file, ProjectLoadingError},
+    migration::migrate_project,
+    model::{JabuProject, CURRENT_FORMAT_VERSION},
+};
+
+/// Rewrites the project's jabu file in the current format version (*see
+/// [`migrate_project`]*) and in the same format, keeping the previous file as a
+/// backup (*`jabu.ron.v<format version>.bak`*).
+#[derive(Debug, Default)]
+pub struct MigrateConfigTask;
+
+impl Task for MigrateConfigTask {
+    fn execute(&self, _: Vec<String>, _: Option<ParsedArguments>) -> TaskResult {
+        let (file, config_format) = project_file(&std::env::current_dir()?)?;
+        let file_name = config_format.project_file_name();
+        let source = std::fs::read_to_string(&file)?;
+        // The project isn't interpolated, so that its variables are kept.
+        let mut project: JabuProject = config_format
+            .deserialize(&source)
+            .map_err(ProjectLoadingError::from)?;
+        let format_version = project.format_version;
+        let migrations = migrate_project(&mut project)?;
+        if migrations.is_empty() {
+            println!(
+                "==> '{file_name}' is already in the current format version ({CURRENT_FORMAT_VERSION})."
+            );
+            return Ok(());
+        }
+
+        let contents = config_format
+            .serialize(&project)
+            .map_err(TaskError::Generic)?;
+        let backup = file.with_file_name(format!("{file_name}.v{format_version}.bak"));
+        std::fs::write(&backup, &source)?;
+        std::fs::write(&file, contents)?;
+
+        migrations
+            .iter()
+            .for_each(|migration| println!("\t- {migration}"));
+        println!(
+            "==> '{file_name}' migrated from format version {format_version} to {CURRENT_FORMAT_VERSION} (backup in '{}').",
+            backup.to_string_lossy()
+        );
+        Ok(())
+    }
+
+    fn description(&self) -> String {
+        "Rewrites the project's jabu file in the current format, keeping a backup.".to_string()
+    }
+}
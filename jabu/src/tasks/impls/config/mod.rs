@@ -0,0 +1,38 @@
+mod check;
+mod config_task_manager;
+mod convert;
+mod migrate;
+
+pub use check::*;
+pub use config_task_manager::*;
+pub use convert::*;
+pub use migrate::*;
+
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{Task, TaskResult},
+};
+
+#[derive(Debug, Default)]
+pub struct ConfigSubtask;
+
+impl Task for ConfigSubtask {
+    fn is_group(&self) -> bool {
+        true
+    }
+
+    fn execute(&self, args: Vec<String>, _: Option<ParsedArguments>) -> TaskResult {
+        let task_manager = get_config_task_manager();
+        let task_name = if let Some(task_name) = args.first() {
+            task_name.as_str()
+        } else {
+            "help"
+        };
+
+        task_manager.execute(task_name, args.clone(), ".")
+    }
+
+    fn description(&self) -> String {
+        "Manage the project's jabu file.".to_string()
+    }
+}
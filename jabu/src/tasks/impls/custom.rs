@@ -0,0 +1,177 @@
+use crate::{
+    args::{
+        options::{Options, ParOptionBuilder},
+        parser::ParsedArguments,
+    },
+    tasks::{
+        impls::ScriptsTask, JabuTask, JabuTaskDependencySpec, PlannedTask, TaskError, TaskResult,
+    },
+    tools::JavaHome,
+};
+use jabu_config::model::{CustomTask, CustomTaskAction, JabuProject};
+use rhai::{Array, Dynamic, EvalAltResult, Map, Scope};
+use std::{path::PathBuf, process::Command};
+
+/// Task declared in the `custom_tasks` section of the project (*see
+/// [`CustomTask`]*).
+///
+/// Rhai scripts get the values of the options in the `options` map (*flags being
+/// `true`*), and the rest of the arguments in the `args` array. Commands get the
+/// options as `JABU_OPTION_<NAME>` environment variables, and the rest of the
+/// arguments appended to the command line.
+pub struct CustomJabuTask {
+    name: String,
+    task: CustomTask,
+}
+
+impl CustomJabuTask {
+    pub fn new(name: impl Into<String>, task: CustomTask) -> Self {
+        Self {
+            name: name.into(),
+            task,
+        }
+    }
+
+    fn run_script(
+        &self,
+        script_name: &str,
+        parsed_args: ParsedArguments,
+        jabu_config: &JabuProject,
+    ) -> TaskResult {
+        let options = parsed_args
+            .options
+            .into_iter()
+            .map(|(name, value)| {
+                let value = value.map_or(Dynamic::TRUE, Dynamic::from);
+                (name.into(), value)
+            })
+            .collect::<Map>();
+        let args = parsed_args
+            .arg_list
+            .into_iter()
+            .map(Dynamic::from)
+            .collect::<Array>();
+
+        let mut scope = Scope::new();
+        scope.push_constant("options", options);
+        scope.push_constant("args", args);
+        let script_path = ScriptsTask::get_script_path(jabu_config, script_name);
+        match ScriptsTask::run_script_with_scope(jabu_config, script_path, scope) {
+            Ok(_) => Ok(()),
+            Err(e) => match e.unwrap_inner() {
+                EvalAltResult::Return(_, _) | EvalAltResult::Exit(_, _) => Ok(()),
+                _ => Err(TaskError::Generic(format!(
+                    "The script '{script_name}' of the task '{}' has failed: {e}",
+                    self.name
+                ))),
+            },
+        }
+    }
+
+    fn run_command(
+        &self,
+        command_line: &str,
+        parsed_args: ParsedArguments,
+        java_home: &JavaHome,
+    ) -> TaskResult {
+        let mut words = command_line.split_whitespace();
+        let Some(program) = words.next() else {
+            return Err(TaskError::Generic(format!(
+                "The command of the task '{}' is empty.",
+                self.name
+            )));
+        };
+        let args = words
+            .map(String::from)
+            .chain(parsed_args.arg_list)
+            .collect::<Vec<String>>();
+
+        let mut command = Command::new(program);
+        command
+            .args(&args)
+            .env("JAVA_HOME", java_home.get_java_home());
+        for (name, value) in parsed_args.options {
+            command.env(
+                format!("JABU_OPTION_{}", name.to_uppercase().replace('-', "_")),
+                value.unwrap_or_else(|| "true".to_string()),
+            );
+        }
+
+        println!("==> [CMD]: [{program:?} {args:?}]");
+        let failure = |description: String| TaskError::CommandFailed {
+            command: command_line.to_string(),
+            description,
+        };
+        match command.status() {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => Err(failure(match status.code() {
+                Some(code) => code.to_string(),
+                None => "Command has no exit code (probably due to a SIGINT)".to_string(),
+            })),
+            // i.e. The invoked binary doesn't exist.
+            Err(e) => Err(failure(e.to_string())),
+        }
+    }
+}
+
+impl JabuTask for CustomJabuTask {
+    fn description(&self) -> String {
+        self.task.description.clone()
+    }
+
+    fn execute(
+        &self,
+        _: Vec<String>,
+        parsed_args: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        java_home: &JavaHome,
+    ) -> TaskResult {
+        // Safe to unwrap, since the task always has options.
+        let parsed_args = parsed_args.unwrap();
+        match &self.task.run {
+            CustomTaskAction::Script(script_name) => {
+                self.run_script(script_name, parsed_args, jabu_config)
+            }
+            CustomTaskAction::Command(command_line) => {
+                self.run_command(command_line, parsed_args, java_home)
+            }
+        }
+    }
+
+    fn get_dependency_task_specs(&self) -> JabuTaskDependencySpec {
+        JabuTaskDependencySpec::new(
+            self.task
+                .depends_on
+                .iter()
+                .filter_map(|task| PlannedTask::parse(task))
+                .map(|task| (task.name, task.args))
+                .collect(),
+        )
+    }
+
+    fn options(&self) -> Option<Options> {
+        let mut options = Options::default();
+        self.task.options.iter().for_each(|option| {
+            let mut builder = ParOptionBuilder::default()
+                .name(&option.name)
+                .has_arg(option.has_arg)
+                .required(option.required);
+            if let Some(description) = &option.description {
+                builder = builder.description(description);
+            }
+            if let Some(default_value) = &option.default_value {
+                builder = builder.default_value(default_value.clone());
+            }
+            options.add_option(builder.build());
+        });
+        Some(options)
+    }
+
+    fn inputs(&self, _: &JabuProject) -> Vec<PathBuf> {
+        self.task.inputs.iter().map(PathBuf::from).collect()
+    }
+
+    fn outputs(&self, _: &JabuProject) -> Vec<PathBuf> {
+        self.task.outputs.iter().map(PathBuf::from).collect()
+    }
+}
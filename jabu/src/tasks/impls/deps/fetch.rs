@@ -3,9 +3,19 @@ use crate::{
     tasks::{JabuTask, TaskError, TaskResult},
     tools::JavaHome,
 };
+use super::maven::MavenRepositories;
 use home::home_dir;
-use jabu_config::model::{ArtifactSpec, JabuProject};
-use jaburepo::repository::Repository;
+use crate::utils::hashing::sha256_file;
+use jabu_config::{
+    fsutils::{lockfile_from_directory, write_lockfile},
+    model::{
+        ArtifactSpec, JabuProject, LockFile, LockedArtifact, RepositoryLayout, JABU_LOCK_FILE_NAME,
+    },
+};
+use jaburepo::{
+    repository::Repository,
+    resolver::{ArtifactSource, DependencyResolver},
+};
 use std::path::PathBuf;
 
 #[derive(Debug, PartialEq, Default)]
@@ -19,59 +29,187 @@ impl JabuTask for FetchDepsTask {
         jabu_config: &JabuProject,
         _: &JavaHome,
     ) -> TaskResult {
-        // TODO: Allow specifying the jaburepo directory with env vars
-        let proj_dir = std::env::current_dir()?;
-        let jabu_repo =
-            jaburepo::repository::Repository::new(home_dir().unwrap().join(".jaburepo"));
-
-        // Split into missing and found
-        let local_found_deps: Vec<&ArtifactSpec> = jabu_config
-            .dependencies
-            .remote
-            .iter()
-            .filter(|dep| jabu_repo.exists(dep))
-            .collect();
+        sync_dependencies(jabu_config, false)
+    }
 
-        let missing_deps: Vec<&ArtifactSpec> = jabu_config
-            .dependencies
-            .remote
-            .iter()
-            .filter(|dep| !jabu_repo.exists(dep))
-            .collect();
-
-        if !local_found_deps.is_empty() {
-            println!("Copying local dependencies {}...", local_found_deps.len());
-            copy_dependencies_from_local_repo(
-                &local_found_deps,
-                &jabu_repo,
-                proj_dir.join(&jabu_config.fs_schema.lib),
-            )?;
+    fn description(&self) -> String {
+        "Fetches the project's dependencies (honoring the versions pinned in 'jabu.lock').".to_string()
+    }
+}
+
+/// Copies the whole dependency graph of the project into its `lib` directory.
+///
+/// If there is a lockfile and the dependencies declared by the project haven't changed,
+/// the pinned artifacts are used, otherwise the graph is resolved again and the lockfile
+/// is rewritten. Artifacts already present in the lockfile must match its checksums,
+/// unless `update` is `true`, in which case the lockfile is always regenerated.
+pub(super) fn sync_dependencies(jabu_config: &JabuProject, update: bool) -> TaskResult {
+    // TODO: Allow specifying the jaburepo directory with env vars
+    let proj_dir = std::env::current_dir()?;
+    let jabu_repo = jaburepo::repository::Repository::new(home_dir().unwrap().join(".jaburepo"));
+    let url = match std::env::var("JABU_REMOTE_REPO") {
+        Ok(url) => url,
+        Err(_) => "https://jabu-remote-repository.com".to_string(),
+    };
+
+    let previous_lockfile = lockfile_from_directory(None)?;
+
+    let lockfile = match previous_lockfile {
+        Some(lockfile)
+            if !update
+                && lockfile.dependencies == jabu_config.dependencies.remote
+                && lockfile.maven_dependencies == jabu_config.dependencies.maven =>
+        {
+            println!("Using the versions pinned in '{JABU_LOCK_FILE_NAME}'...");
+            lockfile.artifacts.iter().try_for_each(|locked| {
+                if !jabu_repo.exists(&locked.spec) {
+                    match locked.layout {
+                        RepositoryLayout::Jabu => {
+                            fetch_dependency(&locked.spec, &locked.source, &jabu_repo)?
+                        }
+                        RepositoryLayout::Maven => MavenRepositories {
+                            urls: std::slice::from_ref(&locked.source),
+                            repo: &jabu_repo,
+                        }
+                        .fetch(&locked.spec)?,
+                    }
+                }
+                verify_checksums(locked, &jabu_repo)
+            })?;
+            lockfile
         }
+        previous_lockfile => {
+            if previous_lockfile.is_some() && !update {
+                println!("The dependencies of the project have changed since '{JABU_LOCK_FILE_NAME}' was generated.");
+            }
+            let lockfile = resolve_dependencies(jabu_config, &url, &jabu_repo)?;
+
+            if let Some(previous_lockfile) = previous_lockfile {
+                // Artifacts that were already pinned must still be the same ones.
+                if !update {
+                    lockfile
+                        .artifacts
+                        .iter()
+                        .filter_map(|locked| previous_lockfile.get(&locked.spec))
+                        .try_for_each(|locked| verify_checksums(locked, &jabu_repo))?;
+                }
+
+                // Remove the jars of the artifacts that are no longer part of the graph.
+                previous_lockfile
+                    .artifacts
+                    .iter()
+                    .filter(|locked| lockfile.get(&locked.spec).is_none())
+                    .map(|locked| {
+                        proj_dir
+                            .join(&jabu_config.fs_schema.lib)
+                            .join(format!("{}.jar", locked.spec))
+                    })
+                    .filter(|jar| jar.exists())
+                    .try_for_each(std::fs::remove_file)?;
+            }
 
-        if !missing_deps.is_empty() {
-            let url = match std::env::var("JABU_REMOTE_REPO") {
-                Ok(url) => url,
-                Err(_) => "https://jabu-remote-repository.com".to_string(),
-            };
-            println!(
-                "Fetching {} remote dependencies from '{}'...",
-                missing_deps.len(),
-                url
-            );
-            fetch_dependencies(&missing_deps, url, &jabu_repo)?;
-            copy_dependencies_from_local_repo(
-                &missing_deps,
-                &jabu_repo,
-                proj_dir.join(&jabu_config.fs_schema.lib),
-            )?;
+            write_lockfile(None, &lockfile)?;
+            println!("'{JABU_LOCK_FILE_NAME}' written.");
+            lockfile
         }
+    };
 
-        Ok(())
+    if !lockfile.artifacts.is_empty() {
+        let direct_dependencies =
+            jabu_config.dependencies.remote.len() + jabu_config.dependencies.maven.len();
+        println!(
+            "Copying {} dependencies ({} direct, {} transitive)...",
+            lockfile.artifacts.len(),
+            direct_dependencies,
+            lockfile.artifacts.len().saturating_sub(direct_dependencies)
+        );
+        copy_dependencies_from_local_repo(
+            &lockfile.artifacts.iter().map(|locked| &locked.spec).collect(),
+            &jabu_repo,
+            proj_dir.join(&jabu_config.fs_schema.lib),
+        )?;
     }
 
-    fn description(&self) -> String {
-        "Fetches the project's dependencies.".to_string()
+    Ok(())
+}
+
+/// Resolves the dependency graph of the project, returning the lockfile that pins it.
+fn resolve_dependencies(
+    jabu_config: &JabuProject,
+    url: &str,
+    jabu_repo: &Repository,
+) -> Result<LockFile, TaskError> {
+    let root = ArtifactSpec::new(
+        &jabu_config.header.author,
+        &jabu_config.header.project_name,
+        &jabu_config.header.version,
+    );
+
+    println!("Resolving dependencies...");
+    let resolution = DependencyResolver::new(jabu_repo).resolve(
+        &root,
+        &jabu_config.dependencies,
+        &mut RemoteRepository {
+            url,
+            repo: jabu_repo,
+            maven: MavenRepositories {
+                urls: &jabu_config.dependencies.maven_repositories,
+                repo: jabu_repo,
+            },
+        },
+    )?;
+
+    if !resolution.conflicts.is_empty() {
+        println!(
+            "==> VERSION CONFLICTS (the version declared by the project wins, otherwise the highest one)"
+        );
+        resolution
+            .conflicts
+            .iter()
+            .for_each(|conflict| println!("\t{conflict}"));
     }
+
+    let artifacts = resolution
+        .artifacts
+        .iter()
+        .map(|spec| {
+            Ok(LockedArtifact {
+                jar_sha256: sha256_file(jabu_repo.jar_path(spec))?,
+                jaburon_sha256: sha256_file(jabu_repo.jaburon_path(spec))?,
+                source: jabu_repo.source(spec).unwrap_or(url.to_string()),
+                layout: resolution.layout_of(spec),
+                spec: spec.clone(),
+            })
+        })
+        .collect::<Result<Vec<LockedArtifact>, TaskError>>()?;
+
+    Ok(LockFile {
+        dependencies: jabu_config.dependencies.remote.clone(),
+        maven_dependencies: jabu_config.dependencies.maven.clone(),
+        artifacts,
+    })
+}
+
+/// Checks that the jar and the jaburon stored in the repository match the
+/// checksums of the locked artifact.
+fn verify_checksums(locked: &LockedArtifact, repo: &Repository) -> TaskResult {
+    [
+        (repo.jar_path(&locked.spec), &locked.jar_sha256),
+        (repo.jaburon_path(&locked.spec), &locked.jaburon_sha256),
+    ]
+    .into_iter()
+    .try_for_each(|(path, expected)| {
+        let found = sha256_file(&path)?;
+        if found == *expected {
+            Ok(())
+        } else {
+            Err(TaskError::ChecksumMismatch {
+                resource_name: path.to_string_lossy().to_string(),
+                expected: expected.clone(),
+                found,
+            })
+        }
+    })
 }
 
 fn copy_dependencies_from_local_repo(
@@ -79,6 +217,7 @@ fn copy_dependencies_from_local_repo(
     repo: &Repository,
     lib_dir: PathBuf,
 ) -> TaskResult {
+    std::fs::create_dir_all(&lib_dir)?;
     deps.iter().try_for_each(|dep| {
         let from_repo_path = repo.jar_path(dep);
         println!(
@@ -86,7 +225,7 @@ fn copy_dependencies_from_local_repo(
             from_repo_path.to_string_lossy().to_string(),
             lib_dir.to_string_lossy().to_string()
         );
-        match std::fs::copy(&from_repo_path, lib_dir.join(dep.to_string())) {
+        match std::fs::copy(&from_repo_path, lib_dir.join(format!("{dep}.jar"))) {
             // TODO: Continue here
             Err(e) => {
                 return Err::<(), TaskError>(TaskError::from_io_error(
@@ -106,43 +245,83 @@ fn copy_dependencies_from_local_repo(
     Ok(())
 }
 
-fn fetch_dependencies(
-    deps: &Vec<&ArtifactSpec>,
-    url: impl Into<String>,
-    repo: &Repository,
-) -> TaskResult {
-    let url = url.into();
+/// Remote repositories from which the artifacts missing in the local repository
+/// are fetched: the jabu repository located at `url`, and the Maven repositories.
+struct RemoteRepository<'a> {
+    url: &'a str,
+    repo: &'a Repository,
+    maven: MavenRepositories<'a>,
+}
 
-    deps.iter().try_for_each(|dep| {
-        // TODO: Use something to create urls
-        let jar_url = format!(
-            "{url}/api/get/{}/{}/{}/jar",
-            dep.author, dep.artifact_id, dep.version
-        );
-        let jaburon_url = format!(
-            "{url}/api/get/{}/{}/{}/jaburon",
-            dep.author, dep.artifact_id, dep.version
-        );
-        let repo_jar_path = repo.jar_path(dep);
+impl ArtifactSource for RemoteRepository<'_> {
+    type Error = TaskError;
 
-        println!("==> FETCHING artifact {}", dep);
+    fn versions(
+        &mut self,
+        layout: RepositoryLayout,
+        author: &str,
+        artifact_id: &str,
+    ) -> Result<Vec<String>, TaskError> {
+        if layout == RepositoryLayout::Maven {
+            return self.maven.versions(author, artifact_id);
+        }
 
-        println!(
-            "Fetching artifact from {} to {}...",
-            jar_url,
-            repo_jar_path.to_string_lossy()
-        );
-        let jar_res = reqwest::blocking::get(jar_url)?;
-        // ERROR: No such file or directory.
+        let versions_url = format!("{}/api/list-versions/{author}/{artifact_id}", self.url);
+        println!("Listing versions of {author}_{artifact_id} from {versions_url}...");
 
-        println!("Fetching jaburon from {}...", jaburon_url);
-        let jaburon_res = reqwest::blocking::get(jaburon_url)?;
+        let response = match reqwest::blocking::get(&versions_url) {
+            Ok(response) if response.status() == reqwest::StatusCode::NOT_FOUND => {
+                return Ok(Vec::new())
+            }
+            Ok(response) => response.error_for_status(),
+            Err(e) => Err(e),
+        };
+        match response.and_then(|response| response.json::<Vec<String>>()) {
+            Ok(versions) => Ok(versions),
+            Err(e) => {
+                // Fall back to the versions in the local repository.
+                println!("Couldn't list the remote versions ({e}), only local versions will be used.");
+                Ok(Vec::new())
+            }
+        }
+    }
 
-        println!("Saving artifact {}...", dep);
-        repo.save_artifact(dep, &jar_res.bytes()?, &jaburon_res.bytes()?)?;
+    fn fetch(&mut self, layout: RepositoryLayout, artifact: &ArtifactSpec) -> Result<(), TaskError> {
+        match layout {
+            RepositoryLayout::Jabu => fetch_dependency(artifact, self.url, self.repo),
+            RepositoryLayout::Maven => self.maven.fetch(artifact),
+        }
+    }
+}
 
-        Ok::<(), TaskError>(())
-    })?;
+/// Fetches the jar and the jaburon of the given artifact from the remote repository
+/// located at `url`, saving them into the local repository.
+fn fetch_dependency(dep: &ArtifactSpec, url: &str, repo: &Repository) -> TaskResult {
+    // TODO: Use something to create urls
+    let jar_url = format!(
+        "{url}/api/get/{}/{}/{}/jar",
+        dep.author, dep.artifact_id, dep.version
+    );
+    let jaburon_url = format!(
+        "{url}/api/get/{}/{}/{}/jaburon",
+        dep.author, dep.artifact_id, dep.version
+    );
+    let repo_jar_path = repo.jar_path(dep);
+
+    println!("==> FETCHING artifact {}", dep);
+
+    println!(
+        "Fetching artifact from {} to {}...",
+        jar_url,
+        repo_jar_path.to_string_lossy()
+    );
+    let jar_res = reqwest::blocking::get(jar_url)?.error_for_status()?;
+
+    println!("Fetching jaburon from {}...", jaburon_url);
+    let jaburon_res = reqwest::blocking::get(jaburon_url)?.error_for_status()?;
+
+    println!("Saving artifact {}...", dep);
+    repo.save_artifact(dep, &jar_res.bytes()?, &jaburon_res.bytes()?)?;
 
     Ok(())
 }
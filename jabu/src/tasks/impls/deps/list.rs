@@ -32,8 +32,11 @@ impl JabuTask for ListDepsTask {
             })
             .collect();
 
-        if jabu_config.dependencies.local.is_empty() && jabu_config.dependencies.remote.is_empty() {
-            println!("==> No local/remote dependencies specified in the jabu file.");
+        if jabu_config.dependencies.local.is_empty()
+            && jabu_config.dependencies.remote.is_empty()
+            && jabu_config.dependencies.maven.is_empty()
+        {
+            println!("==> No local/remote/maven dependencies specified in the jabu file.");
         } else {
             Self::list_dependencies(jabu_config).expect("Error while listing dependencies");
         }
@@ -73,6 +76,20 @@ impl ListDepsTask {
         } else {
             println!("==> No remote dependencies specified in the jabu file.");
         }
+
+        if !jabu_config.dependencies.maven.is_empty() {
+            println!("==> MAVEN DEPENDENCIES");
+            jabu_config
+                .dependencies
+                .maven
+                .iter()
+                .enumerate()
+                .for_each(|(index, artifact)| {
+                    println!("\t{} -> {}", index + 1, artifact.to_maven_coordinates());
+                });
+        } else {
+            println!("==> No maven dependencies specified in the jabu file.");
+        }
         Ok(())
     }
 
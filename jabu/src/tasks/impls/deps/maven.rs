@@ -0,0 +1,103 @@
+use crate::tasks::{TaskError, TaskResult};
+use jabu_config::model::ArtifactSpec;
+use jaburepo::{
+    maven::{artifact_path, metadata_path, parse_metadata_versions, Pom},
+    repository::Repository,
+};
+
+/// Contents of an empty jar (*a zip with just the end of central directory record*),
+/// saved as the jar of the artifacts with `pom` packaging, which have no classes.
+const EMPTY_JAR: [u8; 22] = [
+    0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// The repositories with the Maven layout (*`http(s)://` or `file://`*) from which
+/// the Maven dependencies are fetched, tried in order.
+pub(super) struct MavenRepositories<'a> {
+    pub urls: &'a [String],
+    pub repo: &'a Repository,
+}
+
+impl MavenRepositories<'_> {
+    /// Returns the versions of the given artifact listed by any of the repositories.
+    pub fn versions(&self, group_id: &str, artifact_id: &str) -> Result<Vec<String>, TaskError> {
+        let path = metadata_path(group_id, artifact_id);
+        let mut versions = Vec::new();
+        for url in self.urls {
+            println!("Listing versions of {group_id}:{artifact_id} from {url}...");
+            match download(url, &path) {
+                Ok(Some(metadata)) => versions.extend(parse_metadata_versions(
+                    &String::from_utf8_lossy(&metadata),
+                )?),
+                Ok(None) => (),
+                Err(e) => println!("Couldn't list the versions from {url} ({e}), skipping it."),
+            }
+        }
+        Ok(versions)
+    }
+
+    /// Fetches the jar and the POM of the given artifact, saving them into the local
+    /// repository along a jaburon generated from the POM, whose dependencies are the
+    /// transitive dependencies of the artifact.
+    pub fn fetch(&self, artifact: &ArtifactSpec) -> TaskResult {
+        println!("==> FETCHING Maven artifact {}", artifact.to_maven_coordinates());
+        let (url, pom) = self.pom(artifact)?;
+        let pom = pom.effective(|parent| self.pom(parent).map(|(_, pom)| pom))?;
+
+        let jar = if pom.packaging.as_deref() == Some("pom") {
+            EMPTY_JAR.to_vec()
+        } else {
+            println!("Fetching jar from {url}...");
+            download(&url, &artifact_path(artifact, "jar"))?.ok_or_else(|| {
+                TaskError::UnavailableResource {
+                    resource_name: artifact.to_maven_coordinates(),
+                    error: Some(format!("The jar isn't available in {url}.")),
+                }
+            })?
+        };
+        let jaburon = ron::ser::to_string_pretty(&pom.to_jabu_project(), Default::default())
+            .map_err(|e| TaskError::Generic(e.to_string()))?;
+
+        println!("Saving artifact {}...", artifact.to_maven_coordinates());
+        self.repo.save_artifact(artifact, jar, jaburon.into_bytes())?;
+        self.repo.save_source(artifact, &url)?;
+        Ok(())
+    }
+
+    /// Downloads the POM of the given artifact from the first repository that has it,
+    /// returning the URL of that repository too.
+    fn pom(&self, artifact: &ArtifactSpec) -> Result<(String, Pom), TaskError> {
+        let path = artifact_path(artifact, "pom");
+        for url in self.urls {
+            if let Some(pom) = download(url, &path)? {
+                return Ok((url.clone(), Pom::try_from(String::from_utf8_lossy(&pom).as_ref())?));
+            }
+        }
+        Err(TaskError::UnavailableResource {
+            resource_name: artifact.to_maven_coordinates(),
+            error: Some(format!(
+                "The POM couldn't be found in any of the Maven repositories ({}).",
+                self.urls.join(", ")
+            )),
+        })
+    }
+}
+
+/// Downloads the file at `path` from the repository located at `url`, returning
+/// `None` if the repository doesn't have it.
+fn download(url: &str, path: &str) -> Result<Option<Vec<u8>>, TaskError> {
+    let url = format!("{}/{path}", url.trim_end_matches('/'));
+    if let Some(file_path) = url.strip_prefix("file://") {
+        return match std::fs::read(file_path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        };
+    }
+
+    let response = reqwest::blocking::get(&url)?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    Ok(Some(response.error_for_status()?.bytes()?.to_vec()))
+}
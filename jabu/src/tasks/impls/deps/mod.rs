@@ -1,10 +1,13 @@
 mod deps_task_manager;
 mod list;
 mod fetch;
+mod maven;
+mod update;
 
 pub use deps_task_manager::*;
 pub use list::*;
 use fetch::*;
+use update::*;
 use crate::{
     tasks::{
         JabuTask,
@@ -19,6 +22,10 @@ use jabu_config::model::JabuProject;
 pub struct DepsSubtask;
 
 impl JabuTask for DepsSubtask {
+    fn is_group(&self) -> bool {
+        true
+    }
+
     fn execute(&self, args: Vec<String>, _: Option<ParsedArguments>, jabu_config: &JabuProject, java_home: &JavaHome) -> TaskResult {
         let task_manager = get_deps_task_manager();
         let task_name = if let Some(task_name) = args.get(0) {
@@ -0,0 +1,26 @@
+use super::fetch::sync_dependencies;
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{JabuTask, TaskResult},
+    tools::JavaHome,
+};
+use jabu_config::model::JabuProject;
+
+#[derive(Debug, PartialEq, Default)]
+pub struct UpdateDepsTask;
+
+impl JabuTask for UpdateDepsTask {
+    fn execute(
+        &self,
+        _: Vec<String>,
+        _: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        _: &JavaHome,
+    ) -> TaskResult {
+        sync_dependencies(jabu_config, true)
+    }
+
+    fn description(&self) -> String {
+        "Resolves the project's dependencies again, regenerating 'jabu.lock'.".to_string()
+    }
+}
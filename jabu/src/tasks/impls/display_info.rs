@@ -9,7 +9,10 @@ impl JabuTask for DisplayJabuTask {
         "Displays the info of the current project.".to_string()
     }
     fn execute(&self, args: Vec<String>, _: Option<ParsedArguments>, jabu_config: &JabuProject, java_home: &JavaHome) -> crate::tasks::TaskResult {
-        println!("Project's configuration: {:?}", jabu_config);
+        // The configuration is shown with the profile applied, if one was selected.
+        let config = ron::ser::to_string_pretty(jabu_config, ron::ser::PrettyConfig::default())
+            .map_err(|e| crate::tasks::TaskError::Generic(e.to_string()))?;
+        println!("Project's configuration:\n{config}");
         Ok(())
     }
 }
@@ -0,0 +1,8 @@
+use super::ExportPomTask;
+use crate::tasks::GeneralTaskManager;
+
+pub fn get_export_task_manager() -> GeneralTaskManager {
+    let mut export_taskmanager = GeneralTaskManager::default();
+    export_taskmanager.register_jabu_task("pom", Box::new(ExportPomTask));
+    export_taskmanager
+}
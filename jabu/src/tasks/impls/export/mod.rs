@@ -0,0 +1,38 @@
+mod export_task_manager;
+mod pom;
+
+pub use export_task_manager::*;
+pub use pom::*;
+use crate::{
+    tasks::{
+        JabuTask,
+        TaskResult
+    },
+    tools::JavaHome,
+    args::parser::ParsedArguments,
+};
+use jabu_config::model::JabuProject;
+
+#[derive(Default)]
+pub struct ExportSubtask;
+
+impl JabuTask for ExportSubtask {
+    fn is_group(&self) -> bool {
+        true
+    }
+
+    fn execute(&self, args: Vec<String>, _: Option<ParsedArguments>, _: &JabuProject, _: &JavaHome) -> TaskResult {
+        let task_manager = get_export_task_manager();
+        let task_name = if let Some(task_name) = args.first() {
+            task_name.as_str()
+        } else {
+            "help"
+        };
+
+        task_manager.execute(task_name, args.clone(), ".")
+    }
+
+    fn description(&self) -> String {
+        "Export the project's configuration to the formats of other build tools.".to_string()
+    }
+}
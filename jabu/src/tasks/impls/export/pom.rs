@@ -0,0 +1,54 @@
+use crate::{
+    args::{
+        options::{Options, ParOptionBuilder},
+        parser::ParsedArguments,
+    },
+    tasks::{JabuTask, TaskResult},
+    tools::JavaHome,
+};
+use jabu_config::model::JabuProject;
+use jaburepo::maven::generate_pom;
+
+/// Name of the file a Maven project is described in.
+pub const POM_FILE_NAME: &str = "pom.xml";
+
+#[derive(Debug, PartialEq, Default)]
+pub struct ExportPomTask;
+
+impl JabuTask for ExportPomTask {
+    fn execute(
+        &self,
+        _: Vec<String>,
+        parsed_args: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        _: &JavaHome,
+    ) -> TaskResult {
+        let output = parsed_args
+            .as_ref()
+            .and_then(|parsed_args| parsed_args.get_option_value("output").cloned().flatten())
+            .unwrap_or(POM_FILE_NAME.to_string());
+
+        std::fs::write(&output, generate_pom(jabu_config))?;
+        println!("POM written to '{output}'.");
+        Ok(())
+    }
+
+    fn description(&self) -> String {
+        "Generates a Maven 'pom.xml' equivalent to the project's configuration.".to_string()
+    }
+
+    fn options(&self) -> Option<Options> {
+        let mut options = Options::default();
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("output")
+                .short('o')
+                .description("Path of the generated POM.")
+                .has_arg(true)
+                .required(false)
+                .default_value(POM_FILE_NAME.to_string())
+                .build(),
+        );
+        Some(options)
+    }
+}
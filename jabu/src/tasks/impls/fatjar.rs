@@ -0,0 +1,368 @@
+use crate::{
+    args::{
+        options::{Options, ParOptionBuilder},
+        parser::ParsedArguments,
+    },
+    tasks::{JabuTask, JabuTaskDependencySpec, TaskError, TaskResult},
+    tools::JavaHome,
+    utils::{
+        classfile::{relocate_class, Relocation},
+        jar::{directory_entries, entries_modification_time, write_jar, MANIFEST_PATH},
+    },
+};
+use jabu_config::{fsutils::libs, model::JabuProject};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    path::{Path, PathBuf},
+};
+use zip::ZipArchive;
+
+const SERVICES_DIRECTORY: &str = "META-INF/services/";
+
+#[derive(Debug, Default)]
+pub struct FatJarTask;
+
+impl JabuTask for FatJarTask {
+    fn description(&self) -> String {
+        "Creates an executable jar containing the classes of the project and of all its dependencies."
+            .to_string()
+    }
+
+    fn execute(
+        &self,
+        _: Vec<String>,
+        parsed_args: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        _: &JavaHome,
+    ) -> TaskResult {
+        // Safe to unwrap, since the trait method `JabuTask::options` returns `Some()`
+        let parsed_args = parsed_args.unwrap();
+        let relocations = match parsed_args.get_option_value("relocate").cloned().flatten() {
+            Some(relocations) => parse_relocations(&relocations)?,
+            None => Vec::new(),
+        };
+
+        let jar_path = Self::jar_path(jabu_config);
+        let report = create_fat_jar(Path::new("."), jabu_config, &jar_path, &relocations)?;
+
+        report.duplicates.iter().for_each(|duplicate| {
+            println!(
+                "Duplicate class '{}': using the one from '{}', ignoring the one from '{}'.",
+                duplicate.name, duplicate.kept_from, duplicate.ignored_from
+            )
+        });
+        if !report.duplicates.is_empty() && parsed_args.has_option_with_name("fail-on-duplicates") {
+            std::fs::remove_file(&jar_path)?;
+            return Err(TaskError::Generic(format!(
+                "Found {} duplicate classes while creating the fat jar.",
+                report.duplicates.len()
+            )));
+        }
+
+        println!(
+            "Fat jar created at '{}' ({} entries, {} merged service files, {} signature files dropped).",
+            jar_path.to_string_lossy(),
+            report.entries,
+            report.merged_services,
+            report.dropped_signatures
+        );
+        Ok(())
+    }
+
+    fn get_dependency_task_specs(&self) -> JabuTaskDependencySpec {
+        JabuTaskDependencySpec::new(vec![
+            ("build".to_string(), Vec::new()),
+            ("resources".to_string(), Vec::new()),
+        ])
+    }
+
+    fn required_tools(&self) -> &[&'static str] {
+        &["javac"]
+    }
+
+    fn options(&self) -> Option<Options> {
+        let mut options = Options::default();
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("fail-on-duplicates")
+                .short('d')
+                .description("Fail if the same class is found more than once, instead of keeping the first one.")
+                .has_arg(false)
+                .required(false)
+                .build(),
+        );
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("relocate")
+                .short('r')
+                .description("Packages to relocate, separated by commas (i.e. 'com.google=shaded.com.google').")
+                .has_arg(true)
+                .required(false)
+                .build(),
+        );
+        Some(options)
+    }
+
+    fn inputs(&self, jabu_config: &JabuProject) -> Vec<PathBuf> {
+        vec![
+            jabu_config.fs_schema.target_classes(),
+            PathBuf::from(&jabu_config.fs_schema.lib),
+        ]
+    }
+
+    fn outputs(&self, jabu_config: &JabuProject) -> Vec<PathBuf> {
+        vec![Self::jar_path(jabu_config)]
+    }
+}
+
+impl FatJarTask {
+    /// Path of the fat jar created by the task.
+    fn jar_path(jabu_config: &JabuProject) -> PathBuf {
+        jabu_config
+            .fs_schema
+            .target_bin()
+            .join(jabu_config.jar_name() + "-all.jar")
+    }
+}
+
+/// Parses a list of relocations separated by commas, with the form `from=to`.
+pub fn parse_relocations(value: &str) -> Result<Vec<Relocation>, TaskError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|relocation| !relocation.is_empty())
+        .map(|relocation| match relocation.split_once('=') {
+            Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {
+                Ok(Relocation::new(from.trim(), to.trim()))
+            }
+            _ => Err(TaskError::InvalidConfig(
+                format!("Invalid relocation '{relocation}', expected 'from=to'.").into(),
+            )),
+        })
+        .collect()
+}
+
+/// A class found more than once while creating a fat jar.
+#[derive(Debug, PartialEq)]
+pub struct DuplicateClass {
+    /// Path of the class inside of the jar.
+    pub name: String,
+
+    /// Origin (*the classes directory or a dependency*) of the class that is kept.
+    pub kept_from: String,
+
+    /// Origin of the class that is ignored.
+    pub ignored_from: String,
+}
+
+/// Summary of the contents of a fat jar created by [`create_fat_jar`].
+#[derive(Debug, Default, PartialEq)]
+pub struct FatJarReport {
+    /// Number of files of the jar (*including the manifest*).
+    pub entries: usize,
+
+    /// Number of service files (*`META-INF/services/*`*) found in more than one origin.
+    pub merged_services: usize,
+
+    /// Number of signature files of the dependencies that have been left out.
+    pub dropped_signatures: usize,
+
+    /// Classes with different contents found in more than one origin.
+    pub duplicates: Vec<DuplicateClass>,
+}
+
+/// Entries of the fat jar, in the order they are added.
+#[derive(Default)]
+struct FatJarEntries {
+    entries: Vec<(String, Vec<u8>)>,
+    origins: HashMap<String, (usize, String)>,
+    services: Vec<(String, Vec<String>)>,
+    report: FatJarReport,
+}
+
+impl FatJarEntries {
+    fn add(
+        &mut self,
+        name: String,
+        contents: Vec<u8>,
+        origin: &str,
+        relocations: &[Relocation],
+    ) -> TaskResult {
+        // A single manifest is generated for the whole jar.
+        if name == MANIFEST_PATH {
+            return Ok(());
+        }
+        if is_signature_file(&name) {
+            self.report.dropped_signatures += 1;
+            return Ok(());
+        }
+
+        if let Some(service) = name
+            .strip_prefix(SERVICES_DIRECTORY)
+            .filter(|s| !s.is_empty())
+        {
+            let service = relocate_binary_name(service, relocations);
+            let providers = String::from_utf8_lossy(&contents)
+                .lines()
+                .map(|line| {
+                    line.split('#')
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string()
+                })
+                .filter(|line| !line.is_empty())
+                .map(|provider| relocate_binary_name(&provider, relocations))
+                .collect::<Vec<String>>();
+            self.add_service(service, providers);
+            return Ok(());
+        }
+
+        let contents = if name.ends_with(".class") && !relocations.is_empty() {
+            relocate_class(&contents, relocations).map_err(|e| {
+                TaskError::Generic(format!(
+                    "The class '{name}' of '{origin}' couldn't be relocated: {e}"
+                ))
+            })?
+        } else {
+            contents
+        };
+        let name = relocations
+            .iter()
+            .find_map(|relocation| relocation.relocate_path(&name))
+            .unwrap_or(name);
+
+        match self.origins.get(&name) {
+            Some((index, kept_from)) => {
+                if name.ends_with(".class") && self.entries[*index].1 != contents {
+                    self.report.duplicates.push(DuplicateClass {
+                        name,
+                        kept_from: kept_from.clone(),
+                        ignored_from: origin.to_string(),
+                    });
+                }
+            }
+            None => {
+                self.origins
+                    .insert(name.clone(), (self.entries.len(), origin.to_string()));
+                self.entries.push((name, contents));
+            }
+        }
+        Ok(())
+    }
+
+    fn add_service(&mut self, service: String, providers: Vec<String>) {
+        match self.services.iter_mut().find(|(name, _)| *name == service) {
+            Some((_, existing)) => {
+                self.report.merged_services += 1;
+                providers.into_iter().for_each(|provider| {
+                    if !existing.contains(&provider) {
+                        existing.push(provider);
+                    }
+                });
+            }
+            None => self.services.push((service, providers)),
+        }
+    }
+}
+
+/// Creates a fat jar at `jar_path` with the classes (*and resources*) of the project
+/// located at `project_directory` and the contents of every jar of its `lib`
+/// directory, relocating the packages given in `relocations`.
+///
+/// The classes of the project take precedence over the ones of the dependencies,
+/// the service files are merged, the signature files of the dependencies are
+/// left out (*since they would no longer be valid*) and a single manifest is
+/// generated from [`JabuProject::manifest`].
+pub fn create_fat_jar(
+    project_directory: &Path,
+    jabu_config: &JabuProject,
+    jar_path: &Path,
+    relocations: &[Relocation],
+) -> Result<FatJarReport, TaskError> {
+    let mut entries = FatJarEntries::default();
+
+    let classes_dir = project_directory.join(jabu_config.fs_schema.target_classes());
+    for (name, contents) in directory_entries(&classes_dir)? {
+        entries.add(name, contents, "classes", relocations)?;
+    }
+
+    let mut libs = libs(Some(project_directory.to_path_buf()), jabu_config);
+    libs.sort();
+    for lib in libs {
+        add_jar_entries(&mut entries, &lib, relocations)?;
+    }
+
+    let services = entries.services.into_iter().map(|(service, providers)| {
+        (
+            format!("{SERVICES_DIRECTORY}{service}"),
+            (providers.join("\n") + "\n").into_bytes(),
+        )
+    });
+    let jar_entries = entries
+        .entries
+        .into_iter()
+        .chain(services)
+        .collect::<BTreeMap<String, Vec<u8>>>();
+    write_jar(
+        jar_path,
+        &jabu_config.manifest,
+        &jar_entries,
+        entries_modification_time(),
+    )?;
+
+    entries.report.entries = 1 + jar_entries.len();
+    Ok(entries.report)
+}
+
+fn add_jar_entries(
+    entries: &mut FatJarEntries,
+    lib: &Path,
+    relocations: &[Relocation],
+) -> TaskResult {
+    let origin = lib
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let mut archive = ZipArchive::new(std::fs::File::open(lib)?).map_err(|e| {
+        TaskError::Generic(format!("The dependency '{origin}' couldn't be read: {e}"))
+    })?;
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(|e| {
+            TaskError::Generic(format!("The dependency '{origin}' couldn't be read: {e}"))
+        })?;
+        // The module descriptors of the dependencies don't describe the fat jar.
+        if file.is_dir() || file.name().ends_with("module-info.class") {
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut contents = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut contents)?;
+        entries.add(name, contents, &origin, relocations)?;
+    }
+    Ok(())
+}
+
+fn relocate_binary_name(name: &str, relocations: &[Relocation]) -> String {
+    relocations
+        .iter()
+        .find_map(|relocation| relocation.relocate_binary_name(name))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Returns whether the path is the signature of a signed jar (*i.e. `META-INF/APP.SF`*).
+fn is_signature_file(name: &str) -> bool {
+    match name.strip_prefix("META-INF/") {
+        Some(file) if !file.contains('/') => {
+            let file = file.to_uppercase();
+            file.starts_with("SIG-")
+                || [".SF", ".DSA", ".RSA", ".EC"]
+                    .iter()
+                    .any(|extension| file.ends_with(extension))
+        }
+        _ => false,
+    }
+}
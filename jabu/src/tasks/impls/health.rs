@@ -1,18 +1,69 @@
 use crate::{
     args::parser::ParsedArguments,
-    tools::JavaHome,
+    tools::{discover_jdks, select_jdk, JavaHome},
     tasks::{
         Task,
         TaskResult
     }
 };
+use jabu_config::fsutils::project_from_directory;
+use prettytable::{color, Attr, Cell, Row};
+use std::path::PathBuf;
 
 #[derive(Debug, Default)]
 pub struct HealthCheckTask;
 
 impl Task for HealthCheckTask {
     fn execute(&self, _: Vec<String>, _: Option<ParsedArguments>) -> TaskResult {
-        let java_home = JavaHome::new()?;
+        let jdks = discover_jdks();
+        let preferred = std::env::var_os("JAVA_HOME").map(PathBuf::from);
+
+        // The JDK the project in the current directory would use, if there's one.
+        let project = project_from_directory(None).ok();
+        let selected = project.as_ref().and_then(|project| {
+            select_jdk(&jdks, project.java_config.java_version, preferred.as_deref())
+        });
+
+        let mut table = prettytable::Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_BORDERS_ONLY);
+        table.set_titles(Row::new(vec![
+            Cell::new("Version"),
+            Cell::new("Java home"),
+            Cell::new("Used by the project"),
+        ]));
+        jdks.iter().for_each(|jdk| {
+            let used = if Some(jdk) == selected {
+                Cell::new("Yes").with_style(Attr::ForegroundColor(color::GREEN)).with_style(Attr::Bold)
+            } else {
+                Cell::new("")
+            };
+            table.add_row(Row::new(vec![
+                Cell::new(&jdk.version).with_style(Attr::ForegroundColor(color::BLUE)),
+                Cell::new(&jdk.home.to_string_lossy()),
+                used,
+            ]));
+        });
+        println!("==> JDKS FOUND ({})", jdks.len());
+        table.printstd();
+
+        let java_home = match (project, selected) {
+            (Some(project), Some(jdk)) => {
+                println!(
+                    "==> The project (java {}) uses the JDK at '{}'",
+                    project.java_config.java_version,
+                    jdk.home.to_string_lossy()
+                );
+                JavaHome::try_from(jdk.home.clone())?
+            }
+            (Some(project), None) => {
+                println!(
+                    "==> None of the JDKs satisfies the project's java version {}",
+                    project.java_config.java_version
+                );
+                return Ok(());
+            }
+            (None, _) => JavaHome::new()?,
+        };
 
         java_home.get_tools();
         java_home.print_tool_availability_table();
@@ -21,7 +72,6 @@ impl Task for HealthCheckTask {
     }
 
     fn description(&self) -> String {
-        "Perform a health check for the tools".to_string()
+        "Perform a health check for the tools, listing the JDKs found".to_string()
     }
 }
-
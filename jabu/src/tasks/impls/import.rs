@@ -0,0 +1,143 @@
+use crate::{
+    args::{
+        options::{Options, ParOptionBuilder},
+        parser::ParsedArguments,
+    },
+    tasks::*,
+};
+use home::home_dir;
+use jabu_config::{fsutils::project_file, model::ArtifactSpec};
+use jaburepo::maven::{artifact_path, Pom};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct ImportTask;
+
+impl Task for ImportTask {
+    fn description(&self) -> String {
+        "Creates the jabu file of an existing Maven project from its 'pom.xml'.".to_string()
+    }
+
+    fn execute(&self, _: Vec<String>, parsed_args: Option<ParsedArguments>) -> TaskResult {
+        // Safe to unwrap, since the trait method `Task::options` returns `Some()`
+        let parsed_args = parsed_args.unwrap();
+        let pom_path = PathBuf::from(
+            parsed_args
+                .get_option_value("file")
+                .cloned()
+                .flatten()
+                .unwrap_or_default(),
+        );
+
+        if pom_path.extension().unwrap_or_default() != "xml" {
+            return Err(TaskError::Generic(format!(
+                "'{}' isn't a POM, only Maven projects can be imported.",
+                pom_path.to_string_lossy()
+            )));
+        }
+
+        let project_dir = pom_path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        // An existing jabu file is overwritten in its own format, so that the
+        // project doesn't end up with several of them.
+        let (jabu_file_path, format) = project_file(&project_dir)?;
+        if jabu_file_path.exists() && !parsed_args.has_option_with_name("force") {
+            return Err(TaskError::Generic(format!(
+                "'{}' already exists, use '--force' to overwrite it.",
+                jabu_file_path.to_string_lossy()
+            )));
+        }
+
+        println!("Reading '{}'...", pom_path.to_string_lossy());
+        let pom = Pom::try_from(std::fs::read_to_string(&pom_path)?.as_str())?;
+        let mut missing_poms = Vec::new();
+        let pom = pom.effective(|spec| {
+            match find_pom(&project_dir, spec)? {
+                Some(pom) => Ok::<Pom, TaskError>(pom),
+                None => {
+                    missing_poms.push(spec.to_maven_coordinates());
+                    Ok(Pom {
+                        group_id: Some(spec.author.clone()),
+                        artifact_id: spec.artifact_id.clone(),
+                        version: Some(spec.version.clone()),
+                        ..Pom::default()
+                    })
+                }
+            }
+        })?;
+
+        let import = pom.import();
+        std::fs::write(
+            &jabu_file_path,
+            format
+                .serialize(&import.project)
+                .map_err(TaskError::Generic)?,
+        )?;
+        println!("'{}' written.", jabu_file_path.to_string_lossy());
+
+        let untranslated: Vec<String> = missing_poms
+            .into_iter()
+            .map(|coordinates| {
+                format!("The parent or BOM {coordinates} couldn't be found, so nothing is inherited from it.")
+            })
+            .chain(import.untranslated)
+            .collect();
+        if untranslated.is_empty() {
+            println!("Everything has been imported.");
+        } else {
+            println!("==> NOT IMPORTED ({})", untranslated.len());
+            untranslated
+                .iter()
+                .for_each(|description| println!("\t- {description}"));
+        }
+        Ok(())
+    }
+
+    fn options(&self) -> Option<Options> {
+        let mut options = Options::default();
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("file")
+                .short('f')
+                .description("Path of the POM to import.")
+                .has_arg(true)
+                .default_value("pom.xml".to_string())
+                .build(),
+        );
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("force")
+                .description("Overwrites the jabu file if it already exists.")
+                .has_arg(false)
+                .required(false)
+                .build(),
+        );
+        Some(options)
+    }
+}
+
+/// Looks for the POM with the given coordinates (*a parent or an imported BOM*)
+/// in the directories containing the project, and in the local Maven repository.
+fn find_pom(project_dir: &Path, spec: &ArtifactSpec) -> Result<Option<Pom>, TaskError> {
+    let project_dir = project_dir.canonicalize()?;
+    let candidates = project_dir
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join("pom.xml"))
+        .chain(home_dir().map(|home| {
+            home.join(".m2")
+                .join("repository")
+                .join(artifact_path(spec, "pom"))
+        }));
+
+    for candidate in candidates.filter(|candidate| candidate.exists()) {
+        let pom = Pom::try_from(std::fs::read_to_string(&candidate)?.as_str())?;
+        if pom.spec() == *spec {
+            return Ok(Some(pom));
+        }
+    }
+    Ok(None)
+}
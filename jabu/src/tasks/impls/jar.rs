@@ -1,14 +1,11 @@
 use crate::{
     args::parser::ParsedArguments,
-    tasks::{JabuTask, JabuTaskDependencySpec, TaskError, TaskResult},
-    tools::{JarToolConfig, JavaHome},
-    utils::{exec_cmd, FSNodeType},
+    tasks::{JabuTask, JabuTaskDependencySpec, TaskResult},
+    tools::JavaHome,
+    utils::jar::{directory_entries, entries_modification_time, write_jar},
 };
 use jabu_config::prelude::*;
-use std::{
-    collections::HashMap,
-    path::{Path, PathBuf},
-};
+use std::path::PathBuf;
 
 #[derive(Default)]
 pub struct JarTask;
@@ -23,83 +20,48 @@ impl JabuTask for JarTask {
         _: Vec<String>,
         _: Option<ParsedArguments>,
         jabu_config: &JabuProject,
-        java_home: &JavaHome,
+        _: &JavaHome,
     ) -> TaskResult {
-        let mut jar_tool_config = JarToolConfig::new(
-            Path::new(&jabu_config.fs_schema.target_bin())
-                .join(jabu_config.display_name() + ".jar")
-                .to_string_lossy()
-                .to_string(),
-            jabu_config
-                .fs_schema
-                .target_classes()
-                .to_string_lossy()
-                .to_string(),
-        );
-        let jar_path = java_home
-            .get_jar()
-            .as_ref()
-            .unwrap()
-            .to_string_lossy()
-            .to_string();
-        let manifest_path: PathBuf;
-        let status_code: i32;
-
-        // Create the target/bin dir + Create the manifest
-        std::fs::create_dir_all(&jabu_config.fs_schema.target_bin())?;
-        manifest_path = JarTask::write_manifest(&jabu_config)?;
+        let jar_path = Self::jar_path(jabu_config);
+        let entries = directory_entries(&jabu_config.fs_schema.target_classes())?;
 
-        jar_tool_config.manifest_location = Some(manifest_path);
+        // The entries get a fixed modification time, so that the jar is reproducible.
+        write_jar(
+            &jar_path,
+            &jabu_config.manifest,
+            &entries,
+            entries_modification_time(),
+        )?;
+        println!("Jar created at '{}'.", jar_path.to_string_lossy());
+        Ok(())
+    }
 
-        status_code = match exec_cmd(&jar_path, jar_tool_config.into_args()) {
-            Ok(exit_code) => {
-                if let Some(status_code) = exit_code.code() {
-                    status_code
-                } else {
-                    return Err(TaskError::CommandFailed {
-                        command: jar_path,
-                        description: "Command has no exit code (probably due to a SIGINT)"
-                            .to_string(),
-                    });
-                }
-            }
-            Err(e) => {
-                return Err(TaskError::CommandFailed {
-                    command: jar_path,
-                    description: e.to_string(),
-                });
-            }
-        };
+    fn get_dependency_task_specs(&self) -> JabuTaskDependencySpec {
+        JabuTaskDependencySpec::new(vec![
+            ("build".to_string(), Vec::new()),
+            ("resources".to_string(), Vec::new()),
+        ])
+    }
 
-        if status_code != 0 {
-            Err(TaskError::CommandFailed {
-                command: jar_path,
-                description: status_code.to_string(),
-            })
-        } else {
-            Ok(())
-        }
+    fn required_tools(&self) -> &[&'static str] {
+        &["javac"]
     }
-    fn get_dependency_task_specs(&self) -> JabuTaskDependencySpec {
-        let mut specs = HashMap::new();
-        specs.insert("build".to_string(), Vec::new());
 
-        JabuTaskDependencySpec::new(specs)
+    fn inputs(&self, jabu_config: &JabuProject) -> Vec<PathBuf> {
+        vec![jabu_config.fs_schema.target_classes()]
     }
 
-    fn required_tools(&self) -> &[&'static str] {
-        &["javac", "jar"]
+    fn outputs(&self, jabu_config: &JabuProject) -> Vec<PathBuf> {
+        vec![Self::jar_path(jabu_config)]
     }
 }
 
 impl JarTask {
-    /// Writes the manifest into the `target/bin` directory with the name `MANIFEST.MF`,
-    /// if `target/bin` directory didn't exist, it will be created.
-    fn write_manifest(jabu_config: &JabuProject) -> std::io::Result<PathBuf> {
-        let manifest: JarManifest = JarManifest::from(jabu_config.manifest.clone());
-        let manifest_path = Path::new(&jabu_config.fs_schema.target_bin()).join("MANIFEST.MF");
-        std::fs::create_dir_all(jabu_config.fs_schema.target_bin())?;
-        manifest.write_to_file(manifest_path.as_path())?;
-        Ok(manifest_path)
+    /// Path of the jar created by the task.
+    fn jar_path(jabu_config: &JabuProject) -> PathBuf {
+        jabu_config
+            .fs_schema
+            .target_bin()
+            .join(jabu_config.jar_name() + ".jar")
     }
 }
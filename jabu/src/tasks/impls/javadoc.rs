@@ -8,6 +8,7 @@ use crate::{
     utils::exec_cmd,
 };
 use jabu_config::{fsutils::java_sources, prelude::*};
+use std::path::PathBuf;
 
 #[derive(Default)]
 pub struct JavadocTask;
@@ -130,4 +131,12 @@ impl JabuTask for JavadocTask {
 
         Some(options)
     }
+
+    fn inputs(&self, jabu_project: &JabuProject) -> Vec<PathBuf> {
+        vec![PathBuf::from(&jabu_project.fs_schema.source)]
+    }
+
+    fn outputs(&self, jabu_project: &JabuProject) -> Vec<PathBuf> {
+        vec![jabu_project.fs_schema.target_docs()]
+    }
 }
@@ -37,7 +37,7 @@ impl JabuTask for JPackageTask {
         };
         let output_type = parsed_args.get_option_value("output-type").unwrap_or(&None);
         let input_jar_location = PathBuf::from(jabu_config.fs_schema.target_bin())
-            .join(format!("{}.jar", jabu_config.display_name()));
+            .join(format!("{}.jar", jabu_config.jar_name()));
         let jpackage_config = JPackageToolConfig::new(
             input_jar_location,
             jabu_config.header.project_name.clone(),
@@ -84,10 +84,7 @@ impl JabuTask for JPackageTask {
     }
 
     fn get_dependency_task_specs(&self) -> JabuTaskDependencySpec {
-        let mut spec = std::collections::HashMap::new();
-        spec.insert("jar".to_string(), Vec::new());
-
-        JabuTaskDependencySpec::new(spec)
+        JabuTaskDependencySpec::new(vec![("jar".to_string(), Vec::new())])
     }
 
     fn options(&self) -> Option<Options> {
@@ -2,6 +2,7 @@ mod run;
 mod new;
 mod display_info;
 mod build;
+mod compile_state;
 mod version;
 mod scripts;
 mod clean;
@@ -10,12 +11,22 @@ mod javadoc;
 mod health;
 mod jpackage;
 mod publish;
+mod test;
+mod import;
+mod resources;
+mod fatjar;
+mod custom;
+mod watch;
 
+pub mod config;
 pub mod deps;
+pub mod export;
+pub mod templates;
 pub use run::*;
 pub use new::*;
 pub use display_info::*;
 pub use build::*;
+pub use compile_state::*;
 pub use version::*;
 pub use scripts::*;
 pub use clean::*;
@@ -24,3 +35,9 @@ pub use javadoc::*;
 pub use health::*;
 pub use jpackage::*;
 pub use publish::*;
+pub use test::*;
+pub use import::*;
+pub use resources::*;
+pub use fatjar::*;
+pub use custom::*;
+pub use watch::*;
@@ -5,21 +5,19 @@ use crate::{
     },
     tasks::*,
 };
-use jabu_config::model::{java::ProjectType, JabuProject, JABU_FILE_NAME};
+use super::templates::{Template, TemplateVariables};
+use jabu_config::model::{
+    java::ProjectType, java_package_name, JabuProject, Workspace, JABU_FILE_NAME,
+    JABU_WORKSPACE_FILE_NAME,
+};
 use std::{collections::HashSet, path::Path};
 
 #[derive(Debug, Default)]
 pub struct NewProjectTask;
 
-impl Task for NewProjectTask {
-    fn description(&self) -> String {
-        "Creates a new project.".to_string()
-    }
-
-    fn execute(&self, args: Vec<String>, parsed_args: Option<ParsedArguments>) -> TaskResult {
-        // Safe to unwrap, since the trait method `Task::options` returns `Some()`
-        let parsed_args = parsed_args.unwrap();
-
+impl NewProjectTask {
+    /// Creates the project given by the arguments inside of `directory`.
+    pub fn create_in(&self, directory: &Path, parsed_args: &ParsedArguments) -> TaskResult {
         // Safely unwrap since if this options were missing it would have been caught
         // while parsing with the options.
         let new_project_name = parsed_args
@@ -28,8 +26,7 @@ impl Task for NewProjectTask {
             .as_ref()
             .unwrap()
             .as_str();
-        let new_project_path =
-            Path::new(std::env::current_dir().unwrap().as_os_str()).join(new_project_name);
+        let new_project_path = directory.join(new_project_name);
         let project_type = match ProjectType::try_from(
             parsed_args
                 .get_option_value("project-type")
@@ -43,39 +40,75 @@ impl Task for NewProjectTask {
                 let mut errors = HashSet::new();
                 errors.insert(InvalidArgError::InvalidOptionValue {
                     option_name: "project-type".to_owned(),
-                    error_msg: "Invalid type given.".to_string(),
+                    error_msg: format!(
+                        "Invalid type given, the available types are: {}.",
+                        ProjectType::ALL.map(|project_type| project_type.name()).join(", ")
+                    ),
                 });
                 return Err(TaskError::InvalidArguments(errors));
             }
         };
 
+        let author = parsed_args
+            .get_option_value("author")
+            .cloned()
+            .flatten()
+            .unwrap_or_default();
+        // Find the template before creating anything, so nothing is left behind if it doesn't exist.
+        let template = match parsed_args.get_option_value("template").cloned().flatten() {
+            Some(template) => Some(Template::find(&template)?),
+            None => None,
+        };
+
         match std::fs::create_dir(new_project_path.clone()) {
             Err(e) => return Err(TaskError::IOError(e)),
             _ => (),
         }
 
-        let project_config = JabuProject::default_of_name(new_project_name, project_type);
-
-        match jabu_config::fsutils::create_project(None, &project_config) {
-            Ok(_) => (),
-            Err(e) => return Err(TaskError::IOError(e)),
+        if let Some(template) = template {
+            let variables = TemplateVariables {
+                project_name: new_project_name.to_string(),
+                author,
+                package: java_package_name(new_project_name),
+            };
+            if let Err(e) = template.create_project(&new_project_path, &variables) {
+                let _ = std::fs::remove_dir_all(&new_project_path);
+                return Err(e);
+            }
+            println!("Project created.");
+            return Ok(());
         }
 
-        // Write the project's config into a jabu file.
-        let jabu_file_path = new_project_path
-            .join(JABU_FILE_NAME)
-            .to_string_lossy()
-            .to_string();
-        match std::fs::write(
-            jabu_file_path,
-            ron::ser::to_string_pretty(&project_config, ron::ser::PrettyConfig::default()).unwrap(),
-        ) {
-            Ok(_) => {
-                println!("Project created.");
-                Ok(())
+        if project_type == ProjectType::MultiModule {
+            let (workspace, mut members) = Workspace::new_multi_module();
+            for member in &mut members {
+                member.header.author = author.clone();
+                create_project(&new_project_path, member)?;
             }
-            Err(e) => Err(TaskError::IOError(e)),
+            std::fs::write(
+                new_project_path.join(JABU_WORKSPACE_FILE_NAME),
+                ron::ser::to_string_pretty(&workspace, ron::ser::PrettyConfig::default()).unwrap(),
+            )?;
+            println!("Project created.");
+            return Ok(());
         }
+
+        let mut project_config = JabuProject::default_of_name(new_project_name, project_type);
+        project_config.header.author = author;
+        create_project(directory, &project_config)?;
+        println!("Project created.");
+        Ok(())
+    }
+}
+
+impl Task for NewProjectTask {
+    fn description(&self) -> String {
+        "Creates a new project.".to_string()
+    }
+
+    fn execute(&self, _: Vec<String>, parsed_args: Option<ParsedArguments>) -> TaskResult {
+        // Safe to unwrap, since the trait method `Task::options` returns `Some()`
+        self.create_in(&std::env::current_dir()?, &parsed_args.unwrap())
     }
 
     fn options(&self) -> Option<Options> {
@@ -95,11 +128,48 @@ impl Task for NewProjectTask {
                 .name("project-type")
                 .short('t')
                 .has_arg(true)
-                .description("Defines the type of project to be created.")
-                .default_value("binary".to_string())
+                .description(&format!(
+                    "Defines the type of project to be created ({}).",
+                    ProjectType::ALL.map(|project_type| project_type.name()).join(", ")
+                ))
+                .default_value(ProjectType::Application.name().to_string())
+                .build(),
+        );
+
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("author")
+                .short('a')
+                .has_arg(true)
+                .description("Author of the new project.")
+                .default_value("anon".to_string())
+                .build(),
+        );
+
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("template")
+                .has_arg(true)
+                .required(false)
+                .description("Creates the project from a template: a directory, the name of a template in '~/.config/jabu/templates', or the spec of a published template (the project type is ignored).")
                 .build(),
         );
 
         Some(options)
     }
 }
+
+/// Creates the file structure of the project inside of the given directory, and
+/// writes its jabu file.
+fn create_project(base_directory: &Path, project_config: &JabuProject) -> TaskResult {
+    jabu_config::fsutils::create_project(Some(base_directory.to_path_buf()), project_config)?;
+
+    // Write the project's config into a jabu file.
+    std::fs::write(
+        base_directory
+            .join(&project_config.header.project_name)
+            .join(JABU_FILE_NAME),
+        ron::ser::to_string_pretty(project_config, ron::ser::PrettyConfig::default()).unwrap(),
+    )?;
+    Ok(())
+}
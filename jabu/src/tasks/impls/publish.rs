@@ -4,19 +4,30 @@ use crate::{
     tasks::{JabuTask, JabuTaskDependencySpec, TaskError, TaskResult},
     tools::JavaHome,
 };
-use jabu_config::model::{ArtifactSpec, JabuProject};
-use jaburepo::repository::Repository;
+use jabu_config::model::JabuProject;
+use jaburepo::{maven::generate_pom, repository::Repository};
 use reqwest::blocking::multipart::{Form, Part};
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufReader, Read},
-    path::PathBuf,
-};
+use std::path::Path;
 
 #[derive(Default)]
 pub struct PublishTask;
 
+/// Returns the contents of the jar of the project in `directory`, and the
+/// jaburon (*the project's configuration, in RON*) published along with it.
+pub fn artifact_parts(
+    directory: &Path,
+    jabu_config: &JabuProject,
+) -> Result<(Vec<u8>, String), TaskError> {
+    let jar_path = directory
+        .join(jabu_config.fs_schema.target_bin())
+        .join(jabu_config.jar_name() + ".jar");
+    println!("Reading from file '{}'...", jar_path.to_string_lossy());
+    let jar = std::fs::read(&jar_path)?;
+    let jaburon = ron::ser::to_string_pretty(jabu_config, ron::ser::PrettyConfig::default())
+        .map_err(|e| TaskError::Generic(e.to_string()))?;
+    Ok((jar, jaburon))
+}
+
 impl JabuTask for PublishTask {
     fn description(&self) -> String {
         "Publish the current project to the repository.".to_string()
@@ -36,8 +47,8 @@ impl JabuTask for PublishTask {
         // TODO: PUBLISH-TASK
         // - Use PathBuf to join paths?
         // - Refactor the author_key variable's related code.
-        let author_key = options.unwrap();
-        let author_key = author_key.options.get("author-key");
+        let options = options.unwrap();
+        let author_key = options.options.get("author-key");
         let proj_dir = std::env::current_dir().unwrap();
         let post_url = format!(
             "{url}/api/upload/{}/{}/{}/{}",
@@ -47,45 +58,16 @@ impl JabuTask for PublishTask {
             author_key.unwrap().clone().unwrap()
         );
 
-        let jaburon_path = proj_dir.join("jabu.ron");
-
-        let mut files_content = Vec::new();
-
-        // TODO: Refactor?
-        let _ = [
-            jabu_config.fs_schema.target_bin().join(
-                ArtifactSpec::new(
-                    &jabu_config.header.author,
-                    &jabu_config.header.project_name,
-                    &jabu_config.header.version,
-                )
-                .to_string()
-                    + ".jar",
-            ),
-            jaburon_path,
-        ]
-        .iter()
-        .try_for_each(|path| {
-            let f = File::open(&path)?;
-            let mut b_reader = BufReader::new(f);
-            let mut contents = Vec::new();
-
-            println!(
-                "Reading from file '{}'...",
-                path.to_string_lossy().to_string()
-            );
-
-            b_reader.read_to_end(&mut contents)?;
-            files_content.push(contents);
-            Ok::<(), std::io::Error>(())
-        });
-
-        let form = Form::new();
-
-        let jaburon_part = Part::bytes(files_content.pop().unwrap());
-        let jar_part = Part::bytes(files_content.pop().unwrap());
-
-        let form = form.part("jaburon", jaburon_part).part("jar", jar_part);
+        let (jar, jaburon) = artifact_parts(&proj_dir, jabu_config)?;
+        let form = Form::new()
+            .part("jaburon", Part::text(jaburon))
+            .part("jar", Part::bytes(jar));
+        let form = if options.has_option_with_name("with-pom") {
+            println!("Attaching the generated POM...");
+            form.part("pom", Part::text(generate_pom(jabu_config)))
+        } else {
+            form
+        };
 
         let client = reqwest::blocking::Client::new();
         let resp = client.post(post_url).multipart(form).send()?;
@@ -111,19 +93,23 @@ impl JabuTask for PublishTask {
                 .required(true)
                 .build(),
         );
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("with-pom")
+                .description("Attaches a Maven 'pom.xml' generated from the project's configuration to the artifact.")
+                .has_arg(false)
+                .required(false)
+                .build(),
+        );
 
         Some(options)
     }
 
     fn get_dependency_task_specs(&self) -> JabuTaskDependencySpec {
-        let mut specs = HashMap::new();
-
-        specs.insert("jar".to_string(), Vec::new());
-
-        JabuTaskDependencySpec::new(specs)
+        JabuTaskDependencySpec::new(vec![("jar".to_string(), Vec::new())])
     }
 
     fn required_tools(&self) -> &[&'static str] {
-        &["jar"]
+        &["javac"]
     }
 }
@@ -0,0 +1,184 @@
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{JabuTask, TaskError, TaskResult},
+    tools::JavaHome,
+    utils::hashing::sha256_hex,
+};
+use glob::{MatchOptions, Pattern};
+use jabu_config::model::{ConfigHeader, JabuProject};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Name of the file (*inside of [`jabu_config::model::FsSchema::target_jabu`]*)
+/// where the resources copied by the last processing are stored.
+pub const RESOURCES_STATE_FILE_NAME: &str = "resources-state.ron";
+
+#[derive(Debug, Default)]
+pub struct ProcessResourcesTask;
+
+impl JabuTask for ProcessResourcesTask {
+    fn description(&self) -> String {
+        "Copies the resources of the project into its classes (only copying what has changed).".to_string()
+    }
+
+    fn execute(
+        &self,
+        _: Vec<String>,
+        _: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        _: &JavaHome,
+    ) -> TaskResult {
+        let report = process_resources(Path::new("."), jabu_config)?;
+        if report.copied == 0 && report.removed == 0 {
+            println!("All resources are up to date.");
+        } else {
+            println!(
+                "Resources processed: {} copied, {} removed, {} up to date.",
+                report.copied, report.removed, report.up_to_date
+            );
+        }
+        Ok(())
+    }
+
+    fn inputs(&self, jabu_config: &JabuProject) -> Vec<PathBuf> {
+        vec![PathBuf::from(&jabu_config.fs_schema.resources)]
+    }
+
+    fn outputs(&self, jabu_config: &JabuProject) -> Vec<PathBuf> {
+        vec![jabu_config.fs_schema.target_classes()]
+    }
+}
+
+/// Resources copied by the last processing, with their path relative to the
+/// resources directory as key, and the hash of the copied contents as value.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct ResourcesState {
+    resources: BTreeMap<String, String>,
+}
+
+/// Number of resources affected by [`process_resources`].
+#[derive(Debug, Default, PartialEq)]
+pub struct ResourcesReport {
+    pub copied: usize,
+    pub removed: usize,
+    pub up_to_date: usize,
+}
+
+/// Copies the resources of the project located at `project_directory` into its
+/// classes directory, replacing the placeholders of the filtered resources (*see
+/// [`jabu_config::model::FsSchema::filtered_resources`]*).
+///
+/// Only the resources that have changed since the last processing are copied, and
+/// the copies of the resources that no longer exist are removed.
+pub fn process_resources(
+    project_directory: &Path,
+    jabu_config: &JabuProject,
+) -> Result<ResourcesReport, TaskError> {
+    let fs_schema = &jabu_config.fs_schema;
+    let resources_dir = project_directory.join(&fs_schema.resources);
+    let classes_dir = project_directory.join(fs_schema.target_classes());
+    let state_path = project_directory
+        .join(fs_schema.target_jabu())
+        .join(RESOURCES_STATE_FILE_NAME);
+
+    let filters = fs_schema
+        .filtered_resources
+        .iter()
+        .map(|pattern| Pattern::new(pattern))
+        .collect::<Result<Vec<Pattern>, _>>()
+        .map_err(|e| TaskError::InvalidConfig(Box::new(e)))?;
+    let match_options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::default()
+    };
+
+    let previous_state: ResourcesState = std::fs::read_to_string(&state_path)
+        .ok()
+        .and_then(|state| ron::from_str(&state).ok())
+        .unwrap_or_default();
+    let mut state = ResourcesState::default();
+    let mut report = ResourcesReport::default();
+
+    for resource in resource_files(&resources_dir) {
+        let mut contents = std::fs::read(resources_dir.join(&resource))?;
+        if filters
+            .iter()
+            .any(|filter| filter.matches_with(&resource, match_options))
+        {
+            // Only text resources have placeholders.
+            if let Ok(text) = String::from_utf8(contents.clone()) {
+                contents = replace_placeholders(&text, &jabu_config.header).into_bytes();
+            }
+        }
+
+        let hash = sha256_hex(&contents);
+        let target = classes_dir.join(&resource);
+        if previous_state.resources.get(&resource) == Some(&hash) && target.exists() {
+            report.up_to_date += 1;
+        } else {
+            std::fs::create_dir_all(target.parent().unwrap_or(&classes_dir))?;
+            std::fs::write(&target, contents)?;
+            report.copied += 1;
+        }
+        state.resources.insert(resource, hash);
+    }
+
+    for removed in previous_state
+        .resources
+        .keys()
+        .filter(|resource| !state.resources.contains_key(*resource))
+    {
+        match std::fs::remove_file(classes_dir.join(removed)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => report.removed += 1,
+        }
+    }
+
+    std::fs::create_dir_all(state_path.parent().unwrap_or(project_directory))?;
+    std::fs::write(
+        state_path,
+        ron::to_string(&state).map_err(|e| TaskError::Generic(e.to_string()))?,
+    )?;
+    Ok(report)
+}
+
+/// Returns the paths of the files inside of the resources directory, relative
+/// to it and with `/` as separator.
+fn resource_files(resources_dir: &Path) -> Vec<String> {
+    walkdir::WalkDir::new(resources_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let relative_path = entry.path().strip_prefix(resources_dir).ok()?;
+            Some(
+                relative_path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            )
+        })
+        .collect()
+}
+
+/// Replaces the `${project.*}` placeholders of the text with the values of the
+/// header (*`name`, `author`, `description`, `license` and `version`*). Unknown
+/// placeholders are left as they are.
+pub fn replace_placeholders(text: &str, header: &ConfigHeader) -> String {
+    [
+        ("${project.name}", header.project_name.as_str()),
+        ("${project.author}", header.author.as_str()),
+        ("${project.description}", header.description.as_str()),
+        ("${project.license}", header.license.as_deref().unwrap_or_default()),
+        ("${project.version}", header.version.as_str()),
+    ]
+    .iter()
+    .fold(text.to_string(), |text, (placeholder, value)| {
+        text.replace(placeholder, value)
+    })
+}
@@ -1,27 +1,21 @@
 use crate::args::options::{Options, ParOptionBuilder};
 use crate::args::parser::ParsedArguments;
-use crate::tasks::{JabuTask, JabuTaskDependencySpec};
+use crate::tasks::{JabuTask, JabuTaskDependencySpec, TaskError};
 use crate::tools::{JavaExecTarget, JavaHome, JavaToolConfig};
-use jabu_config::model::JabuProject;
-use std::collections::HashMap;
+use jabu_config::{fsutils::path_dependency_classes, model::JabuProject};
+use std::process::{Child, Command};
 
 #[derive(Debug, Default)]
 pub struct Run;
 
-impl JabuTask for Run {
-    fn description(&self) -> String {
-        "Runs the current project".to_string()
-    }
-
-    fn execute(
-        &self,
-        _: Vec<String>,
-        parsed_args: Option<ParsedArguments>,
+impl Run {
+    /// Returns the java binary and the arguments that run the project, or `None` if
+    /// the java installation has no java binary.
+    fn java_command(
+        parsed_args: &ParsedArguments,
         jabu_config: &JabuProject,
         java_home: &JavaHome,
-    ) -> crate::tasks::TaskResult {
-        let parsed_args = parsed_args.unwrap();
-
+    ) -> Result<Option<(String, Vec<String>)>, TaskError> {
         let main_class: &str = if let Some(Some(main_class)) =
             parsed_args.get_option_value("main-class")
         {
@@ -51,6 +45,12 @@ impl JabuTask for Run {
         .map(|entry| entry.to_string_lossy().to_string())
         .collect();
         classpath.extend(jars);
+        // And the classes of the projects it depends on by path.
+        classpath.extend(
+            path_dependency_classes(None, jabu_config)?
+                .iter()
+                .map(|classes| classes.to_string_lossy().to_string()),
+        );
 
         let java_tool_config = JavaToolConfig::new(
             JavaExecTarget::MainClass(main_class.to_string()),
@@ -58,22 +58,60 @@ impl JabuTask for Run {
             Vec::new(),
         );
 
-        if let Some(java_path) = java_home.get_java() {
-            match crate::utils::exec_cmd(java_path.to_str().unwrap(), java_tool_config.into_args())
-            {
-                Err(e) => {
-                    return Err(crate::tasks::TaskError::IOError(e));
-                }
-                _ => (),
+        Ok(java_home.get_java().as_ref().map(|java_path| {
+            (
+                java_path.to_string_lossy().to_string(),
+                java_tool_config.into_args(),
+            )
+        }))
+    }
+}
+
+impl JabuTask for Run {
+    fn description(&self) -> String {
+        "Runs the current project".to_string()
+    }
+
+    fn execute(
+        &self,
+        _: Vec<String>,
+        parsed_args: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        java_home: &JavaHome,
+    ) -> crate::tasks::TaskResult {
+        let parsed_args = parsed_args.unwrap();
+
+        if let Some((java_path, args)) = Self::java_command(&parsed_args, jabu_config, java_home)? {
+            if let Err(e) = crate::utils::exec_cmd(&java_path, args) {
+                return Err(crate::tasks::TaskError::IOError(e));
             }
         }
         Ok(())
     }
 
+    fn spawn(
+        &self,
+        _: Vec<String>,
+        parsed_args: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        java_home: &JavaHome,
+    ) -> Result<Option<Child>, TaskError> {
+        let parsed_args = parsed_args.unwrap();
+
+        match Self::java_command(&parsed_args, jabu_config, java_home)? {
+            Some((java_path, args)) => {
+                println!("==> [CMD]: [{:?} {:?}]", &java_path, &args);
+                Ok(Some(Command::new(java_path).args(args).spawn()?))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn get_dependency_task_specs(&self) -> crate::tasks::JabuTaskDependencySpec {
-        let mut specs = HashMap::new();
-        specs.insert("build".to_string(), Vec::new());
-        JabuTaskDependencySpec::new(specs)
+        JabuTaskDependencySpec::new(vec![
+            ("build".to_string(), Vec::new()),
+            ("resources".to_string(), Vec::new()),
+        ])
     }
 
     fn required_tools(&self) -> &[&'static str] {
@@ -124,7 +124,7 @@ impl ScriptsTask {
     ///
     /// # See
     /// [`Self::read_script`]
-    fn get_script_path(jabu_config: &JabuProject, script_name: impl AsRef<str>) -> PathBuf {
+    pub fn get_script_path(jabu_config: &JabuProject, script_name: impl AsRef<str>) -> PathBuf {
         let script_name = script_name.as_ref();
         PathBuf::from(&jabu_config.fs_schema.scripts).join(format!("{script_name}.rhai"))
     }
@@ -152,11 +152,20 @@ impl ScriptsTask {
     fn run_script(
         jabu_config: &JabuProject,
         path_to_script: PathBuf,
+    ) -> Result<(), Box<EvalAltResult>> {
+        Self::run_script_with_scope(jabu_config, path_to_script, Scope::new())
+    }
+
+    /// Runs the script located at `path_to_script` like [`Self::run_script`], with
+    /// the constants of `scope` available to it as well.
+    pub fn run_script_with_scope(
+        jabu_config: &JabuProject,
+        path_to_script: PathBuf,
+        mut scope: Scope,
     ) -> Result<(), Box<EvalAltResult>> {
         //let proj_cfg = ProjectConfig::new(jabu_config.clone(), std::env::current_dir().unwrap());
         let fs_package = FilesystemPackage::new();
         let mut engine = Engine::new();
-        let mut scope = Scope::new();
 
         // Register types
         engine.build_type::<ProjectConfig>();
@@ -0,0 +1,105 @@
+use super::{remote_repository_url, templates_directory, TEMPLATE_TAG};
+use crate::{
+    args::{
+        options::{Options, ParOptionBuilder},
+        parser::ParsedArguments,
+    },
+    tasks::{Task, TaskResult},
+};
+use jabu_config::{fsutils::project_from_directory, model::JabuProject};
+use jaburepo::version::VersionRequirement;
+
+#[derive(Debug, Default)]
+pub struct ListTemplatesTask;
+
+impl Task for ListTemplatesTask {
+    fn execute(&self, _: Vec<String>, parsed_args: Option<ParsedArguments>) -> TaskResult {
+        // Safe to unwrap, since the trait method `Task::options` returns `Some()`
+        let parsed_args = parsed_args.unwrap();
+
+        match parsed_args.get_option_value("author").cloned().flatten() {
+            Some(author) => list_remote_templates(&author),
+            None => list_local_templates(),
+        }
+    }
+
+    fn description(&self) -> String {
+        "Lists the user's templates, or the ones published by an author with '--author'.".to_string()
+    }
+
+    fn options(&self) -> Option<Options> {
+        let mut options = Options::default();
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("author")
+                .short('a')
+                .description("Author whose published templates are listed.")
+                .has_arg(true)
+                .required(false)
+                .build(),
+        );
+        Some(options)
+    }
+}
+
+fn list_local_templates() -> TaskResult {
+    let Some(directory) = templates_directory().filter(|directory| directory.is_dir()) else {
+        println!("==> No templates found.");
+        return Ok(());
+    };
+
+    let mut templates: Vec<(String, JabuProject)> = std::fs::read_dir(&directory)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let project = project_from_directory(Some(entry.path())).ok()?;
+            Some((entry.file_name().to_string_lossy().to_string(), project))
+        })
+        .collect();
+    templates.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    if templates.is_empty() {
+        println!("==> No templates found in '{}'.", directory.to_string_lossy());
+    } else {
+        println!("==> TEMPLATES ({})", directory.to_string_lossy());
+        templates
+            .iter()
+            .for_each(|(name, project)| println!("\t- {name}: {}", project.header.description));
+    }
+    Ok(())
+}
+
+fn list_remote_templates(author: &str) -> TaskResult {
+    let url = remote_repository_url();
+    let artifacts: Vec<String> = reqwest::blocking::get(format!("{url}/api/list/{author}"))?
+        .error_for_status()?
+        .json()?;
+
+    println!("==> TEMPLATES PUBLISHED BY '{author}'");
+    for artifact in artifacts {
+        let versions: Vec<String> =
+            reqwest::blocking::get(format!("{url}/api/list-versions/{author}/{artifact}"))?
+                .error_for_status()?
+                .json()?;
+        let Some(version) = VersionRequirement::Latest
+            .select(&versions)
+            .or(versions.last().cloned())
+        else {
+            continue;
+        };
+
+        let jaburon = reqwest::blocking::get(format!(
+            "{url}/api/get/{author}/{artifact}/{version}/jaburon"
+        ))?
+        .error_for_status()?
+        .text()?;
+        if let Ok(project) = JabuProject::try_from(jaburon.as_str()) {
+            if project.header.tags.iter().any(|tag| tag == TEMPLATE_TAG) {
+                println!(
+                    "\t- {author}_{artifact}_{version}: {}",
+                    project.header.description
+                );
+            }
+        }
+    }
+    Ok(())
+}
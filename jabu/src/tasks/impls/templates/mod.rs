@@ -0,0 +1,241 @@
+mod list;
+mod publish;
+mod templates_task_manager;
+
+pub use list::*;
+pub use publish::*;
+pub use templates_task_manager::*;
+
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{Task, TaskError, TaskResult},
+    utils::user_config_directory,
+};
+use jabu_config::{
+    fsutils::{create_project, project_from_directory},
+    model::{ArtifactSpec, ConfigHeader, FsSchema, JabuProject, JABU_FILE_NAME},
+};
+use jaburepo::version::VersionRequirement;
+use std::{
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+
+/// Tag of the published artifacts that are project templates.
+pub const TEMPLATE_TAG: &str = "template";
+
+#[derive(Debug, Default)]
+pub struct TemplatesSubtask;
+
+impl Task for TemplatesSubtask {
+    fn is_group(&self) -> bool {
+        true
+    }
+
+    fn execute(&self, args: Vec<String>, _: Option<ParsedArguments>) -> TaskResult {
+        let task_manager = get_templates_task_manager();
+        let task_name = if let Some(task_name) = args.first() {
+            task_name.as_str()
+        } else {
+            "help"
+        };
+
+        task_manager.execute(task_name, args.clone(), ".")
+    }
+
+    fn description(&self) -> String {
+        "Manage the templates used to create new projects.".to_string()
+    }
+}
+
+/// Returns the directory containing the user's templates (*`~/.config/jabu/templates`*),
+/// honoring `XDG_CONFIG_HOME` if it's set.
+pub fn templates_directory() -> Option<PathBuf> {
+    user_config_directory().map(|config| config.join("templates"))
+}
+
+/// Returns the URL of the remote jabu repository.
+pub fn remote_repository_url() -> String {
+    std::env::var("JABU_REMOTE_REPO")
+        .unwrap_or_else(|_| "https://jabu-remote-repository.com".to_string())
+}
+
+/// Values of the placeholders (*i.e. `{{project_name}}`*) of the files of a template.
+#[derive(Debug, PartialEq)]
+pub struct TemplateVariables {
+    pub project_name: String,
+    pub author: String,
+    pub package: String,
+}
+
+impl TemplateVariables {
+    /// Replaces the placeholders in the given text with their values.
+    pub fn render(&self, text: &str) -> String {
+        text.replace("{{project_name}}", &self.project_name)
+            .replace("{{author}}", &self.author)
+            .replace("{{package}}", &self.package)
+    }
+
+    /// Replaces the placeholders in the given path, where the dots of the package
+    /// are directories (*i.e. `src/main/{{package}}` becomes `src/main/com/example`*).
+    pub fn render_path(&self, path: &str) -> String {
+        path.replace("{{package}}", &self.package.replace('.', "/"))
+            .replace("{{project_name}}", &self.project_name)
+            .replace("{{author}}", &self.author)
+    }
+}
+
+/// A template a project can be created from.
+#[derive(Debug, PartialEq)]
+pub enum Template {
+    /// A template directory: either a path, or one of the user's templates.
+    Directory(PathBuf),
+
+    /// A template published to the remote repository.
+    Remote(ArtifactSpec),
+}
+
+impl Template {
+    /// Finds the template referenced by the given value, which is a path to a
+    /// directory, the name of one of the user's templates, or the spec of a
+    /// published template (*i.e. `me_webapp_latest`*), checked in that order.
+    pub fn find(value: &str) -> Result<Self, TaskError> {
+        let path = PathBuf::from(value);
+        if path.is_dir() {
+            return Ok(Self::Directory(path));
+        }
+        if let Some(path) = templates_directory()
+            .map(|directory| directory.join(value))
+            .filter(|path| path.is_dir())
+        {
+            return Ok(Self::Directory(path));
+        }
+        match ArtifactSpec::try_from(value) {
+            Ok(spec) => Ok(Self::Remote(spec)),
+            Err(_) => Err(TaskError::UnavailableResource {
+                resource_name: value.to_string(),
+                error: Some(format!(
+                    "It isn't a directory, nor a template in '{}', nor the spec of a published template.",
+                    templates_directory().unwrap_or_default().to_string_lossy()
+                )),
+            }),
+        }
+    }
+
+    /// Creates the project located at `project_directory` from the template, filling
+    /// the placeholders with the given variables.
+    pub fn create_project(&self, project_directory: &Path, variables: &TemplateVariables) -> TaskResult {
+        match self {
+            Self::Directory(directory) => {
+                create_from_template_directory(directory, project_directory, variables)
+            }
+            Self::Remote(spec) => {
+                let directory = std::env::temp_dir()
+                    .join(format!("jabu-template-{}-{}", spec.artifact_id, std::process::id()));
+                let result = fetch_remote_template(spec, &directory).and_then(|_| {
+                    create_from_template_directory(&directory, project_directory, variables)
+                });
+                let _ = std::fs::remove_dir_all(directory);
+                result
+            }
+        }
+    }
+}
+
+/// Copies the files of the template directory into the project directory,
+/// replacing their placeholders, and creates the directories of the resulting
+/// project.
+fn create_from_template_directory(
+    template_directory: &Path,
+    project_directory: &Path,
+    variables: &TemplateVariables,
+) -> TaskResult {
+    if !template_directory.join(JABU_FILE_NAME).exists() {
+        return Err(TaskError::InvalidConfig(
+            format!(
+                "The template '{}' doesn't contain a '{JABU_FILE_NAME}' file.",
+                template_directory.to_string_lossy()
+            )
+            .into(),
+        ));
+    }
+
+    for entry in walkdir::WalkDir::new(template_directory)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let relative_path = entry
+            .path()
+            .strip_prefix(template_directory)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .to_string();
+        let path = project_directory.join(variables.render_path(&relative_path));
+        std::fs::create_dir_all(path.parent().unwrap_or(project_directory))?;
+
+        // Binary files are copied as they are.
+        let contents = std::fs::read(entry.path())?;
+        match String::from_utf8(contents) {
+            Ok(text) => std::fs::write(path, variables.render(&text))?,
+            Err(e) => std::fs::write(path, e.into_bytes())?,
+        }
+    }
+
+    let project = project_from_directory(Some(project_directory.to_path_buf()))?;
+    let project_name = project_directory
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    create_project(
+        project_directory.parent().map(Path::to_path_buf),
+        &JabuProject {
+            header: ConfigHeader {
+                project_name,
+                ..project.header
+            },
+            fs_schema: FsSchema {
+                generated_files: Default::default(),
+                ..project.fs_schema
+            },
+            ..project
+        },
+    )?;
+    Ok(())
+}
+
+/// Downloads the published template with the given spec, extracting its files
+/// into the given directory.
+fn fetch_remote_template(spec: &ArtifactSpec, directory: &Path) -> TaskResult {
+    let url = remote_repository_url();
+    let requirement = VersionRequirement::from(spec.version.as_str());
+    let version = if requirement.is_exact() {
+        spec.version.clone()
+    } else {
+        let versions: Vec<String> = reqwest::blocking::get(format!(
+            "{url}/api/list-versions/{}/{}",
+            spec.author, spec.artifact_id
+        ))?
+        .error_for_status()?
+        .json()?;
+        requirement
+            .select(&versions)
+            .ok_or_else(|| TaskError::UnavailableResource {
+                resource_name: spec.to_string(),
+                error: Some(format!("No published version matches '{requirement}'.")),
+            })?
+    };
+
+    println!("Fetching template {}_{}_{version}...", spec.author, spec.artifact_id);
+    let archive = reqwest::blocking::get(format!(
+        "{url}/api/get/{}/{}/{version}/jar",
+        spec.author, spec.artifact_id
+    ))?
+    .error_for_status()?
+    .bytes()?;
+
+    zip::ZipArchive::new(Cursor::new(archive))
+        .and_then(|mut archive| archive.extract(directory))
+        .map_err(|e| TaskError::Generic(format!("The template {spec} couldn't be extracted: {e}")))
+}
@@ -0,0 +1,169 @@
+use super::{remote_repository_url, TEMPLATE_TAG};
+use crate::{
+    args::{
+        options::{Options, ParOptionBuilder},
+        parser::ParsedArguments,
+    },
+    tasks::{Task, TaskError, TaskResult},
+};
+use jabu_config::{fsutils::project_from_directory, model::ArtifactSpec};
+use reqwest::blocking::multipart::{Form, Part};
+use std::{
+    io::{Cursor, Write},
+    path::{Path, PathBuf},
+};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+#[derive(Debug, Default)]
+pub struct PublishTemplateTask;
+
+impl Task for PublishTemplateTask {
+    fn execute(&self, _: Vec<String>, parsed_args: Option<ParsedArguments>) -> TaskResult {
+        // Safe to unwrap, since the trait method `Task::options` returns `Some()`
+        // and the options without default values are required.
+        let parsed_args = parsed_args.unwrap();
+        let option = |name: &str| {
+            parsed_args
+                .get_option_value(name)
+                .cloned()
+                .flatten()
+                .unwrap_or_default()
+        };
+        let directory = PathBuf::from(option("path"));
+        let name = match option("name") {
+            name if name.is_empty() => directory
+                .canonicalize()?
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            name => name,
+        };
+        let spec = ArtifactSpec::new(option("author"), name, option("version"));
+
+        // The jaburon of the artifact is the skeleton of the template, tagged so
+        // that it can be told apart from the rest of the artifacts.
+        let mut skeleton = project_from_directory(Some(directory.clone()))?;
+        skeleton.header.author = spec.author.clone();
+        skeleton.header.project_name = spec.artifact_id.clone();
+        skeleton.header.version = spec.version.clone();
+        if !skeleton.header.tags.iter().any(|tag| tag == TEMPLATE_TAG) {
+            skeleton.header.tags.push(TEMPLATE_TAG.to_string());
+        }
+        let jaburon = ron::ser::to_string_pretty(&skeleton, ron::ser::PrettyConfig::default())
+            .map_err(|e| TaskError::Generic(e.to_string()))?;
+
+        println!("Archiving template '{}'...", directory.to_string_lossy());
+        let archive = archive_directory(&directory)?;
+
+        let form = Form::new()
+            .part("jaburon", Part::text(jaburon))
+            .part("jar", Part::bytes(archive));
+        let post_url = format!(
+            "{}/api/upload/{}/{}/{}/{}",
+            remote_repository_url(),
+            spec.author,
+            spec.artifact_id,
+            spec.version,
+            option("author-key")
+        );
+        let resp = reqwest::blocking::Client::new()
+            .post(post_url)
+            .multipart(form)
+            .send()?;
+
+        if resp.status().is_success() {
+            println!("Template published as {spec}!");
+            Ok(())
+        } else {
+            Err(TaskError::Generic(format!(
+                "The template couldn't be published.\nThe server has returned {} as error code.",
+                resp.status()
+            )))
+        }
+    }
+
+    fn description(&self) -> String {
+        "Publishes a template directory to the repository.".to_string()
+    }
+
+    fn options(&self) -> Option<Options> {
+        let mut options = Options::default();
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("path")
+                .short('p')
+                .description("Directory of the template.")
+                .has_arg(true)
+                .default_value(".".to_string())
+                .build(),
+        );
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("name")
+                .short('n')
+                .description("Name of the published template, the name of its directory by default.")
+                .has_arg(true)
+                .default_value(String::new())
+                .build(),
+        );
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("version")
+                .short('v')
+                .description("Version of the published template.")
+                .has_arg(true)
+                .default_value("0.0.1".to_string())
+                .build(),
+        );
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("author")
+                .short('a')
+                .description("Author publishing the template.")
+                .has_arg(true)
+                .required(true)
+                .build(),
+        );
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("author-key")
+                .short('k')
+                .has_arg(true)
+                .required(true)
+                .build(),
+        );
+        Some(options)
+    }
+}
+
+/// Returns the contents of a zip archive with every file of the directory.
+fn archive_directory(directory: &Path) -> Result<Vec<u8>, TaskError> {
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for entry in walkdir::WalkDir::new(directory)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let name = entry
+            .path()
+            .strip_prefix(directory)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        archive
+            .start_file(name, options)
+            .map_err(|e| TaskError::Generic(e.to_string()))?;
+        archive.write_all(&std::fs::read(entry.path())?)?;
+    }
+
+    let archive = archive
+        .finish()
+        .map_err(|e| TaskError::Generic(e.to_string()))?;
+    Ok(archive.into_inner())
+}
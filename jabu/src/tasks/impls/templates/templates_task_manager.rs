@@ -0,0 +1,9 @@
+use super::{ListTemplatesTask, PublishTemplateTask};
+use crate::tasks::GeneralTaskManager;
+
+pub fn get_templates_task_manager() -> GeneralTaskManager {
+    let mut templates_taskmanager = GeneralTaskManager::default();
+    templates_taskmanager.register_task("list", Box::new(ListTemplatesTask));
+    templates_taskmanager.register_task("publish", Box::new(PublishTemplateTask));
+    templates_taskmanager
+}
@@ -0,0 +1,187 @@
+use crate::args::options::{Options, ParOptionBuilder};
+use crate::args::parser::ParsedArguments;
+use crate::tasks::{JabuTask, JabuTaskDependencySpec, TaskError};
+use crate::tools::{
+    JUnitLauncherConfig, JavaExecTarget, JavaHome, JavaToolConfig, JavacConfig, TestSelector,
+};
+use jabu_config::{
+    fsutils::{java_test_sources, path_dependency_classes},
+    model::JabuProject,
+};
+
+/// Part of the name of the jar of the JUnit Platform console launcher that has
+/// to be present between the dependencies of the project in order to run the tests.
+pub const JUNIT_LAUNCHER_JAR_NAME: &str = "junit-platform-console-standalone";
+
+#[derive(Debug, Default)]
+pub struct TestTask;
+
+impl JabuTask for TestTask {
+    fn description(&self) -> String {
+        "Compiles and runs the tests of the current project.".to_string()
+    }
+
+    fn execute(
+        &self,
+        _: Vec<String>,
+        parsed_args: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        java_home: &JavaHome,
+    ) -> crate::tasks::TaskResult {
+        let test_sources: Vec<String> = java_test_sources(None, jabu_config)
+            .iter()
+            .map(|source| source.to_string_lossy().to_string())
+            .collect();
+
+        if test_sources.is_empty() {
+            println!("No tests to run.");
+            return Ok(());
+        }
+
+        let jars: Vec<String> = crate::utils::walkdir_find(
+            &jabu_config.fs_schema.lib,
+            |entry| entry.extension().unwrap_or_default() == "jar",
+            &[
+                crate::utils::FSNodeType::File,
+                crate::utils::FSNodeType::SymLink,
+            ],
+        )
+        .into_iter()
+        .chain(path_dependency_classes(None, jabu_config)?)
+        .map(|entry| entry.to_string_lossy().to_string())
+        .collect();
+
+        let launcher_jar = match jars.iter().find(|jar| {
+            std::path::Path::new(jar)
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .contains(JUNIT_LAUNCHER_JAR_NAME)
+        }) {
+            Some(launcher_jar) => launcher_jar.clone(),
+            None => {
+                return Err(TaskError::UnavailableResource {
+                    resource_name: JUNIT_LAUNCHER_JAR_NAME.to_string(),
+                    error: Some(format!(
+                        "The JUnit Platform console launcher must be one of the dependencies of the project (and be fetched into '{}') in order to run the tests.",
+                        jabu_config.fs_schema.lib
+                    )),
+                });
+            }
+        };
+
+        // Compile the tests from scratch, so that no classes from removed tests are left.
+        let test_classes_dir = jabu_config.fs_schema.target_test_classes();
+        if test_classes_dir.exists() {
+            std::fs::remove_dir_all(&test_classes_dir)?;
+        }
+
+        let classes_dir = jabu_config
+            .fs_schema
+            .target_classes()
+            .to_string_lossy()
+            .to_string();
+        let test_classes_dir = test_classes_dir.to_string_lossy().to_string();
+
+        let mut javac_config = JavacConfig::new(
+            test_sources,
+            Some(test_classes_dir.clone()),
+            Some(jabu_config.java_config.clone()),
+        );
+        javac_config.classpath = vec![classes_dir.clone()];
+        javac_config.classpath.extend(jars.iter().cloned());
+
+        let javac_path = java_home
+            .get_javac()
+            .clone()
+            .expect("No javac installed.")
+            .to_string_lossy()
+            .to_string();
+        let exit_code = Self::exec_tool("javac", &javac_path, javac_config.into_args())?;
+        if exit_code != 0 {
+            return Err(TaskError::CommandFailed {
+                command: "javac".to_string(),
+                description: exit_code.to_string(),
+            });
+        }
+
+        // Run the tests.
+        let mut launcher_config = JUnitLauncherConfig::new(
+            std::iter::once(test_classes_dir.clone())
+                .chain(std::iter::once(classes_dir))
+                .chain(jars.into_iter().filter(|jar| *jar != launcher_jar))
+                .collect(),
+            vec![test_classes_dir],
+        );
+        launcher_config.reports_dir = Some(
+            jabu_config
+                .fs_schema
+                .target_test_reports()
+                .to_string_lossy()
+                .to_string(),
+        );
+        if let Some(parsed_args) = &parsed_args {
+            if let Some(Some(filter)) = parsed_args.get_option_value("filter") {
+                launcher_config.selectors = TestSelector::parse_list(filter);
+            }
+        }
+
+        let java_tool_config = JavaToolConfig::new(
+            JavaExecTarget::Jar(launcher_jar),
+            Vec::new(),
+            launcher_config.into_args(),
+        );
+        let java_path = java_home
+            .get_java()
+            .clone()
+            .expect("No java installed.")
+            .to_string_lossy()
+            .to_string();
+        match Self::exec_tool("java", &java_path, java_tool_config.into_args())? {
+            0 => Ok(()),
+            exit_code => Err(TaskError::TestsFailed(exit_code)),
+        }
+    }
+
+    fn get_dependency_task_specs(&self) -> JabuTaskDependencySpec {
+        JabuTaskDependencySpec::new(vec![
+            ("build".to_string(), Vec::new()),
+            ("resources".to_string(), Vec::new()),
+        ])
+    }
+
+    fn required_tools(&self) -> &[&'static str] {
+        &["java", "javac"]
+    }
+
+    fn options(&self) -> Option<Options> {
+        let mut options = Options::default();
+        options.add_option(
+            ParOptionBuilder::default()
+                .name("filter")
+                .short('f')
+                .description("Comma separated list of the test classes (i.e. 'com.example.AppTest'), methods (i.e. 'com.example.AppTest#sum') or class name patterns (i.e. 'com.example.*Test') to run.")
+                .has_arg(true)
+                .required(false)
+                .build(),
+        );
+        Some(options)
+    }
+}
+
+impl TestTask {
+    /// Executes the given tool, returning its exit code.
+    fn exec_tool(tool_name: &str, tool_path: &str, args: Vec<String>) -> Result<i32, TaskError> {
+        match crate::utils::exec_cmd(tool_path, args) {
+            Ok(exit_status) => exit_status.code().ok_or_else(|| TaskError::CommandFailed {
+                command: tool_name.to_string(),
+                description: "Command has no exit code (probably due to a SIGINT)".to_string(),
+            }),
+            // i.e. The invoked binary doesn't exist.
+            Err(e) => Err(TaskError::CommandFailed {
+                command: tool_name.to_string(),
+                description: e.to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{project_with_profile, GeneralTaskManager, Task, TaskError, TaskResult},
+};
+use jabu_config::{format::CONFIG_FORMATS, model::JabuProject};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    path::{Path, PathBuf},
+    process::Child,
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    time::Duration,
+};
+
+/// Time without changes after which a burst of changes (*i.e. an editor saving
+/// several files, or a `git checkout`*) is considered finished.
+pub const WATCH_DEBOUNCE_PERIOD: Duration = Duration::from_millis(300);
+
+/// Executes the given tasks every time the sources, resources, tests, libraries
+/// or configuration of the project change. The processes started by the tasks
+/// (*i.e. the JVM of `run`*) are killed before executing the tasks again.
+#[derive(Debug, Default)]
+pub struct WatchTask {
+    /// Profile the tasks are executed with (*see [`GeneralTaskManager::set_profile`]*).
+    pub profile: Option<String>,
+}
+
+impl Task for WatchTask {
+    fn description(&self) -> String {
+        "Executes the given tasks again whenever the files of the project change.".to_string()
+    }
+
+    fn execute(&self, args: Vec<String>, _: Option<ParsedArguments>) -> TaskResult {
+        if args.is_empty() {
+            return Err(TaskError::Generic(
+                "No tasks to watch were given (i.e. 'jabu watch run').".to_string(),
+            ));
+        }
+
+        let directory = std::env::current_dir()?;
+        let (sender, receiver) = channel();
+        let mut watcher = RecommendedWatcher::new(sender, notify::Config::default())?;
+        let mut watched = Vec::new();
+        let mut children = Vec::new();
+        loop {
+            stop_processes(&mut children);
+
+            // The project is reloaded, since its jabu file may have changed. If it
+            // can't be loaded anymore, the error is reported and the previous paths
+            // are watched until it's fixed.
+            let cwd = directory.to_string_lossy().to_string();
+            match project_with_profile(&cwd, self.profile.as_deref()) {
+                Ok(project) => {
+                    let paths = watched_paths(&directory, &project);
+                    if paths != watched {
+                        watched.iter().for_each(|path: &PathBuf| {
+                            let _ = watcher.unwatch(watch_target(path));
+                        });
+                        for path in &paths {
+                            let mode = if path.is_dir() {
+                                RecursiveMode::Recursive
+                            } else {
+                                RecursiveMode::NonRecursive
+                            };
+                            watcher.watch(watch_target(path), mode)?;
+                        }
+                        watched = paths;
+                    }
+
+                    let mut manager = GeneralTaskManager::top_level_default(&cwd);
+                    manager.set_profile(self.profile.clone());
+                    match manager.spawn_command(args.clone(), &cwd) {
+                        Ok(spawned) => children = spawned,
+                        // Failures (*i.e. compilation errors*) are reported, and the tasks
+                        // are executed again once they are fixed.
+                        Err(e) => eprintln!("Failure:\n{e}"),
+                    }
+                }
+                // There is nothing to watch yet.
+                Err(e) if watched.is_empty() => return Err(e),
+                Err(e) => eprintln!("Failure:\n{e}"),
+            }
+
+            println!("=> Waiting for changes... (press Ctrl+C to exit)");
+            match wait_for_changes(&receiver, &watched, WATCH_DEBOUNCE_PERIOD) {
+                Some(changed) => println!(
+                    "=> Changes detected in {}",
+                    changed
+                        .iter()
+                        .map(|path| format!("'{}'", path.to_string_lossy()))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                None => break,
+            }
+        }
+
+        stop_processes(&mut children);
+        Ok(())
+    }
+
+    fn is_group(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the paths watched for the project located in `directory`: its sources,
+/// resources, tests and libraries, and its jabu file (*in any format*). The paths
+/// that don't exist are left out.
+pub fn watched_paths(directory: &Path, project: &JabuProject) -> Vec<PathBuf> {
+    let fs_schema = &project.fs_schema;
+    [
+        fs_schema.source.clone(),
+        fs_schema.resources.clone(),
+        fs_schema.test.clone(),
+        fs_schema.lib.clone(),
+    ]
+    .into_iter()
+    .chain(CONFIG_FORMATS.map(|format| format.project_file_name()))
+    .filter_map(|path| directory.join(path).canonicalize().ok())
+    .collect()
+}
+
+/// Blocks until a file inside of the `watched` paths is created, modified or
+/// removed, and then until no other changes happen for `debounce_period`. The
+/// changed files are returned, or `None` if the watcher has been dropped.
+pub fn wait_for_changes(
+    receiver: &Receiver<notify::Result<Event>>,
+    watched: &[PathBuf],
+    debounce_period: Duration,
+) -> Option<Vec<PathBuf>> {
+    let mut changed = Vec::new();
+    while changed.is_empty() {
+        collect_changes(receiver.recv().ok()?, watched, &mut changed);
+    }
+    loop {
+        match receiver.recv_timeout(debounce_period) {
+            Ok(event) => collect_changes(event, watched, &mut changed),
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+    changed.sort();
+    changed.dedup();
+    Some(changed)
+}
+
+fn collect_changes(event: notify::Result<Event>, watched: &[PathBuf], changed: &mut Vec<PathBuf>) {
+    let Ok(event) = event else {
+        return;
+    };
+    if matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        changed.extend(
+            event
+                .paths
+                .into_iter()
+                .filter(|path| watched.iter().any(|watched| path.starts_with(watched))),
+        );
+    }
+}
+
+/// Files are watched through their directory, since editors usually replace the
+/// file when saving it, which would end the watch of the file itself.
+fn watch_target(path: &Path) -> &Path {
+    if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    }
+}
+
+fn stop_processes(children: &mut Vec<Child>) {
+    for mut child in children.drain(..) {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
@@ -3,17 +3,23 @@ use crate::{
     tools::JavaHome,
 };
 use jabu_config::{
-    fsutils::project_from_directory,
-    model::{JabuProject, JABU_FILE_NAME},
+    fsutils::{project_from_directory, workspace_from_directory, workspace_members},
+    model::{JabuProject, Workspace},
+    validation::check_project,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    process::Child,
 };
-use std::{collections::HashMap, path::PathBuf};
 
 use super::{
     impls::{
-        deps, BuildJabuTask, CleanTask, DisplayJabuTask, JPackageTask, JarTask, JavadocTask,
-        PublishTask, Run, ScriptsTask,
+        deps, export, BuildJabuTask, CleanTask, CustomJabuTask, DisplayJabuTask, FatJarTask,
+        JPackageTask, JarTask, JavadocTask, ProcessResourcesTask, PublishTask, Run, ScriptsTask,
+        TestTask,
     },
-    TaskError, TaskResult,
+    inputs_fingerprint, project_settings, TaskError, TaskResult, TaskStates, TASK_STATES_FILE_NAME,
 };
 
 /// Represents a task that it's supposed to be executed inside of a Jabu project.
@@ -42,35 +48,125 @@ pub trait JabuTask {
     fn options(&self) -> Option<Options> {
         None
     }
+
+    /// Returns the files and directories read by the task. If none of them (*nor the
+    /// project's configuration or the task's args*) has changed since the last
+    /// execution of the task, and neither have its [`Self::outputs`], the task is
+    /// skipped as `UP-TO-DATE`. Tasks without inputs are always executed.
+    fn inputs(&self, _jabu_config: &JabuProject) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Returns the files and directories written by the task (*see [`Self::inputs`]*).
+    fn outputs(&self, _jabu_config: &JabuProject) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Executes the task like [`Self::execute`], but without waiting for the
+    /// long-running process it starts (*i.e. the JVM of `run`*), which is returned
+    /// so that it can be stopped. By default, the task is just executed.
+    fn spawn(
+        &self,
+        args: Vec<String>,
+        parsed_args: Option<ParsedArguments>,
+        jabu_config: &JabuProject,
+        java_home: &JavaHome,
+    ) -> Result<Option<Child>, TaskError> {
+        self.execute(args, parsed_args, jabu_config, java_home)?;
+        Ok(None)
+    }
+
+    /// Returns whether the task is a group of subtasks (*i.e. `deps`*), whose first
+    /// argument is the subtask to execute. When several tasks are invoked at once,
+    /// every argument after a group belongs to it.
+    fn is_group(&self) -> bool {
+        false
+    }
 }
 
+/// The dependencies of a task, in the order they are declared, along with the
+/// arguments they are executed with.
+#[derive(Default)]
 pub struct JabuTaskDependencySpec {
-    specs: HashMap<String, Vec<String>>,
+    specs: Vec<(String, Vec<String>)>,
 }
 
-impl Default for JabuTaskDependencySpec {
-    fn default() -> Self {
-        Self {
-            specs: HashMap::new(),
-        }
+impl JabuTaskDependencySpec {
+    pub fn new(specs: Vec<(String, Vec<String>)>) -> Self {
+        Self { specs }
     }
 }
 
-impl JabuTaskDependencySpec {
-    pub fn new(specs: HashMap<String, Vec<String>>) -> Self {
-        Self { specs }
+/// Option that prints the [`ExecutionPlan`] of a task instead of executing it.
+pub const DRY_RUN_OPTION: &str = "--dry-run";
+
+/// Option that executes the tasks even if they are up to date (*see
+/// [`JabuTask::inputs`]*).
+pub const RERUN_TASKS_OPTION: &str = "--rerun-tasks";
+
+/// A task of an [`ExecutionPlan`], along with the arguments it's executed with.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PlannedTask {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+impl PlannedTask {
+    /// Parses a task written as in the command line, along with its args (*i.e.
+    /// `"test --filter:Fast*"`*). Returns `None` if there's no task name.
+    pub fn parse(task: &str) -> Option<Self> {
+        let mut words = task.split_whitespace().map(String::from);
+        Some(Self {
+            name: words.next()?,
+            args: words.collect(),
+        })
     }
 }
 
+impl std::fmt::Display for PlannedTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.args.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} {}", self.name, self.args.join(" "))
+        }
+    }
+}
+
+/// The tasks executed when a task is invoked, in the order they are executed:
+/// every task goes after its dependencies, and is executed only once even if
+/// many tasks depend on it.
+#[derive(Debug, PartialEq, Default)]
+pub struct ExecutionPlan {
+    pub tasks: Vec<PlannedTask>,
+}
+
+/// Tasks that, when executed in the directory of a workspace, are executed on
+/// every member of the workspace instead.
+pub const WORKSPACE_TASKS: [&str; 4] = ["build", "jar", "clean", "test"];
+
 /// Contains a collection [`JabuTask`] that can be executed.
 pub struct JabuTaskManager {
     pub tasks: HashMap<String, Box<dyn JabuTask>>,
+
+    /// Tasks executed before a task whenever it's executed, by the name of the task.
+    pub before_hooks: HashMap<String, Vec<String>>,
+
+    /// Tasks executed after a task whenever it's executed, by the name of the task.
+    pub after_hooks: HashMap<String, Vec<String>>,
+
+    /// Profile of the projects (*see [`JabuProject::profiles`]*) the tasks are
+    /// executed with.
+    pub profile: Option<String>,
 }
 
 impl Default for JabuTaskManager {
     fn default() -> Self {
         Self {
             tasks: HashMap::new(),
+            before_hooks: HashMap::new(),
+            after_hooks: HashMap::new(),
+            profile: None,
         }
     }
 }
@@ -89,11 +185,73 @@ impl JabuTaskManager {
         tasks.insert("scripts".to_string(), Box::new(ScriptsTask::default()));
         tasks.insert("clean".to_string(), Box::new(CleanTask::default()));
         tasks.insert("jar".to_string(), Box::new(JarTask::default()));
+        tasks.insert("fatjar".to_string(), Box::new(FatJarTask));
         tasks.insert("deps".to_string(), Box::new(deps::DepsSubtask::default()));
         tasks.insert("javadoc".to_string(), Box::new(JavadocTask::default()));
         tasks.insert("jpackage".to_string(), Box::new(JPackageTask::default()));
         tasks.insert("publish".to_string(), Box::new(PublishTask::default()));
-        Self { tasks }
+        tasks.insert("test".to_string(), Box::new(TestTask::default()));
+        tasks.insert("export".to_string(), Box::new(export::ExportSubtask::default()));
+        tasks.insert("resources".to_string(), Box::new(ProcessResourcesTask));
+        Self {
+            tasks,
+            ..Default::default()
+        }
+    }
+
+    /// Registers the custom tasks of the project (*see [`JabuProject::custom_tasks`]*),
+    /// along with their `before` and `after` hooks.
+    ///
+    /// The custom tasks with the name of another task, and the hooks into tasks that
+    /// don't exist, are skipped. Once the rest of them are registered, they are
+    /// reported with [`TaskError::InvalidConfig`].
+    pub fn register_custom_tasks(&mut self, jabu_project: &JabuProject) -> TaskResult {
+        let mut problems = Vec::new();
+        let mut registered = Vec::new();
+
+        for (name, task) in &jabu_project.custom_tasks {
+            let mut option_names = HashSet::new();
+            if let Some(option) = task.options.iter().find(|option| !option_names.insert(&option.name)) {
+                problems.push(format!(
+                    "the custom task '{name}' declares the option '{}' more than once",
+                    option.name
+                ));
+            } else if self.register_task(name, Box::new(CustomJabuTask::new(name, task.clone()))) {
+                registered.push((name, task));
+            } else {
+                problems.push(format!(
+                    "the custom task '{name}' has the name of an existing task"
+                ));
+            }
+        }
+
+        for (name, task) in registered {
+            for (hooked, before) in task
+                .before
+                .iter()
+                .map(|hooked| (hooked, true))
+                .chain(task.after.iter().map(|hooked| (hooked, false)))
+            {
+                if !self.tasks.contains_key(hooked) {
+                    problems.push(format!(
+                        "the custom task '{name}' hooks into the task '{hooked}', which doesn't exist"
+                    ));
+                    continue;
+                }
+                let hooks = if before {
+                    &mut self.before_hooks
+                } else {
+                    &mut self.after_hooks
+                };
+                hooks.entry(hooked.clone()).or_default().push(name.clone());
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(TaskError::InvalidConfig(problems.join("\n").into()))
+        }
     }
 
     pub fn register_task(&mut self, task_name: &str, new_task: Box<dyn JabuTask>) -> bool {
@@ -117,44 +275,280 @@ impl JabuTaskManager {
         self.tasks.get(task_name)
     }
 
-    pub fn execute(&self, task_name: &str, args: &Vec<String>, directory: &str) -> TaskResult {
-        let task = if let Some(task) = self.get_task(task_name) {
-            task
-        } else {
-            return Err(TaskError::NoSuchTask(task_name.to_string()));
+    /// Returns the plan for executing the given tasks in order: the dependencies of
+    /// each task (*with the args they declare*) sorted topologically, and then the
+    /// task itself. The tasks shared by several of them are only planned once.
+    ///
+    /// Returns [`TaskError::DependencyCycle`] if a task ends up depending on itself.
+    pub fn plan_all(&self, tasks: &[PlannedTask]) -> Result<ExecutionPlan, TaskError> {
+        let mut plan = ExecutionPlan::default();
+        for task in tasks {
+            self.plan_task(&task.name, &task.args, &mut Vec::new(), &mut plan)?;
+        }
+        Ok(plan)
+    }
+
+    fn plan_task(
+        &self,
+        task_name: &str,
+        args: &[String],
+        dependents: &mut Vec<String>,
+        plan: &mut ExecutionPlan,
+    ) -> TaskResult {
+        let planned_task = PlannedTask {
+            name: task_name.to_string(),
+            args: args.to_vec(),
+        };
+        if plan.tasks.contains(&planned_task) {
+            return Ok(());
+        }
+        if let Some(start) = dependents.iter().position(|name| name == task_name) {
+            let mut cycle = dependents[start..].to_vec();
+            cycle.push(task_name.to_string());
+            return Err(TaskError::DependencyCycle(cycle));
+        }
+
+        let task = match self.get_task(task_name) {
+            Some(task) => task,
+            None if dependents.is_empty() => {
+                return Err(TaskError::NoSuchTask(task_name.to_string()))
+            }
+            None => return Err(TaskError::DependencyTaskDoesntExist(task_name.to_string())),
         };
 
-        //let jabu_config = JabuProject::try_from(PathBuf::from(directory).join(JABU_FILE_NAME))?;
-        let jabu_project = project_from_directory(Some(PathBuf::from(directory)))?;
-        let java_home = JavaHome::new()?;
+        dependents.push(task_name.to_string());
+        for (dependency, dependency_args) in &task.get_dependency_task_specs().specs {
+            self.plan_task(dependency, dependency_args, dependents, plan)?;
+        }
+        for hook in self.before_hooks.get(task_name).into_iter().flatten() {
+            self.plan_task(hook, &[], dependents, plan)?;
+        }
+        dependents.pop();
+
+        plan.tasks.push(planned_task);
+        for hook in self.after_hooks.get(task_name).into_iter().flatten() {
+            self.plan_task(hook, &[], dependents, plan)?;
+        }
+        Ok(())
+    }
+
+    /// Executes the task along with its dependencies, following its [`ExecutionPlan`].
+    /// If the args contain [`DRY_RUN_OPTION`], the plan is printed instead.
+    pub fn execute(&self, task_name: &str, args: &Vec<String>, directory: &str) -> TaskResult {
+        self.execute_all(
+            &[PlannedTask {
+                name: task_name.to_string(),
+                args: args.clone(),
+            }],
+            directory,
+        )
+    }
+
+    /// Executes the given tasks in order, following their [`ExecutionPlan`], so the
+    /// dependencies they share are executed once. If the args of any of them contain
+    /// [`DRY_RUN_OPTION`], the plan is printed instead.
+    pub fn execute_all(&self, tasks: &[PlannedTask], directory: &str) -> TaskResult {
+        self.execute_tasks(tasks, directory, false).map(|_| ())
+    }
 
-        let required_tools = task.required_tools();
-        let required_tools_status = java_home.check_required_tools(&required_tools.to_vec());
+    /// Executes the given tasks like [`Self::execute_all`], but starting the
+    /// long-running processes of the tasks in the background (*see
+    /// [`JabuTask::spawn`]*), which are returned.
+    pub fn spawn_all(&self, tasks: &[PlannedTask], directory: &str) -> Result<Vec<Child>, TaskError> {
+        self.execute_tasks(tasks, directory, true)
+    }
+
+    fn execute_tasks(
+        &self,
+        tasks: &[PlannedTask],
+        directory: &str,
+        background: bool,
+    ) -> Result<Vec<Child>, TaskError> {
+        if let Some(task) = tasks.iter().find(|task| !self.tasks.contains_key(&task.name)) {
+            return Err(TaskError::NoSuchTask(task.name.clone()));
+        }
 
-        // If any tool is missing
-        if required_tools_status
+        if tasks
             .iter()
-            .any(|(_, available)| !available)
+            .all(|task| WORKSPACE_TASKS.contains(&task.name.as_str()))
         {
-            return Err(TaskError::MissingRequiredTaskTools(required_tools_status));
+            if let Some(workspace) = workspace_from_directory(Some(PathBuf::from(directory)))? {
+                self.execute_in_workspace(tasks, directory, &workspace)?;
+                return Ok(Vec::new());
+            }
         }
 
-        let parsed_args = if let Some(options) = task.options() {
-            Some(ParsedArguments::new_with_options(args.clone(), &options)?)
-        } else {
-            None
-        };
+        self.execute_in_project(tasks, directory, background)
+    }
 
-        for (task_name, task_args) in task.get_dependency_task_specs().specs {
-            println!("=> Executing dependency task '{task_name}' with args '{task_args:?}'");
+    /// Executes the tasks on the project located in `directory`, even if it's also
+    /// the directory of a workspace.
+    fn execute_in_project(
+        &self,
+        tasks: &[PlannedTask],
+        directory: &str,
+        background: bool,
+    ) -> Result<Vec<Child>, TaskError> {
+        let mut tasks = tasks.to_vec();
+        let dry_run = remove_option(&mut tasks, DRY_RUN_OPTION);
+        let rerun_tasks = remove_option(&mut tasks, RERUN_TASKS_OPTION);
+        let plan = self.plan_all(&tasks)?;
 
-            if self.tasks.contains_key(&task_name) {
-                self.execute(&task_name, args, directory)?
+        if dry_run {
+            let names = tasks
+                .iter()
+                .map(|task| task.name.as_str())
+                .collect::<Vec<&str>>();
+            println!("Execution plan for '{}':", names.join(" "));
+            plan.tasks
+                .iter()
+                .enumerate()
+                .for_each(|(index, task)| println!("\t{}. {task}", index + 1));
+            return Ok(Vec::new());
+        }
+
+        // The tasks aren't executed on projects with errors, which are reported along
+        // with the rest of their problems.
+        let diagnostics = check_project(Path::new(directory), self.profile.as_deref())?;
+        if diagnostics.has_errors() {
+            return Err(TaskError::InvalidConfig(Box::new(diagnostics)));
+        }
+
+        let jabu_project = project_with_profile(directory, self.profile.as_deref())?;
+        let java_home = JavaHome::for_java_version(jabu_project.java_config.java_version)?;
+
+        // Every task of the plan is checked before executing any of them.
+        let mut executions = Vec::with_capacity(plan.tasks.len());
+        for planned_task in &plan.tasks {
+            // Safe to unwrap, since the plan only contains existing tasks.
+            let task = self.get_task(&planned_task.name).unwrap();
+
+            let required_tools_status = java_home.check_required_tools(task.required_tools());
+            if required_tools_status
+                .iter()
+                .any(|(_, available)| !available)
+            {
+                return Err(TaskError::MissingRequiredTaskTools(required_tools_status));
+            }
+
+            let parsed_args = match task.options() {
+                Some(options) => Some(ParsedArguments::new_with_options(
+                    planned_task.args.clone(),
+                    &options,
+                )?),
+                None => None,
+            };
+            executions.push((planned_task, task, parsed_args));
+        }
+
+        // The tasks with inputs are skipped if they are up to date. The states are
+        // loaded for every task, since a task may have removed them (*i.e. `clean`*).
+        let states_path = jabu_project
+            .fs_schema
+            .target_jabu()
+            .join(TASK_STATES_FILE_NAME);
+        let mut children = Vec::new();
+        let settings = project_settings(&jabu_project, java_home.get_java_home());
+
+        for (planned_task, task, parsed_args) in executions {
+            let key = planned_task.to_string();
+            let inputs = task.inputs(&jabu_project);
+            let inputs_fingerprint = if inputs.is_empty() || rerun_tasks {
+                None
+            } else {
+                Some(inputs_fingerprint(&settings, &inputs)?)
+            };
+            if let Some(fingerprint) = &inputs_fingerprint {
+                if TaskStates::load(&states_path)
+                    .is_some_and(|states| states.is_up_to_date(&key, fingerprint))
+                {
+                    println!("=> Task '{key}' UP-TO-DATE");
+                    continue;
+                }
+            }
+
+            if !tasks.contains(planned_task) {
+                println!(
+                    "=> Executing dependency task '{}' with args '{:?}'",
+                    planned_task.name, planned_task.args
+                );
+            } else if tasks.len() > 1 {
+                println!("=> Executing task '{planned_task}'");
+            }
+            let args = planned_task.args.clone();
+            if background {
+                children.extend(task.spawn(args, parsed_args, &jabu_project, &java_home)?);
             } else {
-                return Err(TaskError::DependencyTaskDoesntExist(task_name.clone()));
+                task.execute(args, parsed_args, &jabu_project, &java_home)?;
+            }
+
+            if let Some(fingerprint) = inputs_fingerprint {
+                let mut task_states = TaskStates::load(&states_path).unwrap_or_default();
+                task_states.record(&key, fingerprint, &task.outputs(&jabu_project))?;
+                task_states.save(&states_path)?;
             }
         }
+        Ok(children)
+    }
+
+    /// Executes the tasks on every member of the workspace located in `directory`,
+    /// making sure that the members are executed after the ones they depend on.
+    fn execute_in_workspace(
+        &self,
+        tasks: &[PlannedTask],
+        directory: &str,
+        workspace: &Workspace,
+    ) -> TaskResult {
+        let members = workspace_members(Some(PathBuf::from(directory)), workspace)?;
+        // The tasks work with paths relative to the directory of the project.
+        let workspace_dir = std::env::current_dir()?;
+        let task_names = tasks
+            .iter()
+            .map(|task| task.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        let result = members.iter().try_for_each(|member| {
+            println!(
+                "==> [{}] Executing task '{task_names}'",
+                member.project.header.project_name
+            );
+            std::env::set_current_dir(&member.directory)?;
+            // The members aren't looked up for workspaces again, since a member could
+            // be a workspace itself, which would lead to an endless recursion.
+            self.execute_in_project(tasks, &member.directory.to_string_lossy(), false)
+                .map(|_| ())
+        });
+
+        std::env::set_current_dir(workspace_dir)?;
+        result
+    }
+}
+
+/// Loads the project located in `directory`, with the given profile applied.
+///
+/// Returns [`TaskError::InvalidConfig`] if the project doesn't have the profile.
+pub fn project_with_profile(
+    directory: &str,
+    profile: Option<&str>,
+) -> Result<JabuProject, TaskError> {
+    let jabu_project = project_from_directory(Some(PathBuf::from(directory)))?;
+    match profile {
+        Some(profile) => jabu_project.with_profile(profile).ok_or_else(|| {
+            TaskError::InvalidConfig(format!("the profile '{profile}' doesn't exist").into())
+        }),
+        None => Ok(jabu_project),
+    }
+}
 
-        task.execute(args.clone(), parsed_args, &jabu_project, &java_home)
+/// Removes the option from the args of every task, returning whether any of them
+/// had it.
+fn remove_option(tasks: &mut [PlannedTask], option: &str) -> bool {
+    let mut found = false;
+    for task in tasks {
+        let args_count = task.args.len();
+        task.args.retain(|arg| arg != option);
+        found |= task.args.len() != args_count;
     }
+    found
 }
@@ -1,6 +1,8 @@
 pub mod task;
 pub mod impls;
 pub mod jabu_tasks;
+pub mod task_state;
 
 pub use task::*;
 pub use jabu_tasks::*;
+pub use task_state::*;
@@ -3,13 +3,16 @@ use crate::args::{
     parser::{InvalidArgError, ParsedArguments},
 };
 use crate::tasks::{
-    impls::{HealthCheckTask, NewProjectTask, VersionTask},
-    JabuTask, JabuTaskManager,
+    impls::{config, templates, HealthCheckTask, ImportTask, NewProjectTask, VersionTask, WatchTask},
+    JabuTask, JabuTaskManager, PlannedTask,
 };
-use jaburepo::error::RepositoryOperationError;
+use crate::tools::JDKS_CONFIG_FILE_NAME;
+use jaburepo::error::{MavenParsingError, RepositoryOperationError, ResolutionError};
 use prettytable::{color, Attr, Cell, Row};
-use std::collections::{HashMap, HashSet};
-use jabu_config::fsutils::ProjectLoadingError;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::Child;
+use jabu_config::fsutils::{project_from_directory, ProjectLoadingError, WorkspaceError};
 
 pub type TaskResult = Result<(), TaskError>;
 
@@ -19,6 +22,16 @@ pub enum TaskError {
     /// Couldn't find any java environment.
     MissingJavaEnvironment,
 
+    /// None of the installed JDKs satisfies the java version required by the
+    /// project (*see [`jabu_config::model::JavaConfig::java_version`]*).
+    UnsatisfiedJavaVersion {
+        /// The minimum java version required by the project.
+        java_version: u8,
+
+        /// The JDKs that have been found (*i.e. `11.0.2 (/usr/lib/jvm/jdk-11)`*).
+        found: Vec<String>,
+    },
+
     /// The java environment that has been found is not valid. (*it may be missing
     /// some of the utilities*). The string of the variant represents the path to the
     /// java home/environment.
@@ -63,6 +76,11 @@ pub enum TaskError {
     /// dependency.
     DependencyTaskDoesntExist(String),
 
+    /// The dependencies of a task (*or the tasks of an alias*) form a cycle. The
+    /// variant holds the names of the tasks of the cycle, starting and ending with
+    /// the same task.
+    DependencyCycle(Vec<String>),
+
     /// A dependency task specified by another task has failed.
     DependencyTaskFailed {
         task_name: String,
@@ -80,6 +98,23 @@ pub enum TaskError {
         error: Option<String>,
     },
 
+    /// The contents of a resource don't match the checksum pinned in the
+    /// lockfile of the project.
+    ChecksumMismatch {
+        /// The name of the resource (*i.e. the path to the file*).
+        resource_name: String,
+
+        /// The checksum pinned in the lockfile.
+        expected: String,
+
+        /// The checksum of the actual contents.
+        found: String,
+    },
+
+    /// The tests of the project have been executed, but some of them have
+    /// failed. The variant holds the exit code of the test launcher.
+    TestsFailed(i32),
+
     /// Generic error with a message attached to it.
     Generic(String),
 }
@@ -122,11 +157,42 @@ impl From<jaburepo::error::RepositoryOperationError> for TaskError {
     }
 }
 
+impl From<jaburepo::error::ResolutionError> for TaskError {
+    fn from(value: jaburepo::error::ResolutionError) -> Self {
+        match value {
+            ResolutionError::Repository(e) => e.into(),
+            e => TaskError::Generic(e.to_string()),
+        }
+    }
+}
+
+impl From<notify::Error> for TaskError {
+    fn from(value: notify::Error) -> Self {
+        TaskError::Generic(value.to_string())
+    }
+}
+
+impl From<MavenParsingError> for TaskError {
+    fn from(value: MavenParsingError) -> Self {
+        TaskError::Generic(value.to_string())
+    }
+}
+
 impl From<ProjectLoadingError> for TaskError {
     fn from(value: jabu_config::fsutils::ProjectLoadingError) -> Self {
         match value {
             ProjectLoadingError::IoError(e) => Self::IOError(e),
-            ProjectLoadingError::FileParsingError(e) => Self::InvalidConfig(Box::new(e))
+            ProjectLoadingError::FileParsingError(e) => Self::InvalidConfig(Box::new(e)),
+            e => Self::InvalidConfig(Box::new(e)),
+        }
+    }
+}
+
+impl From<WorkspaceError> for TaskError {
+    fn from(value: WorkspaceError) -> Self {
+        match value {
+            WorkspaceError::Member(_, ProjectLoadingError::IoError(e)) => Self::IOError(e),
+            e => Self::InvalidConfig(Box::new(e)),
         }
     }
 }
@@ -135,6 +201,14 @@ impl std::fmt::Display for TaskError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let msg = match self {
             Self::MissingJavaEnvironment => "No java environment could be found. (you can specify one with the environment variable 'JAVA_HOME'".to_string(),
+            Self::UnsatisfiedJavaVersion { java_version, found } => {
+                let found = if found.is_empty() {
+                    "none".to_string()
+                } else {
+                    found.join(", ")
+                };
+                format!("No installed JDK satisfies the project's java version {java_version} (found: {found}). Install one, or register its path in '~/.config/jabu/{JDKS_CONFIG_FILE_NAME}'.")
+            }
             Self::InvalidJavaEnvironment(env) => format!("'{env}' as a java home is invalid (it might be missing some tools)"),
             Self::MissingRequiredTaskTools(tool_map) => {
                 // TODO: Add colors / Put it in a table.
@@ -145,10 +219,19 @@ impl std::fmt::Display for TaskError {
                 format!("Missing required tools for the given task:\n{body}")
             }
             Self::Generic(desc) => format!("Something went wrong: {desc}"),
+            Self::ChecksumMismatch { resource_name, expected, found } => {
+                format!("The checksum of '{resource_name}' doesn't match the one in the lockfile (expected {expected}, found {found}). If the change is intended, run 'deps update'.")
+            }
+            Self::TestsFailed(exit_code) => {
+                format!("Some tests have failed (the test launcher exited with code {exit_code}).")
+            }
             Self::IOError(io_error) => format!("An IO error has occurred: {io_error}"),
             Self::DependencyTaskFailed { task_name, description } => {
                 format!("While executing a task there was an error executing its dependency task '{task_name}': {description}")
             }
+            Self::DependencyCycle(tasks) => {
+                format!("The dependencies of the tasks form a cycle: {}", tasks.join(" -> "))
+            }
             Self::DependencyTaskDoesntExist(dependency_task) => {
                 format!("A task called a dependency task '{dependency_task}' which doesn't exist.")
             }
@@ -207,6 +290,13 @@ pub trait Task: std::fmt::Debug {
     fn options(&self) -> Option<Options> {
         None
     }
+
+    /// Returns whether the task is a group of subtasks (*i.e. `deps`*), whose first
+    /// argument is the subtask to execute. When several tasks are invoked at once,
+    /// every argument after a group belongs to it.
+    fn is_group(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug)]
@@ -233,6 +323,10 @@ impl TaskManager {
         tasks.insert("new".to_string(), Box::new(NewProjectTask {}));
         tasks.insert("version".to_string(), Box::new(VersionTask::default()));
         tasks.insert("health".to_string(), Box::new(HealthCheckTask::default()));
+        tasks.insert("import".to_string(), Box::new(ImportTask));
+        tasks.insert("templates".to_string(), Box::new(templates::TemplatesSubtask));
+        tasks.insert("watch".to_string(), Box::new(WatchTask::default()));
+        tasks.insert("config".to_string(), Box::new(config::ConfigSubtask));
         Self { tasks }
     }
 
@@ -280,6 +374,42 @@ impl TaskManager {
     }
 }
 
+/// Global option selecting the profile of the project (*see
+/// [`jabu_config::model::JabuProject::profiles`]*), given anywhere in the command
+/// line as `--profile:release` or `--profile release`.
+pub const PROFILE_OPTION: &str = "--profile";
+
+/// Removes the [`PROFILE_OPTION`] from the arguments of the command line (*except
+/// for the ones after `--`*), returning its value.
+pub fn take_profile(args: &mut Vec<String>) -> Result<Option<String>, TaskError> {
+    let missing_value = || {
+        TaskError::InvalidArguments(HashSet::from([InvalidArgError::MissingOptionArgument(
+            PROFILE_OPTION[2..].to_string(),
+        )]))
+    };
+
+    let mut profile = None;
+    let mut index = 0;
+    while index < args.len() && args[index] != "--" {
+        if let Some(value) = args[index].strip_prefix(&format!("{PROFILE_OPTION}:")) {
+            if value.is_empty() {
+                return Err(missing_value());
+            }
+            profile = Some(value.to_string());
+            args.remove(index);
+        } else if args[index] == PROFILE_OPTION {
+            args.remove(index);
+            if index == args.len() || args[index].starts_with("--") {
+                return Err(missing_value());
+            }
+            profile = Some(args.remove(index));
+        } else {
+            index += 1;
+        }
+    }
+    Ok(profile)
+}
+
 /// The `GeneralTaskManager` is a task manager with the same purposes
 /// as the normal `TaskManager` and the `JabuTaskManager`, but has the
 /// additional purpose of containing those task managers together and
@@ -306,6 +436,35 @@ impl GeneralTaskManager {
         }
     }
 
+    /// Selects the profile of the projects the tasks are executed with (*see
+    /// [`take_profile`]*).
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        // The tasks executed by `watch` use the profile as well.
+        self.task_manager.remove("watch");
+        self.task_manager.register_task(
+            "watch",
+            Box::new(WatchTask {
+                profile: profile.clone(),
+            }),
+        );
+        self.jabu_task_manager.profile = profile;
+    }
+
+    /// Creates the general task manager with the top level tasks, along with the
+    /// custom tasks of the project located in `directory`, if there's one.
+    ///
+    /// The invalid custom tasks are reported as a warning and skipped, so that the
+    /// rest of the tasks can still be used.
+    pub fn top_level_default(directory: &str) -> Self {
+        let mut jabu_task_manager = JabuTaskManager::top_level_default();
+        if let Ok(project) = project_from_directory(Some(PathBuf::from(directory))) {
+            if let Err(e) = jabu_task_manager.register_custom_tasks(&project) {
+                eprintln!("Warning:\n{e}");
+            }
+        }
+        Self::new(jabu_task_manager, TaskManager::top_level_default())
+    }
+
     /// Checks if any of the task managers contain a task with the given
     /// name.
     pub fn contains_task_with_name(&self, task_name: &str) -> bool {
@@ -320,7 +479,7 @@ impl GeneralTaskManager {
     pub fn execute(&self, task_name: &str, args: Vec<String>, directory: &str) -> TaskResult {
         // TODO: Refactor :D
         if task_name == "help" {
-            self.list_tasks();
+            self.list_tasks(&Self::aliases(directory));
             return Ok(());
         }
 
@@ -333,7 +492,175 @@ impl GeneralTaskManager {
         }
     }
 
-    fn list_tasks(&self) {
+    /// Executes the tasks invoked by the arguments of the command line (*see
+    /// [`Self::split_invocations`]*), resolving the aliases of the project located
+    /// in `directory`.
+    pub fn execute_command(&self, args: Vec<String>, directory: &str) -> TaskResult {
+        let aliases = Self::aliases(directory);
+        let invocations = self.split_invocations(args, &aliases);
+        self.execute_invocations(invocations, &aliases, directory, false)
+            .map(|_| ())
+    }
+
+    /// Executes the tasks invoked by the arguments of the command line like
+    /// [`Self::execute_command`], but starting the long-running processes of the
+    /// jabu tasks in the background (*see [`JabuTask::spawn`]*), which are returned.
+    pub fn spawn_command(
+        &self,
+        args: Vec<String>,
+        directory: &str,
+    ) -> Result<Vec<Child>, TaskError> {
+        let aliases = Self::aliases(directory);
+        let invocations = self.split_invocations(args, &aliases);
+        self.execute_invocations(invocations, &aliases, directory, true)
+    }
+
+    /// Executes the given tasks in order, resolving the aliases of the project located
+    /// in `directory`.
+    pub fn execute_all(&self, tasks: Vec<PlannedTask>, directory: &str) -> TaskResult {
+        self.execute_invocations(tasks, &Self::aliases(directory), directory, false)
+            .map(|_| ())
+    }
+
+    /// Consecutive jabu tasks are executed together, so the dependencies they
+    /// share are only executed once.
+    fn execute_invocations(
+        &self,
+        invocations: Vec<PlannedTask>,
+        aliases: &BTreeMap<String, Vec<String>>,
+        directory: &str,
+        background: bool,
+    ) -> Result<Vec<Child>, TaskError> {
+        let execute_jabu_tasks = |jabu_tasks: &[PlannedTask]| {
+            if background {
+                self.jabu_task_manager.spawn_all(jabu_tasks, directory)
+            } else {
+                self.jabu_task_manager
+                    .execute_all(jabu_tasks, directory)
+                    .map(|_| Vec::new())
+            }
+        };
+
+        let mut children = Vec::new();
+        let mut jabu_tasks = Vec::new();
+        for invocation in self.expand_aliases(invocations, aliases)? {
+            if self.jabu_task_manager.contains_task_with_name(&invocation.name) {
+                jabu_tasks.push(invocation);
+                continue;
+            }
+            if !jabu_tasks.is_empty() {
+                children.extend(execute_jabu_tasks(&std::mem::take(&mut jabu_tasks))?);
+            }
+            self.execute(&invocation.name, invocation.args, directory)?;
+        }
+
+        if !jabu_tasks.is_empty() {
+            children.extend(execute_jabu_tasks(&jabu_tasks)?);
+        }
+        Ok(children)
+    }
+
+    /// Returns the task aliases of the project located in `directory`, if there's one.
+    fn aliases(directory: &str) -> BTreeMap<String, Vec<String>> {
+        project_from_directory(Some(PathBuf::from(directory)))
+            .map(|project| project.tasks)
+            .unwrap_or_default()
+    }
+
+    /// Splits the arguments of the command line into the tasks they invoke, along
+    /// with their args: every argument naming a task or an alias starts a new
+    /// invocation (*i.e. `clean build --verbose jar`*). The arguments after `--`,
+    /// or after a task group such as `deps`, belong to the last task.
+    pub fn split_invocations(
+        &self,
+        args: Vec<String>,
+        aliases: &BTreeMap<String, Vec<String>>,
+    ) -> Vec<PlannedTask> {
+        let mut invocations: Vec<PlannedTask> = Vec::new();
+        let mut splitting = true;
+        for arg in args {
+            let starts_invocation =
+                splitting && (self.contains_task_with_name(&arg) || aliases.contains_key(&arg));
+            match invocations.last_mut() {
+                Some(invocation) if !starts_invocation => {
+                    splitting &= arg != "--";
+                    invocation.args.push(arg);
+                }
+                _ => {
+                    splitting = !self.is_group(&arg);
+                    invocations.push(PlannedTask {
+                        name: arg,
+                        args: Vec::new(),
+                    });
+                }
+            }
+        }
+        invocations
+    }
+
+    /// Replaces the invocations of aliases by the tasks of the alias, the last of
+    /// them receiving the args of the invocation. Aliases may contain other aliases,
+    /// and tasks take precedence over the aliases with the same name.
+    pub fn expand_aliases(
+        &self,
+        invocations: Vec<PlannedTask>,
+        aliases: &BTreeMap<String, Vec<String>>,
+    ) -> Result<Vec<PlannedTask>, TaskError> {
+        let mut expanded = Vec::new();
+        for invocation in invocations {
+            self.expand_alias(invocation, aliases, &mut Vec::new(), &mut expanded)?;
+        }
+        Ok(expanded)
+    }
+
+    fn expand_alias(
+        &self,
+        invocation: PlannedTask,
+        aliases: &BTreeMap<String, Vec<String>>,
+        expanding: &mut Vec<String>,
+        expanded: &mut Vec<PlannedTask>,
+    ) -> TaskResult {
+        let alias = match aliases.get(&invocation.name) {
+            Some(alias) if !self.contains_task_with_name(&invocation.name) => alias,
+            _ => {
+                expanded.push(invocation);
+                return Ok(());
+            }
+        };
+        if let Some(start) = expanding.iter().position(|name| *name == invocation.name) {
+            let mut cycle = expanding[start..].to_vec();
+            cycle.push(invocation.name);
+            return Err(TaskError::DependencyCycle(cycle));
+        }
+
+        let mut tasks = alias
+            .iter()
+            .filter_map(|task| PlannedTask::parse(task))
+            .collect::<Vec<PlannedTask>>();
+        if let Some(last) = tasks.last_mut() {
+            last.args.extend(invocation.args);
+        }
+
+        expanding.push(invocation.name);
+        for task in tasks {
+            self.expand_alias(task, aliases, expanding, expanded)?;
+        }
+        expanding.pop();
+        Ok(())
+    }
+
+    /// Checks if the task with the given name is a group of subtasks.
+    fn is_group(&self, task_name: &str) -> bool {
+        match self.jabu_task_manager.get_task(task_name) {
+            Some(task) => task.is_group(),
+            None => self
+                .task_manager
+                .get_task(task_name)
+                .is_some_and(|task| task.is_group()),
+        }
+    }
+
+    fn list_tasks(&self, aliases: &BTreeMap<String, Vec<String>>) {
         let mut table = prettytable::Table::new();
         table.set_format(*prettytable::format::consts::FORMAT_BORDERS_ONLY);
         table.add_row(Row::new(vec![Cell::new("TASKS")
@@ -368,6 +695,21 @@ impl GeneralTaskManager {
                     Cell::new(&task.description()),
                 ]));
             });
+        if !aliases.is_empty() {
+            table.add_empty_row();
+            table.add_row(Row::new(vec![Cell::new("ALIASES")
+                .with_style(Attr::Bold)
+                .with_style(Attr::BackgroundColor(color::BRIGHT_BLACK))
+                .with_style(Attr::ForegroundColor(color::WHITE))]));
+            aliases.iter().for_each(|(name, tasks)| {
+                table.add_row(Row::new(vec![
+                    Cell::new(name)
+                        .with_style(Attr::Bold)
+                        .with_style(Attr::ForegroundColor(color::BLUE)),
+                    Cell::new(&format!("Runs '{}'", tasks.join(", "))),
+                ]));
+            });
+        }
         table.printstd();
     }
 
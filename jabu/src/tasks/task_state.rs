@@ -0,0 +1,125 @@
+use crate::utils::hashing::{sha256_file, sha256_hex};
+use jabu_config::model::JabuProject;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Name of the file (*inside of [`jabu_config::model::FsSchema::target_jabu`]*)
+/// where the fingerprints of the last execution of every task are stored.
+pub const TASK_STATES_FILE_NAME: &str = "task-states.ron";
+
+/// Fingerprints of the last successful execution of a task, used to skip it
+/// when it's up to date (*see [`crate::tasks::JabuTask::inputs`]*).
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TaskState {
+    /// Fingerprint of the inputs of the task (*see [`inputs_fingerprint`]*).
+    pub inputs: String,
+
+    /// Hashes of the files written by the task, by their path. The task is out
+    /// of date if any of them has changed or disappeared, while other files that
+    /// appear in the outputs (*i.e. written by other tasks*) are ignored.
+    pub outputs: BTreeMap<String, String>,
+}
+
+/// States of the tasks executed in a project, with the task and its args
+/// (*i.e. `test --filter:AppTest`*) as key.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TaskStates {
+    pub tasks: BTreeMap<String, TaskState>,
+}
+
+impl TaskStates {
+    /// Loads the task states from the given file. If the file doesn't exist or
+    /// it cannot be parsed, `None` is returned.
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        ron::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    }
+
+    /// Writes the task states into the given file, creating its parent
+    /// directory if necessary.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = ron::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, contents)
+    }
+
+    /// Checks if the task was last executed with the same inputs, and its outputs
+    /// haven't changed since then.
+    pub fn is_up_to_date(&self, task: &str, inputs_fingerprint: &str) -> bool {
+        self.tasks.get(task).is_some_and(|state| {
+            state.inputs == inputs_fingerprint
+                && state
+                    .outputs
+                    .iter()
+                    .all(|(path, hash)| sha256_file(path).is_ok_and(|current| current == *hash))
+        })
+    }
+
+    /// Records a successful execution of the task, with the given inputs and the
+    /// current contents of its outputs.
+    pub fn record(
+        &mut self,
+        task: &str,
+        inputs_fingerprint: String,
+        outputs: &[PathBuf],
+    ) -> std::io::Result<()> {
+        let outputs = files_of(outputs)
+            .into_iter()
+            .map(|path| Ok((path.to_string_lossy().to_string(), sha256_file(&path)?)))
+            .collect::<std::io::Result<BTreeMap<String, String>>>()?;
+        self.tasks.insert(
+            task.to_string(),
+            TaskState {
+                inputs: inputs_fingerprint,
+                outputs,
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Returns a fingerprint of the `settings` the task is executed with (*such as the
+/// project's configuration*), and of the path and contents of every file in
+/// `inputs` (*the directories being walked recursively*).
+pub fn inputs_fingerprint(settings: &str, inputs: &[PathBuf]) -> std::io::Result<String> {
+    let mut fingerprint = settings.to_string();
+    for path in files_of(inputs) {
+        fingerprint.push_str(&format!(
+            "\n{}\t{}",
+            path.to_string_lossy(),
+            sha256_file(&path)?
+        ));
+    }
+    Ok(sha256_hex(fingerprint))
+}
+
+/// Returns the settings the tasks of a project are executed with, given to
+/// [`inputs_fingerprint`]: the project's configuration and the Java home used.
+pub fn project_settings(project: &JabuProject, java_home: &Path) -> String {
+    format!(
+        "{}\n{}",
+        ron::to_string(project).unwrap_or_default(),
+        java_home.to_string_lossy()
+    )
+}
+
+/// Returns the files in the given paths, sorted, walking the directories
+/// recursively. The paths that don't exist are ignored.
+fn files_of(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = paths
+        .iter()
+        .flat_map(|path| walkdir::WalkDir::new(path).follow_links(true))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect::<Vec<PathBuf>>();
+    files.sort();
+    files.dedup();
+    files
+}
@@ -135,4 +135,18 @@ fn test_default_values() {
         Ok(_) => (),
         Err(e) => assert!(false, stringify!(e))
     }
-}
\ No newline at end of file
+}
+#[test]
+fn options_without_short() {
+    let mut options = Options::default();
+    assert!(options.add_option(ParOption::builder().name("template").has_arg(true).build()));
+    assert!(options.add_option(ParOption::builder().name("force").build()));
+    assert!(options.add_option(ParOption::builder().name("name").short('n').build()));
+    // The names and the shorts that are given still have to be unique.
+    assert!(!options.add_option(ParOption::builder().name("force").build()));
+    assert!(!options.add_option(ParOption::builder().name("number").short('n').build()));
+
+    assert_eq!("--template:{value}", options.options[0].display_name());
+    assert_eq!("--force", options.options[1].display_name());
+    assert_eq!("--name,\n   -n", options.options[2].display_name());
+}
@@ -1,4 +1,6 @@
-use jabu_config::model::ArtifactSpec;
+use jabu_config::model::{
+    ArtifactSpec, LockFile, LockedArtifact, RepositoryLayout, MAVEN_CENTRAL_URL,
+};
 
 #[test]
 fn parse_dependency_spec() {
@@ -20,3 +22,686 @@ fn parse_invalid_dependency_spec() {
     let input = "me.userregistry:0.0.1-SNAPSHOT";
     assert!(ArtifactSpec::try_from(input).is_err())
 }
+
+#[test]
+fn lockfile_roundtrip() {
+    let artifact = ArtifactSpec::new("me", "registry", "1.0.0");
+    let maven_artifact = ArtifactSpec::new("org.typelevel", "cats-core_2.13", "2.9.0");
+    let lockfile = LockFile {
+        dependencies: vec![artifact.clone()],
+        maven_dependencies: vec![maven_artifact.clone()],
+        artifacts: vec![
+            LockedArtifact {
+                spec: artifact.clone(),
+                source: "https://jabu-remote-repository.com".to_string(),
+                layout: RepositoryLayout::Jabu,
+                jar_sha256: "0a".to_string(),
+                jaburon_sha256: "0b".to_string(),
+            },
+            LockedArtifact {
+                spec: maven_artifact.clone(),
+                source: MAVEN_CENTRAL_URL.to_string(),
+                layout: RepositoryLayout::Maven,
+                jar_sha256: "0c".to_string(),
+                jaburon_sha256: "0d".to_string(),
+            },
+        ],
+    };
+
+    let serialized = ron::to_string(&lockfile).unwrap();
+    assert!(serialized.contains("\"me_registry_1.0.0\""));
+    assert!(serialized.contains("\"org.typelevel:cats-core_2.13:2.9.0\""));
+
+    let parsed = LockFile::try_from(serialized.as_str()).unwrap();
+    assert_eq!(lockfile, parsed);
+    assert!(parsed.get(&artifact).is_some());
+    assert!(parsed.get(&ArtifactSpec::new("me", "registry", "2.0.0")).is_none());
+}
+
+mod project_types {
+    use jabu_config::model::{java_package_name, JabuProject, ProjectType, Workspace};
+
+    #[test]
+    fn parse_project_types() {
+        ProjectType::ALL.iter().for_each(|project_type| {
+            assert_eq!(Ok(*project_type), ProjectType::try_from(project_type.name()))
+        });
+        assert_eq!(Ok(ProjectType::Application), ProjectType::try_from("Binary"));
+        assert_eq!(Err(()), ProjectType::try_from("plugin"));
+    }
+
+    #[test]
+    fn only_applications_have_main_class() {
+        let application = JabuProject::default_of_name("app", ProjectType::Application);
+        let library = JabuProject::default_of_name("my-lib", ProjectType::Library);
+
+        assert_eq!(Some(&"App".to_string()), application.manifest.get("Main-Class"));
+        assert_eq!(None, library.manifest.get("Main-Class"));
+        assert_eq!(vec!["run"], application.default_tasks);
+        assert_eq!(vec!["jar"], library.default_tasks);
+        assert!(library
+            .fs_schema
+            .generated_files
+            .contains_key("./src/main/my_lib/Library.java"));
+    }
+
+    #[test]
+    fn package_names() {
+        assert_eq!("app", java_package_name("App"));
+        assert_eq!("my_lib", java_package_name("my-lib"));
+        assert_eq!("_2d", java_package_name("2d"));
+    }
+
+    #[test]
+    fn multi_module_members() {
+        let (workspace, members) = Workspace::new_multi_module();
+
+        assert_eq!(vec!["core", "app"], workspace.members);
+        assert_eq!(vec!["../core"], members[1].dependencies.path);
+        assert!(members[0].manifest.get("Main-Class").is_none());
+    }
+}
+
+mod workspace {
+    use crate::tests::TemporaryDirectory;
+    use jabu_config::{
+        fsutils::{path_dependency_classes, workspace_from_directory, workspace_members, WorkspaceError},
+        model::{JabuProject, ProjectType, Workspace, JABU_FILE_NAME, JABU_WORKSPACE_FILE_NAME},
+    };
+    use std::path::Path;
+
+    /// Creates a workspace in a new temporary directory, with a member for each
+    /// given name and path dependencies.
+    fn create_workspace(name: &str, members: &[(&str, &[&str])]) -> TemporaryDirectory {
+        let directory = TemporaryDirectory::new(name);
+
+        for (member, dependencies) in members {
+            let mut project = JabuProject::default_of_name(*member, ProjectType::Application);
+            project.dependencies.path = dependencies.iter().map(|d| format!("../{d}")).collect();
+            std::fs::create_dir_all(directory.join(member)).unwrap();
+            std::fs::write(
+                directory.join(member).join(JABU_FILE_NAME),
+                ron::to_string(&project).unwrap(),
+            )
+            .unwrap();
+        }
+
+        let workspace = Workspace {
+            members: members.iter().map(|(member, _)| member.to_string()).collect(),
+            ..Workspace::default()
+        };
+        std::fs::write(
+            directory.join(JABU_WORKSPACE_FILE_NAME),
+            ron::to_string(&workspace).unwrap(),
+        )
+        .unwrap();
+        directory
+    }
+
+    fn member_names(directory: &Path) -> Result<Vec<String>, WorkspaceError> {
+        let workspace = workspace_from_directory(Some(directory.to_path_buf()))
+            .unwrap()
+            .unwrap();
+        Ok(workspace_members(Some(directory.to_path_buf()), &workspace)?
+            .into_iter()
+            .map(|member| member.project.header.project_name)
+            .collect())
+    }
+
+    #[test]
+    fn members_are_sorted_by_dependencies() {
+        let directory = create_workspace(
+            "sorted",
+            &[("app", &["core", "utils"]), ("utils", &["core"]), ("core", &[]), ("docs", &[])],
+        );
+
+        assert_eq!(
+            vec!["core", "utils", "app", "docs"],
+            member_names(&directory).unwrap()
+        );
+    }
+
+    #[test]
+    fn members_depending_on_each_other() {
+        let directory = create_workspace("cycle", &[("a", &["b"]), ("b", &["c"]), ("c", &["a"])]);
+
+        match member_names(&directory) {
+            Err(WorkspaceError::Cycle(cycle)) => assert_eq!(vec!["a", "b", "c", "a"], cycle),
+            other => panic!("Expected a cycle, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn workspace_as_its_own_member() {
+        let directory = create_workspace("self", &[(".", &[]), ("core", &[])]);
+
+        match member_names(&directory) {
+            Err(WorkspaceError::SelfMember(member)) => assert_eq!(".", member),
+            other => panic!("Expected a self member, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transitive_path_dependency_classes() {
+        let directory = create_workspace(
+            "classes",
+            &[("app", &["utils"]), ("utils", &["core"]), ("core", &[])],
+        );
+        let app_directory = directory.join("app");
+        let app = JabuProject::try_from(
+            std::fs::read_to_string(app_directory.join(JABU_FILE_NAME))
+                .unwrap()
+                .as_str(),
+        )
+        .unwrap();
+
+        let mut classes = path_dependency_classes(Some(app_directory), &app).unwrap();
+        classes.sort();
+        let directory = directory.canonicalize().unwrap();
+        assert_eq!(
+            vec![
+                directory.join("core").join(app.fs_schema.target_classes()),
+                directory.join("utils").join(app.fs_schema.target_classes()),
+            ],
+            classes
+        );
+    }
+
+    #[test]
+    fn no_workspace() {
+        let directory = create_workspace("none", &[]);
+        std::fs::remove_file(directory.join(JABU_WORKSPACE_FILE_NAME)).unwrap();
+
+        assert_eq!(None, workspace_from_directory(Some(directory.to_path_buf())).unwrap());
+    }
+}
+
+mod profiles {
+    use crate::tasks::take_profile;
+    use jabu_config::model::{JabuProject, Profile, ProjectType};
+
+    #[test]
+    fn profiles_override_the_project() {
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.profiles = ron::from_str(
+            r#"{
+                "release": (
+                    java_config: (target: Some(21), compiler_args: Some(["-Werror"])),
+                    manifest: {"Main-Class": "com.App", "Sealed": "true"},
+                    fs_schema: (target: Some("./target/release"), jar_name: Some("app")),
+                    dependencies: (maven: Some(["org.slf4j:slf4j-api:2.0.9"])),
+                ),
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(None, project.clone().with_profile("dev"));
+        let release = project.clone().with_profile("release").unwrap();
+        assert_eq!(21, release.java_config.target);
+        assert_eq!(project.java_config.source, release.java_config.source);
+        assert_eq!(vec!["-Werror"], release.java_config.compiler_args);
+        assert_eq!("com.App", release.manifest.get("Main-Class").unwrap());
+        assert_eq!("true", release.manifest.get("Sealed").unwrap());
+        assert_eq!("./target/release", release.fs_schema.target);
+        assert_eq!(project.fs_schema.source, release.fs_schema.source);
+        assert_eq!("app", release.jar_name());
+        assert_eq!("anon_app_0.0.1", project.jar_name());
+        assert_eq!("slf4j-api", release.dependencies.maven[0].artifact_id);
+    }
+
+    #[test]
+    fn profile_maven_coordinates() {
+        let profile = r#"(dependencies: (maven: Some(["org.typelevel:cats-core_2.13:2.10.0"])))"#;
+        let profile: Profile = ron::from_str(profile).unwrap();
+        let maven = profile.dependencies.maven.as_ref().unwrap();
+        assert_eq!("org.typelevel", maven[0].author);
+        assert_eq!("cats-core_2.13", maven[0].artifact_id);
+        assert_eq!("2.10.0", maven[0].version);
+
+        let serialized = ron::to_string(&profile).unwrap();
+        assert!(serialized.contains(r#"Some(["org.typelevel:cats-core_2.13:2.10.0"])"#));
+        assert_eq!(profile, ron::from_str(&serialized).unwrap());
+
+        let without_maven: Profile = ron::from_str("(dependencies: (remote: Some([])))").unwrap();
+        assert_eq!(None, without_maven.dependencies.maven);
+        assert!(ron::from_str::<Profile>(r#"(dependencies: (maven: Some(["org_lib_1.0"])))"#).is_err());
+    }
+
+    #[test]
+    fn profile_option() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+
+        let mut command_line = args(&["--profile:release", "build"]);
+        assert_eq!(Some("release".to_string()), take_profile(&mut command_line).unwrap());
+        assert_eq!(args(&["build"]), command_line);
+
+        let mut command_line = args(&["info", "--profile", "release", "--", "--profile:dev"]);
+        assert_eq!(Some("release".to_string()), take_profile(&mut command_line).unwrap());
+        assert_eq!(args(&["info", "--", "--profile:dev"]), command_line);
+
+        let mut command_line = args(&["build"]);
+        assert_eq!(None, take_profile(&mut command_line).unwrap());
+        assert!(take_profile(&mut args(&["build", "--profile"])).is_err());
+        assert!(take_profile(&mut args(&["build", "--profile:"])).is_err());
+    }
+}
+
+mod interpolation {
+    use jabu_config::{
+        fsutils::ProjectLoadingError,
+        interpolation::interpolate_project,
+        model::{ArtifactSpec, JabuProject, ProjectType},
+    };
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "BUILD_NUMBER" => Some("42".to_string()),
+            "HOME" => Some("/home/me".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn variables_are_replaced() {
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.header.version = "1.0.${env.BUILD_NUMBER}".to_string();
+        project.properties = [
+            ("releases", "${env.HOME}/releases"),
+            ("channel", "${env.CHANNEL:-stable}"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+        project.fs_schema.target = "${releases}/${header.project_name}".to_string();
+        project.fs_schema.jar_name = Some("app-${header.version}-${channel}".to_string());
+        project.default_tasks = vec!["run -- $${literal}".to_string()];
+
+        interpolate_project(&mut project, &env).unwrap();
+        assert_eq!("1.0.42", project.header.version);
+        assert_eq!("/home/me/releases/app", project.fs_schema.target);
+        assert_eq!(Some("app-1.0.42-stable".to_string()), project.fs_schema.jar_name);
+        assert_eq!(vec!["run -- ${literal}"], project.default_tasks);
+        assert_eq!("stable", project.properties["channel"]);
+    }
+
+    #[test]
+    fn dependencies_with_default_values() {
+        let mut project = JabuProject::try_from(
+            r#"(
+                header: (project_name: "app"),
+                dependencies: (
+                    remote: ["me_lib_${env.LIB_VERSION:-1.0}"],
+                    maven: ["org.example:${env.ARTIFACT:-core}:${env.BUILD_NUMBER:-1}"],
+                ),
+            )"#,
+        )
+        .unwrap();
+
+        interpolate_project(&mut project, &env).unwrap();
+        assert_eq!(vec![ArtifactSpec::new("me", "lib", "1.0")], project.dependencies.remote);
+        assert_eq!(
+            vec![ArtifactSpec::new("org.example", "core", "42")],
+            project.dependencies.maven
+        );
+    }
+
+    #[test]
+    fn undefined_and_circular_variables() {
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.fs_schema.lib = "${env.LIBS}".to_string();
+        match interpolate_project(&mut project, &env) {
+            Err(ProjectLoadingError::UndefinedVariable { field, variable }) => {
+                assert_eq!("fs_schema.lib", field);
+                assert_eq!("env.LIBS", variable);
+            }
+            other => panic!("Expected an undefined variable, got {other:?}"),
+        }
+
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.properties.insert("a".to_string(), "${b}".to_string());
+        project.properties.insert("b".to_string(), "${a}".to_string());
+        project.header.description = "${a}".to_string();
+        match interpolate_project(&mut project, &env) {
+            Err(ProjectLoadingError::CircularVariable { field, variable }) => {
+                assert_eq!("header.description", field);
+                assert_eq!("a", variable);
+            }
+            other => panic!("Expected a circular variable, got {other:?}"),
+        }
+    }
+}
+
+mod validation {
+    use crate::tests::TemporaryDirectory;
+    use jabu_config::{
+        model::{
+            ArtifactSpec, CustomTask, CustomTaskAction, CustomTaskOption, JabuProject, ProjectType,
+            JABU_FILE_NAME,
+        },
+        validation::{check_project, Location, ProjectDiagnostics, Severity},
+    };
+
+    /// Writes the given jabu file in a new temporary directory, containing the
+    /// sources of the default application and its libraries directory.
+    fn project_directory(name: &str, jabu_file: &str) -> TemporaryDirectory {
+        let directory = TemporaryDirectory::new(name);
+        std::fs::create_dir_all(directory.join("src/main")).unwrap();
+        std::fs::create_dir_all(directory.join("lib")).unwrap();
+        std::fs::write(directory.join("src/main/App.java"), "public class App {}").unwrap();
+        std::fs::write(directory.join(JABU_FILE_NAME), jabu_file).unwrap();
+        directory
+    }
+
+    fn pretty(project: &JabuProject) -> String {
+        ron::ser::to_string_pretty(project, ron::ser::PrettyConfig::default()).unwrap()
+    }
+
+    fn problems(diagnostics: &ProjectDiagnostics) -> Vec<(Severity, &str, Option<usize>)> {
+        diagnostics
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let line = diagnostic.location.map(|location| location.line);
+                (diagnostic.severity, diagnostic.message.as_str(), line)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_project_has_no_problems() {
+        let project = JabuProject::default_of_name("app", ProjectType::Application);
+        let directory = project_directory("valid", &pretty(&project));
+        let diagnostics = check_project(&directory, None).unwrap();
+        assert!(diagnostics.diagnostics.is_empty());
+        assert!(!diagnostics.has_errors());
+    }
+
+    #[test]
+    fn semantic_problems_are_located() {
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.header.version = "1.x".to_string();
+        project.java_config.source = 21;
+        project.fs_schema.lib = "./libs".to_string();
+        project.manifest.contents.insert("Main-Class".to_string(), "com.Main".to_string());
+        project.dependencies.remote = vec![ArtifactSpec::new("a", "b", "1.0")];
+        project.dependencies.maven = vec![ArtifactSpec::new("a", "b", "2.0")];
+        let source = pretty(&project);
+        let line_of = |text: &str| source.lines().position(|line| line.contains(text)).map(|i| i + 1);
+        let directory = project_directory("semantic", &source);
+
+        let diagnostics = check_project(&directory, None).unwrap();
+        assert!(diagnostics.has_errors());
+        assert_eq!(
+            vec![
+                (
+                    Severity::Error,
+                    "the source version (21) is greater than the target version (17)",
+                    line_of("source: 21")
+                ),
+                (
+                    Severity::Error,
+                    "'1.x' isn't a valid version (i.e. '1.2.0' or '2.0-beta')",
+                    line_of("\"1.x\"")
+                ),
+                (
+                    Severity::Warning,
+                    "the libraries directory './libs' doesn't exist",
+                    line_of("\"./libs\"")
+                ),
+                (
+                    Severity::Error,
+                    "the main class 'com.Main' doesn't exist in './src/main'",
+                    line_of("\"com.Main\"")
+                ),
+                (
+                    Severity::Error,
+                    "the dependency 'a:b' is declared more than once",
+                    line_of("\"a:b:2.0\"")
+                ),
+            ],
+            problems(&diagnostics)
+        );
+
+        // The version is marked with carets under the line containing it.
+        let line = source.lines().find(|line| line.contains("\"1.x\"")).unwrap();
+        let column = line.find("\"1.x\"").unwrap();
+        let rendered = diagnostics.render(&diagnostics.diagnostics[1]);
+        assert!(rendered.ends_with(&format!("| {line}\n  | {}^^^^^", " ".repeat(column))));
+    }
+
+    #[test]
+    fn profiles_and_syntax_errors() {
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        let mut release = jabu_config::model::Profile::default();
+        release.java_config.target = Some(11);
+        project.profiles.insert("release".to_string(), release);
+        let source = pretty(&project);
+        let line = source.lines().position(|line| line.contains("source: 17")).unwrap() + 1;
+        let directory = project_directory("profiles-check", &source);
+        assert_eq!(
+            vec![(
+                Severity::Error,
+                "the source version (17) is greater than the target version (11) (with the profile 'release')",
+                Some(line)
+            )],
+            problems(&check_project(&directory, None).unwrap())
+        );
+
+        std::fs::write(directory.join(JABU_FILE_NAME), "(\n    header: (\n        project_name: 3,\n").unwrap();
+        let diagnostics = check_project(&directory, None).unwrap();
+        assert_eq!(1, diagnostics.diagnostics.len());
+        assert_eq!(
+            Some(Location { line: 3, column: 23, length: 1 }),
+            diagnostics.diagnostics[0].location
+        );
+    }
+
+    #[test]
+    fn duplicated_custom_task_options() {
+        let option = |name: &str| CustomTaskOption {
+            name: name.to_string(),
+            description: None,
+            has_arg: false,
+            required: false,
+            default_value: None,
+        };
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.custom_tasks.insert(
+            "lint".to_string(),
+            CustomTask {
+                description: String::new(),
+                run: CustomTaskAction::Command("true".to_string()),
+                options: vec![option("verbose"), option("version"), option("verbose")],
+                depends_on: Vec::new(),
+                before: Vec::new(),
+                after: Vec::new(),
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+            },
+        );
+        let source = pretty(&project);
+        let line = source.lines().position(|line| line.contains("options:")).map(|i| i + 1);
+        let directory = project_directory("custom-options", &source);
+
+        assert_eq!(
+            vec![(
+                Severity::Error,
+                "the option 'verbose' of the custom task 'lint' is declared more than once",
+                line
+            )],
+            problems(&check_project(&directory, None).unwrap())
+        );
+    }
+
+    #[test]
+    fn selected_profile_is_checked() {
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.java_config.target = 11;
+        let mut release = jabu_config::model::Profile::default();
+        release.java_config.source = Some(11);
+        project.profiles.insert("release".to_string(), release);
+        let mut debug = jabu_config::model::Profile::default();
+        debug.java_config.target = Some(21);
+        project.profiles.insert("debug".to_string(), debug);
+        let directory = project_directory("selected-profile", &pretty(&project));
+
+        // The profile fixes the problem of the project.
+        assert!(check_project(&directory, Some("release")).unwrap().diagnostics.is_empty());
+        assert_eq!(
+            vec![
+                "the source version (17) is greater than the target version (11)",
+                "the target version (21) is greater than the java version (17) (with the profile 'debug')",
+            ],
+            problems(&check_project(&directory, None).unwrap())
+                .into_iter()
+                .map(|(_, message, _)| message)
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(
+            vec!["the target version (21) is greater than the java version (17) (with the profile 'debug')"],
+            problems(&check_project(&directory, Some("debug")).unwrap())
+                .into_iter()
+                .map(|(_, message, _)| message)
+                .collect::<Vec<&str>>()
+        );
+    }
+}
+
+mod migration {
+    use jabu_config::{
+        fsutils::ProjectLoadingError,
+        migration::migrate_project,
+        model::{JabuProject, ProjectType, CURRENT_FORMAT_VERSION},
+    };
+
+    #[test]
+    fn old_layouts_are_migrated() {
+        // Only the name of the project is required.
+        let mut project = JabuProject::try_from(
+            r#"(header: (project_name: "app"), fs_schema: (source: "./java"))"#,
+        )
+        .unwrap();
+        assert_eq!(0, project.format_version);
+        assert_eq!("0.0.1", project.header.version);
+        assert_eq!("./java", project.fs_schema.source);
+        assert_eq!("./target", project.fs_schema.target);
+        assert!(project.fs_schema.generated_files.is_empty());
+        assert_eq!(17, project.java_config.java_version);
+
+        assert_eq!(1, migrate_project(&mut project).unwrap().len());
+        assert_eq!(CURRENT_FORMAT_VERSION, project.format_version);
+        assert!(migrate_project(&mut project).unwrap().is_empty());
+
+        let project = JabuProject::default_of_name("app", ProjectType::Application);
+        assert_eq!(CURRENT_FORMAT_VERSION, project.format_version);
+    }
+
+    #[test]
+    fn newer_format_versions_are_unsupported() {
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.format_version = CURRENT_FORMAT_VERSION + 1;
+        assert!(matches!(
+            migrate_project(&mut project),
+            Err(ProjectLoadingError::UnsupportedFormatVersion(format_version))
+                if format_version == CURRENT_FORMAT_VERSION + 1
+        ));
+    }
+}
+
+mod formats {
+    use crate::tests::TemporaryDirectory;
+    use jabu_config::{
+        format::{ConfigFormat, ParsingError, CONFIG_FORMATS},
+        fsutils::{project_file, project_from_directory, ProjectLoadingError},
+        model::{ArtifactSpec, CustomTask, CustomTaskAction, JabuProject, Profile, ProjectType},
+    };
+
+    fn sample_project() -> JabuProject {
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.header.license = Some("MIT".to_string());
+        project.dependencies.maven = vec![ArtifactSpec::new("org.junit", "junit", "5.10.0")];
+        project.tasks.insert("ci".to_string(), vec!["clean".to_string(), "jar".to_string()]);
+        project.custom_tasks.insert(
+            "proto".to_string(),
+            CustomTask {
+                description: "Generates the sources.".to_string(),
+                run: CustomTaskAction::Command("protoc".to_string()),
+                options: Vec::new(),
+                depends_on: Vec::new(),
+                before: vec!["build".to_string()],
+                after: Vec::new(),
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+            },
+        );
+        let mut release = Profile::default();
+        release.java_config.compiler_args = Some(vec!["-Werror".to_string()]);
+        project.profiles.insert("release".to_string(), release);
+        // The generated files aren't written in the jabu file.
+        project.fs_schema.generated_files.clear();
+        project
+    }
+
+    #[test]
+    fn projects_in_every_format() {
+        let project = sample_project();
+        for format in CONFIG_FORMATS {
+            let contents = format.serialize(&project).unwrap();
+            assert_eq!(
+                project,
+                format.deserialize::<JabuProject>(&contents).unwrap(),
+                "{format}"
+            );
+        }
+        assert_eq!(Some(ConfigFormat::Toml), ConfigFormat::from_extension("toml"));
+        assert_eq!(None, ConfigFormat::from_extension("yaml"));
+    }
+
+    #[test]
+    fn parsing_errors_are_located() {
+        let toml = "format_version = 1\n[header]\nproject_name = 3\n";
+        assert!(matches!(
+            ConfigFormat::Toml.deserialize::<JabuProject>(toml),
+            Err(ParsingError { line: 3, column: 16, .. })
+        ));
+        let json = "{\n  \"header\": {\n    \"project_name\": [\n";
+        assert!(matches!(
+            ConfigFormat::Json.deserialize::<JabuProject>(json),
+            Err(ParsingError { line: 3, .. })
+        ));
+        let ron = "(\n    header: (\n        project_name: 3,\n";
+        assert!(matches!(
+            ConfigFormat::Ron.deserialize::<JabuProject>(ron),
+            Err(ParsingError { line: 3, column: 23, .. })
+        ));
+    }
+
+    #[test]
+    fn project_file_is_detected() {
+        let directory = TemporaryDirectory::new("formats");
+        let project = sample_project();
+
+        // Without a jabu file, the default one is expected.
+        assert_eq!(
+            (directory.join("jabu.ron"), ConfigFormat::Ron),
+            project_file(&directory).unwrap()
+        );
+
+        let toml = ConfigFormat::Toml.serialize(&project).unwrap();
+        std::fs::write(directory.join("jabu.toml"), toml).unwrap();
+        assert_eq!(
+            (directory.join("jabu.toml"), ConfigFormat::Toml),
+            project_file(&directory).unwrap()
+        );
+        assert_eq!(project, project_from_directory(Some(directory.to_path_buf())).unwrap());
+
+        let json = ConfigFormat::Json.serialize(&project).unwrap();
+        std::fs::write(directory.join("jabu.json"), json).unwrap();
+        match project_from_directory(Some(directory.to_path_buf())) {
+            Err(ProjectLoadingError::SeveralProjectFiles(files)) => {
+                assert_eq!(vec!["jabu.toml", "jabu.json"], files)
+            }
+            other => panic!("Expected several project files, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    tasks::impls::{create_fat_jar, parse_relocations, DuplicateClass},
+    tests::TemporaryDirectory,
+    utils::classfile::{relocate_class, ClassFileInfo, Relocation},
+};
+use jabu_config::model::{JabuProject, ProjectType};
+use std::{
+    io::{Cursor, Read, Write},
+    path::Path,
+};
+use zip::{write::FileOptions, ZipArchive, ZipWriter};
+
+fn write_files(directory: &Path, files: &[(&str, &[u8])]) {
+    for (name, contents) in files {
+        let path = directory.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+}
+
+fn write_jar(path: &Path, files: &[(&str, &[u8])]) {
+    let mut jar = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in files {
+        jar.start_file(*name, FileOptions::default()).unwrap();
+        jar.write_all(contents).unwrap();
+    }
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, jar.finish().unwrap().into_inner()).unwrap();
+}
+
+fn read_entry(archive: &mut ZipArchive<std::fs::File>, name: &str) -> String {
+    let mut contents = String::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut contents)
+        .unwrap();
+    contents
+}
+
+/// Bytes of a class `com/lib/Util` (*from `Util.java`*) with a method taking a
+/// `com/lib/Other`, and a string constant with the binary name of `com.lib.Other`.
+fn sample_class_file() -> Vec<u8> {
+    let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 61];
+    let mut pool = Vec::new();
+    let mut utf8 = |value: &str| {
+        pool.push(1);
+        pool.extend((value.len() as u16).to_be_bytes());
+        pool.extend(value.as_bytes());
+    };
+    utf8("com/lib/Util"); // 1
+    utf8("java/lang/Object"); // 2
+    utf8("(Lcom/lib/Other;[Lcom/library/Other;)V"); // 3
+    utf8("SourceFile"); // 4
+    utf8("Util.java"); // 5
+    utf8("com.lib.Other"); // 6
+    pool.extend([7, 0, 1]); // 7
+    pool.extend([7, 0, 2]); // 8
+    bytes.extend(9u16.to_be_bytes());
+    bytes.extend(pool);
+    // access_flags, this_class, super_class, interfaces, fields, methods
+    bytes.extend([0, 0x21, 0, 7, 0, 8, 0, 0, 0, 0, 0, 0]);
+    // One attribute: SourceFile -> Util.java
+    bytes.extend([0, 1, 0, 4, 0, 0, 0, 2, 0, 5]);
+    bytes
+}
+
+#[test]
+fn relocations() {
+    let relocations = parse_relocations("com.lib=shaded.com.lib, org.x.=y").unwrap();
+    assert_eq!(
+        vec![
+            Relocation::new("com.lib", "shaded.com.lib"),
+            Relocation::new("org.x", "y")
+        ],
+        relocations
+    );
+    assert_eq!(
+        Some("shaded/com/lib/Util.class".to_string()),
+        relocations[0].relocate_path("com/lib/Util.class")
+    );
+    assert_eq!(None, relocations[0].relocate_path("com/library/Util.class"));
+    assert_eq!(
+        Some("y.Impl".to_string()),
+        relocations[1].relocate_binary_name("org.x.Impl")
+    );
+    assert!(parse_relocations("com.lib").is_err());
+    assert!(parse_relocations("=shaded").is_err());
+}
+
+#[test]
+fn relocated_class_file() {
+    let relocations = [Relocation::new("com.lib", "shaded.com.lib")];
+    let relocated = relocate_class(&sample_class_file(), &relocations).unwrap();
+    let info = ClassFileInfo::parse(&relocated).unwrap();
+
+    assert_eq!("shaded/com/lib/Util", info.class_name);
+    assert_eq!(Some("Util.java".to_string()), info.source_file);
+    assert!(info.referenced_classes.contains("shaded/com/lib/Other"));
+    assert!(info.referenced_classes.contains("com/library/Other"));
+    assert!(info.referenced_classes.contains("java/lang/Object"));
+    assert!(relocated
+        .windows("shaded.com.lib.Other".len())
+        .any(|window| window == b"shaded.com.lib.Other"));
+
+    // Classes outside of the relocated packages are kept as they are.
+    let unrelated = [Relocation::new("org.other", "shaded.org.other")];
+    assert_eq!(
+        sample_class_file(),
+        relocate_class(&sample_class_file(), &unrelated).unwrap()
+    );
+}
+
+#[test]
+fn fat_jar_contents() {
+    let directory = TemporaryDirectory::new("fatjar");
+    let project = JabuProject::default_of_name("app", ProjectType::Application);
+    write_files(
+        &directory.join(project.fs_schema.target_classes()),
+        &[
+            ("App.class", b"app"),
+            ("META-INF/services/x.Service", b"a.Impl\n"),
+        ],
+    );
+    write_jar(
+        &directory.join(&project.fs_schema.lib).join("dep.jar"),
+        &[
+            ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\nMain-Class: Dep\n"),
+            ("META-INF/DEP.SF", b"signature"),
+            ("META-INF/DEP.RSA", b"signature"),
+            ("META-INF/services/x.Service", b"# providers\nb.Impl\na.Impl\n"),
+            ("App.class", b"dep"),
+            ("dep/Dep.class", b"dep"),
+            ("module-info.class", b"module"),
+        ],
+    );
+
+    let jar_path = directory.join("app-all.jar");
+    let report = create_fat_jar(&directory, &project, &jar_path, &[]).unwrap();
+
+    assert_eq!(4, report.entries);
+    assert_eq!(1, report.merged_services);
+    assert_eq!(2, report.dropped_signatures);
+    assert_eq!(
+        vec![DuplicateClass {
+            name: "App.class".to_string(),
+            kept_from: "classes".to_string(),
+            ignored_from: "dep.jar".to_string(),
+        }],
+        report.duplicates
+    );
+
+    let mut archive = ZipArchive::new(std::fs::File::open(&jar_path).unwrap()).unwrap();
+    assert_eq!("META-INF/MANIFEST.MF", archive.by_index(0).unwrap().name());
+    assert_eq!(
+        "Manifest-Version: 1.0\r\nCreated-By: jabu\r\nMain-Class: App\r\n\r\n",
+        read_entry(&mut archive, "META-INF/MANIFEST.MF")
+    );
+    assert_eq!("app", read_entry(&mut archive, "App.class"));
+    assert_eq!("dep", read_entry(&mut archive, "dep/Dep.class"));
+    assert_eq!(
+        "a.Impl\nb.Impl\n",
+        read_entry(&mut archive, "META-INF/services/x.Service")
+    );
+    assert!(archive.by_name("META-INF/DEP.SF").is_err());
+    assert!(archive.by_name("module-info.class").is_err());
+}
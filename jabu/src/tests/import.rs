@@ -0,0 +1,40 @@
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{impls::ImportTask, Task},
+    tests::TemporaryDirectory,
+};
+use jabu_config::fsutils::project_from_directory;
+use std::path::Path;
+
+const POM: &str = r#"<project>
+    <modelVersion>4.0.0</modelVersion>
+    <groupId>com.example</groupId>
+    <artifactId>imported</artifactId>
+    <version>1.0</version>
+</project>"#;
+
+fn import(pom_path: &Path, force: bool) -> bool {
+    let task = ImportTask;
+    let mut args = vec![format!("--file:{}", pom_path.to_string_lossy())];
+    if force {
+        args.push("--force".to_string());
+    }
+    let parsed_args = ParsedArguments::new_with_options(args, &task.options().unwrap()).unwrap();
+    task.execute(Vec::new(), Some(parsed_args)).is_ok()
+}
+
+#[test]
+fn existing_jabu_files_are_kept() {
+    let directory = TemporaryDirectory::new("import");
+    std::fs::write(directory.join("pom.xml"), POM).unwrap();
+    std::fs::write(directory.join("jabu.toml"), "").unwrap();
+
+    assert!(!import(&directory.join("pom.xml"), false));
+    assert_eq!("", std::fs::read_to_string(directory.join("jabu.toml")).unwrap());
+
+    // The existing jabu file is overwritten in its own format.
+    assert!(import(&directory.join("pom.xml"), true));
+    assert!(!directory.join("jabu.ron").exists());
+    let project = project_from_directory(Some(directory.to_path_buf())).unwrap();
+    assert_eq!("imported", project.header.project_name);
+}
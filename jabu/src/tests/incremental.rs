@@ -0,0 +1,190 @@
+mod compile_state {
+    use crate::tasks::impls::{CompileState, SourceState};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn source_state(hash: &str, classes: &[&str], dependencies: &[&str]) -> SourceState {
+        SourceState {
+            hash: hash.to_string(),
+            classes: classes.iter().map(|c| c.to_string()).collect(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            declares_constants: false,
+        }
+    }
+
+    /// `A` depends on `B`, which depends on `C`. `D` is independent.
+    fn sample_state() -> CompileState {
+        let mut sources = BTreeMap::new();
+        sources.insert("A.java".to_string(), source_state("a", &["A"], &["B"]));
+        sources.insert("B.java".to_string(), source_state("b", &["B", "B$1"], &["C"]));
+        sources.insert("C.java".to_string(), source_state("c", &["C"], &[]));
+        sources.insert("D.java".to_string(), source_state("d", &["D"], &["java/lang/Object"]));
+        CompileState {
+            settings_fingerprint: "settings".to_string(),
+            sources,
+        }
+    }
+
+    fn hashes(sources: &[(&str, &str)]) -> BTreeMap<String, String> {
+        sources
+            .iter()
+            .map(|(source, hash)| (source.to_string(), hash.to_string()))
+            .collect()
+    }
+
+    fn set(items: &[&str]) -> BTreeSet<String> {
+        items.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn nothing_changed() {
+        let current = hashes(&[("A.java", "a"), ("B.java", "b"), ("C.java", "c"), ("D.java", "d")]);
+        assert!(sample_state().plan(&current, "settings").is_empty());
+    }
+
+    #[test]
+    fn changed_source_recompiles_dependents() {
+        let current = hashes(&[("A.java", "a"), ("B.java", "b"), ("C.java", "c2"), ("D.java", "d")]);
+        let plan = sample_state().plan(&current, "settings");
+
+        assert!(!plan.full_rebuild);
+        assert_eq!(set(&["A.java", "B.java", "C.java"]), plan.to_compile);
+        assert_eq!(set(&["A.java", "B.java", "C.java"]), plan.stale);
+    }
+
+    #[test]
+    fn deleted_and_new_sources() {
+        let current = hashes(&[("A.java", "a"), ("B.java", "b"), ("C.java", "c"), ("E.java", "e")]);
+        let plan = sample_state().plan(&current, "settings");
+
+        assert_eq!(set(&["E.java"]), plan.to_compile);
+        assert_eq!(set(&["D.java"]), plan.stale);
+    }
+
+    #[test]
+    fn changed_settings_rebuild_everything() {
+        let current = hashes(&[("A.java", "a"), ("B.java", "b"), ("C.java", "c"), ("D.java", "d")]);
+        let plan = sample_state().plan(&current, "other settings");
+
+        assert!(plan.full_rebuild);
+        assert_eq!(4, plan.to_compile.len());
+    }
+
+    #[test]
+    fn changed_constants_rebuild_everything() {
+        // `D` uses a constant of `C`, which has been inlined, so it doesn't reference it.
+        let mut state = sample_state();
+        state.sources.get_mut("C.java").unwrap().declares_constants = true;
+        let current = hashes(&[("A.java", "a"), ("B.java", "b"), ("C.java", "c2"), ("D.java", "d")]);
+        let plan = state.plan(&current, "settings");
+
+        assert!(plan.full_rebuild);
+        assert_eq!(4, plan.to_compile.len());
+
+        // Unchanged sources declaring constants don't affect the plan.
+        let current = hashes(&[("A.java", "a2"), ("B.java", "b"), ("C.java", "c"), ("D.java", "d")]);
+        let plan = state.plan(&current, "settings");
+
+        assert!(!plan.full_rebuild);
+        assert_eq!(set(&["A.java"]), plan.to_compile);
+    }
+
+    #[test]
+    fn pending_sources_are_recompiled_after_a_failure() {
+        let current = hashes(&[("A.java", "a"), ("B.java", "b"), ("C.java", "c2"), ("D.java", "d")]);
+        let mut state = sample_state();
+        let plan = state.plan(&current, "settings");
+        state.mark_pending(&plan, &current);
+
+        // The compilation failed and `C.java` was reverted to its previous contents.
+        let reverted = hashes(&[("A.java", "a"), ("B.java", "b"), ("C.java", "c"), ("D.java", "d")]);
+        assert_eq!(
+            set(&["A.java", "B.java", "C.java"]),
+            state.plan(&reverted, "settings").to_compile
+        );
+    }
+}
+
+mod classfile {
+    use crate::utils::classfile::ClassFileInfo;
+
+    fn utf8(pool: &mut Vec<u8>, value: &str) {
+        pool.push(1);
+        pool.extend((value.len() as u16).to_be_bytes());
+        pool.extend(value.as_bytes());
+    }
+
+    fn class(pool: &mut Vec<u8>, name_index: u16) {
+        pool.push(7);
+        pool.extend(name_index.to_be_bytes());
+    }
+
+    /// Bytes of a class `com/example/A` (*from `A.java`*) referencing `com/example/B`
+    /// through a method descriptor.
+    fn sample_class_file() -> Vec<u8> {
+        class_file_with_fields(&[])
+    }
+
+    /// Same as [`sample_class_file`], with an `int` field for each of the given access
+    /// flags, holding a `ConstantValue` attribute.
+    fn class_file_with_fields(constant_fields: &[u16]) -> Vec<u8> {
+        let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 61];
+        let mut pool = Vec::new();
+        utf8(&mut pool, "com/example/A"); // 1
+        class(&mut pool, 1); // 2
+        utf8(&mut pool, "java/lang/Object"); // 3
+        class(&mut pool, 3); // 4
+        utf8(&mut pool, "(Lcom/example/B;I)V"); // 5
+        utf8(&mut pool, "SourceFile"); // 6
+        utf8(&mut pool, "A.java"); // 7
+        utf8(&mut pool, "VALUE"); // 8
+        utf8(&mut pool, "I"); // 9
+        utf8(&mut pool, "ConstantValue"); // 10
+        pool.extend([3, 0, 0, 0, 42]); // 11
+        bytes.extend(12u16.to_be_bytes());
+        bytes.extend(pool);
+        // access_flags, this_class, super_class, interfaces
+        bytes.extend([0, 0x21, 0, 2, 0, 4, 0, 0]);
+        bytes.extend((constant_fields.len() as u16).to_be_bytes());
+        constant_fields.iter().for_each(|access_flags| {
+            bytes.extend(access_flags.to_be_bytes());
+            // name, descriptor and one attribute: ConstantValue -> 42
+            bytes.extend([0, 8, 0, 9, 0, 1, 0, 10, 0, 0, 0, 2, 0, 11]);
+        });
+        // methods
+        bytes.extend([0, 0]);
+        // One attribute: SourceFile -> A.java
+        bytes.extend([0, 1, 0, 6, 0, 0, 0, 2, 0, 7]);
+        bytes
+    }
+
+    #[test]
+    fn parse_class_file() {
+        let info = ClassFileInfo::parse(&sample_class_file()).unwrap();
+
+        assert_eq!("com/example/A", info.class_name);
+        assert_eq!("com/example", info.package());
+        assert_eq!(Some("A.java".to_string()), info.source_file);
+        assert!(info.referenced_classes.contains("com/example/B"));
+        assert!(info.referenced_classes.contains("java/lang/Object"));
+        assert!(!info.referenced_classes.contains("com/example/A"));
+        assert!(!info.declares_constants);
+    }
+
+    #[test]
+    fn constant_fields() {
+        // public static final
+        let info = ClassFileInfo::parse(&class_file_with_fields(&[0x0019])).unwrap();
+        assert!(info.declares_constants);
+        assert_eq!(Some("A.java".to_string()), info.source_file);
+
+        // private static final, which cannot be used by other classes
+        let info = ClassFileInfo::parse(&class_file_with_fields(&[0x001A])).unwrap();
+        assert!(!info.declares_constants);
+    }
+
+    #[test]
+    fn invalid_class_file() {
+        assert!(ClassFileInfo::parse(&[0, 1, 2, 3]).is_err());
+        assert!(ClassFileInfo::parse(&sample_class_file()[..20]).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    tests::TemporaryDirectory,
+    utils::jar::{date_time_from_epoch, write_jar, MANIFEST_PATH},
+};
+use jabu_config::model::JarManifest;
+use std::collections::{BTreeMap, HashMap};
+use zip::{DateTime, ZipArchive};
+
+fn manifest(attributes: &[(&str, &str)]) -> JarManifest {
+    JarManifest::from(
+        attributes
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<String, String>>(),
+    )
+}
+
+#[test]
+fn manifest_attributes_are_sorted() {
+    let manifest = manifest(&[("Main-Class", "App"), ("Class-Path", "lib/a.jar")]);
+    assert_eq!(
+        "Manifest-Version: 1.0\r\nClass-Path: lib/a.jar\r\nMain-Class: App\r\n\r\n",
+        manifest.to_string()
+    );
+
+    let manifest = self::manifest(&[("Main-Class", "App"), ("Manifest-Version", "2.0")]);
+    assert_eq!(
+        "Manifest-Version: 2.0\r\nMain-Class: App\r\n\r\n",
+        manifest.to_string()
+    );
+}
+
+#[test]
+fn manifest_lines_are_wrapped() {
+    let class_path = (0..10)
+        .map(|i| format!("lib/dependency-{i}.jar"))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let contents = manifest(&[("Class-Path", &class_path), ("Name", "ñandú")]).to_string();
+
+    let lines = contents.split("\r\n").collect::<Vec<&str>>();
+    assert!(lines.iter().all(|line| line.len() <= 72));
+    assert!(lines[2].starts_with(' '));
+    // Removing the line breaks of the continuation lines gives back the attribute.
+    assert!(contents
+        .replace("\r\n ", "")
+        .contains(&format!("Class-Path: {class_path}\r\n")));
+    assert!(contents.contains("Name: ñandú\r\n"));
+}
+
+fn date(time: DateTime) -> (u16, u8, u8, u8, u8, u8) {
+    (
+        time.year(),
+        time.month(),
+        time.day(),
+        time.hour(),
+        time.minute(),
+        time.second(),
+    )
+}
+
+#[test]
+fn epoch_to_date_time() {
+    assert_eq!(
+        Some((2023, 11, 14, 22, 13, 20)),
+        date_time_from_epoch(1_700_000_000).map(date)
+    );
+    assert_eq!(
+        Some((2000, 2, 29, 0, 0, 0)),
+        date_time_from_epoch(951_782_400).map(date)
+    );
+    // Before 1980, which can't be stored in a zip archive.
+    assert!(date_time_from_epoch(0).is_none());
+}
+
+#[test]
+fn jars_are_reproducible() {
+    let directory = TemporaryDirectory::new("jar");
+    let manifest = manifest(&[("Main-Class", "com.example.App")]);
+    let entries = [
+        ("com/example/App.class", "app"),
+        ("META-INF/MANIFEST.MF", "ignored"),
+        ("app.properties", "key=value"),
+    ]
+    .iter()
+    .map(|(name, contents)| (name.to_string(), contents.as_bytes().to_vec()))
+    .collect::<BTreeMap<String, Vec<u8>>>();
+    let time = date_time_from_epoch(1_700_000_000).unwrap();
+
+    write_jar(&directory.join("a.jar"), &manifest, &entries, time).unwrap();
+    write_jar(&directory.join("b.jar"), &manifest, &entries, time).unwrap();
+    let jar = std::fs::read(directory.join("a.jar")).unwrap();
+    assert_eq!(jar, std::fs::read(directory.join("b.jar")).unwrap());
+
+    let mut archive = ZipArchive::new(std::io::Cursor::new(jar)).unwrap();
+    assert_eq!(
+        vec![
+            MANIFEST_PATH,
+            "META-INF/",
+            "app.properties",
+            "com/",
+            "com/example/",
+            "com/example/App.class"
+        ],
+        (0..archive.len())
+            .map(|i| archive.by_index(i).unwrap().name().to_string())
+            .collect::<Vec<String>>()
+    );
+    assert_eq!(
+        (2023, 11, 14, 22, 13, 20),
+        date(archive.by_index(2).unwrap().last_modified())
+    );
+}
@@ -1,3 +1,48 @@
 mod arg_parsing;
 mod tools;
 mod config_tests;
+mod incremental;
+mod templates;
+mod new_project;
+mod resources;
+mod fatjar;
+mod import;
+mod jar;
+mod publish;
+mod task_graph;
+mod task_state;
+mod watch;
+
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+/// A directory created for a test in the temporary directory, removed with its
+/// contents when dropped, even if the test fails.
+pub struct TemporaryDirectory(PathBuf);
+
+impl TemporaryDirectory {
+    /// Creates the empty directory `jabu-<name>-<pid>`, replacing the one left
+    /// by a previous run if any.
+    pub fn new(name: &str) -> Self {
+        let directory = std::env::temp_dir().join(format!("jabu-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        TemporaryDirectory(directory)
+    }
+}
+
+impl Deref for TemporaryDirectory {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TemporaryDirectory {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
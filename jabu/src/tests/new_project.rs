@@ -0,0 +1,41 @@
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{impls::NewProjectTask, Task},
+    tests::TemporaryDirectory,
+};
+use jabu_config::{
+    fsutils::project_from_directory,
+    model::{JabuProject, ProjectType},
+};
+use std::path::Path;
+
+fn create(directory: &Path, args: &[&str]) {
+    let task = NewProjectTask;
+    let args = args.iter().map(|arg| arg.to_string()).collect();
+    let parsed_args = ParsedArguments::new_with_options(args, &task.options().unwrap()).unwrap();
+    task.create_in(directory, &parsed_args).unwrap();
+}
+
+#[test]
+fn applications_are_created_by_default() {
+    let directory = TemporaryDirectory::new("new-default");
+    create(&directory, &["--name:demo"]);
+
+    let project = project_from_directory(Some(directory.join("demo"))).unwrap();
+    let expected = JabuProject::default_of_name("demo", ProjectType::Application);
+    assert_eq!(expected.default_tasks, project.default_tasks);
+    assert_eq!("App", project.manifest.get("Main-Class").unwrap());
+    assert_eq!("anon", project.header.author);
+    assert!(directory.join("demo/src/main/App.java").is_file());
+}
+
+#[test]
+fn libraries_are_created_with_the_project_type() {
+    let directory = TemporaryDirectory::new("new-library");
+    create(&directory, &["--name:demo", "--project-type:library"]);
+
+    let project = project_from_directory(Some(directory.join("demo"))).unwrap();
+    let expected = JabuProject::default_of_name("demo", ProjectType::Library);
+    assert_eq!(expected.default_tasks, project.default_tasks);
+    assert!(project.manifest.get("Main-Class").is_none());
+}
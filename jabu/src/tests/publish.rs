@@ -0,0 +1,21 @@
+use crate::{tasks::impls::artifact_parts, tests::TemporaryDirectory};
+use jabu_config::model::{JabuProject, ProjectType};
+
+#[test]
+fn published_jar_with_custom_name() {
+    let directory = TemporaryDirectory::new("publish");
+    let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+    project.fs_schema.jar_name = Some("custom".to_string());
+
+    // The jar hasn't been created.
+    assert!(artifact_parts(&directory, &project).is_err());
+
+    let target_bin = directory.join(project.fs_schema.target_bin());
+    std::fs::create_dir_all(&target_bin).unwrap();
+    std::fs::write(target_bin.join("custom.jar"), "jar").unwrap();
+    let (jar, jaburon) = artifact_parts(&directory, &project).unwrap();
+    assert_eq!(b"jar".to_vec(), jar);
+    let published = ron::from_str::<JabuProject>(&jaburon).unwrap();
+    assert_eq!(project.header, published.header);
+    assert_eq!(project.fs_schema.jar_name, published.fs_schema.jar_name);
+}
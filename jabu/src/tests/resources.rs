@@ -0,0 +1,61 @@
+use crate::{
+    tasks::impls::{process_resources, replace_placeholders, ResourcesReport},
+    tests::TemporaryDirectory,
+};
+use jabu_config::model::{ConfigHeader, JabuProject, ProjectType};
+
+fn project_directory() -> TemporaryDirectory {
+    let directory = TemporaryDirectory::new("resources");
+    std::fs::create_dir_all(directory.join("src/resources/config")).unwrap();
+    directory
+}
+
+#[test]
+fn placeholders() {
+    let mut header = ConfigHeader::of_proj_name("app");
+    header.version = "1.2.3".to_string();
+
+    assert_eq!(
+        "app 1.2.3 ${project.unknown}",
+        replace_placeholders("${project.name} ${project.version} ${project.unknown}", &header)
+    );
+}
+
+#[test]
+fn resources_are_copied_incrementally() {
+    let directory = project_directory();
+    let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+    project.fs_schema.filtered_resources = vec!["**/*.properties".to_string()];
+    let resources = directory.join(&project.fs_schema.resources);
+    let classes = directory.join(project.fs_schema.target_classes());
+    std::fs::write(resources.join("logo.txt"), "${project.version}").unwrap();
+    std::fs::write(resources.join("config/app.properties"), "version=${project.version}").unwrap();
+
+    assert_eq!(
+        ResourcesReport { copied: 2, removed: 0, up_to_date: 0 },
+        process_resources(&directory, &project).unwrap()
+    );
+    assert_eq!("${project.version}", std::fs::read_to_string(classes.join("logo.txt")).unwrap());
+    assert_eq!(
+        "version=0.0.1",
+        std::fs::read_to_string(classes.join("config/app.properties")).unwrap()
+    );
+
+    // Changing the version only affects the filtered resources.
+    project.header.version = "0.0.2".to_string();
+    assert_eq!(
+        ResourcesReport { copied: 1, removed: 0, up_to_date: 1 },
+        process_resources(&directory, &project).unwrap()
+    );
+    assert_eq!(
+        "version=0.0.2",
+        std::fs::read_to_string(classes.join("config/app.properties")).unwrap()
+    );
+
+    std::fs::remove_file(resources.join("logo.txt")).unwrap();
+    assert_eq!(
+        ResourcesReport { copied: 0, removed: 1, up_to_date: 1 },
+        process_resources(&directory, &project).unwrap()
+    );
+    assert!(!classes.join("logo.txt").exists());
+}
@@ -0,0 +1,350 @@
+use crate::{
+    args::parser::ParsedArguments,
+    tasks::{
+        GeneralTaskManager, JabuTask, JabuTaskDependencySpec, JabuTaskManager, PlannedTask,
+        TaskError, TaskManager, TaskResult,
+    },
+    tools::JavaHome,
+};
+use jabu_config::model::{CustomTask, CustomTaskAction, CustomTaskOption, JabuProject, ProjectType};
+use std::collections::BTreeMap;
+
+/// Task that does nothing, depending on the given tasks.
+struct FakeTask(Vec<(&'static str, Vec<&'static str>)>);
+
+impl JabuTask for FakeTask {
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    fn execute(
+        &self,
+        _: Vec<String>,
+        _: Option<ParsedArguments>,
+        _: &JabuProject,
+        _: &JavaHome,
+    ) -> TaskResult {
+        Ok(())
+    }
+
+    fn get_dependency_task_specs(&self) -> JabuTaskDependencySpec {
+        JabuTaskDependencySpec::new(
+            self.0
+                .iter()
+                .map(|(name, args)| {
+                    (
+                        name.to_string(),
+                        args.iter().map(|arg| arg.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+fn task_manager(tasks: Vec<(&str, FakeTask)>) -> JabuTaskManager {
+    let mut task_manager = JabuTaskManager::default();
+    for (name, task) in tasks {
+        task_manager.register_task(name, Box::new(task));
+    }
+    task_manager
+}
+
+fn planned(name: &str, args: &[&str]) -> PlannedTask {
+    PlannedTask {
+        name: name.to_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+    }
+}
+
+#[test]
+fn shared_dependencies_are_planned_once() {
+    let task_manager = task_manager(vec![
+        ("a", FakeTask(vec![("b", vec![]), ("c", vec![])])),
+        ("b", FakeTask(vec![("d", vec![])])),
+        ("c", FakeTask(vec![("d", vec![])])),
+        ("d", FakeTask(vec![])),
+    ]);
+
+    assert_eq!(
+        vec![
+            planned("d", &[]),
+            planned("b", &[]),
+            planned("c", &[]),
+            planned("a", &["--verbose"])
+        ],
+        task_manager
+            .plan_all(&[planned("a", &["--verbose"])])
+            .unwrap()
+            .tasks
+    );
+}
+
+#[test]
+fn dependencies_get_their_declared_args() {
+    let task_manager = task_manager(vec![
+        ("a", FakeTask(vec![("b", vec!["--x:1"]), ("c", vec![])])),
+        ("b", FakeTask(vec![])),
+        ("c", FakeTask(vec![("b", vec!["--x:2"])])),
+    ]);
+
+    assert_eq!(
+        vec![
+            planned("b", &["--x:1"]),
+            planned("b", &["--x:2"]),
+            planned("c", &[]),
+            planned("a", &[])
+        ],
+        task_manager.plan_all(&[planned("a", &[])]).unwrap().tasks
+    );
+}
+
+#[test]
+fn cycles_and_missing_tasks() {
+    let task_manager = task_manager(vec![
+        ("a", FakeTask(vec![("b", vec![])])),
+        ("b", FakeTask(vec![("c", vec![])])),
+        ("c", FakeTask(vec![("a", vec![])])),
+        ("d", FakeTask(vec![("missing", vec![])])),
+    ]);
+
+    assert!(matches!(
+        task_manager.plan_all(&[planned("b", &[])]),
+        Err(TaskError::DependencyCycle(cycle)) if cycle == ["b", "c", "a", "b"]
+    ));
+    assert!(matches!(
+        task_manager.plan_all(&[planned("d", &[])]),
+        Err(TaskError::DependencyTaskDoesntExist(task)) if task == "missing"
+    ));
+    assert!(matches!(
+        task_manager.plan_all(&[planned("missing", &[])]),
+        Err(TaskError::NoSuchTask(_))
+    ));
+}
+
+#[test]
+fn default_tasks_plan() {
+    let task_manager = JabuTaskManager::top_level_default();
+
+    assert_eq!(
+        vec![
+            planned("build", &[]),
+            planned("resources", &[]),
+            planned("jar", &[]),
+            planned("publish", &["--with-pom"])
+        ],
+        task_manager
+            .plan_all(&[planned("publish", &["--with-pom"])])
+            .unwrap()
+            .tasks
+    );
+}
+
+#[test]
+fn several_tasks_share_their_dependencies() {
+    let task_manager = JabuTaskManager::top_level_default();
+
+    assert_eq!(
+        vec![
+            planned("clean", &[]),
+            planned("build", &[]),
+            planned("resources", &[]),
+            planned("jar", &[]),
+            planned("test", &["--filter:AppTest"])
+        ],
+        task_manager
+            .plan_all(&[
+                planned("clean", &[]),
+                planned("build", &[]),
+                planned("jar", &[]),
+                planned("test", &["--filter:AppTest"])
+            ])
+            .unwrap()
+            .tasks
+    );
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn aliases(aliases: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+    aliases
+        .iter()
+        .map(|(name, tasks)| (name.to_string(), args(tasks)))
+        .collect()
+}
+
+#[test]
+fn command_line_is_split_into_tasks() {
+    let task_manager = GeneralTaskManager::new(
+        JabuTaskManager::top_level_default(),
+        TaskManager::top_level_default(),
+    );
+    let aliases = aliases(&[("ci", &["clean", "jar"])]);
+
+    assert_eq!(
+        vec![
+            planned("clean", &[]),
+            planned("test", &["--filter:AppTest"]),
+            planned("ci", &["--dry-run"]),
+            planned("run", &["--", "jar", "--verbose"])
+        ],
+        task_manager.split_invocations(
+            args(&[
+                "clean",
+                "test",
+                "--filter:AppTest",
+                "ci",
+                "--dry-run",
+                "run",
+                "--",
+                "jar",
+                "--verbose"
+            ]),
+            &aliases
+        )
+    );
+    // The subtask of a group isn't taken as another task.
+    assert_eq!(
+        vec![
+            planned("build", &[]),
+            planned("templates", &["publish", "--name:app"])
+        ],
+        task_manager.split_invocations(
+            args(&["build", "templates", "publish", "--name:app"]),
+            &aliases
+        )
+    );
+}
+
+#[test]
+fn aliases_are_expanded() {
+    let task_manager = GeneralTaskManager::new(
+        JabuTaskManager::top_level_default(),
+        TaskManager::top_level_default(),
+    );
+    let aliases = aliases(&[
+        ("check", &["build", "test --filter:Fast*"]),
+        ("ci", &["clean", "check", "jar"]),
+        ("build", &["clean"]),
+        ("a", &["b"]),
+        ("b", &["a"]),
+    ]);
+
+    assert_eq!(
+        vec![
+            planned("clean", &[]),
+            planned("build", &[]),
+            planned("test", &["--filter:Fast*"]),
+            planned("jar", &["--dry-run"]),
+            planned("build", &[])
+        ],
+        task_manager
+            .expand_aliases(
+                vec![planned("ci", &["--dry-run"]), planned("build", &[])],
+                &aliases
+            )
+            .unwrap()
+    );
+    assert!(matches!(
+        task_manager.expand_aliases(vec![planned("a", &[])], &aliases),
+        Err(TaskError::DependencyCycle(cycle)) if cycle == ["a", "b", "a"]
+    ));
+}
+
+fn custom_task(depends_on: &[&str], before: &[&str], after: &[&str]) -> CustomTask {
+    CustomTask {
+        description: String::new(),
+        run: CustomTaskAction::Command("true".to_string()),
+        options: Vec::new(),
+        depends_on: args(depends_on),
+        before: args(before),
+        after: args(after),
+        inputs: Vec::new(),
+        outputs: Vec::new(),
+    }
+}
+
+#[test]
+fn custom_tasks_and_hooks() {
+    let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+    project
+        .custom_tasks
+        .insert("generate".to_string(), custom_task(&[], &["build"], &[]));
+    project.custom_tasks.insert(
+        "stamp".to_string(),
+        custom_task(&["generate"], &[], &["jar"]),
+    );
+    let mut task_manager = JabuTaskManager::top_level_default();
+    task_manager.register_custom_tasks(&project).unwrap();
+
+    assert_eq!(
+        vec![
+            planned("generate", &[]),
+            planned("build", &[]),
+            planned("resources", &[]),
+            planned("jar", &[]),
+            planned("stamp", &[])
+        ],
+        task_manager.plan_all(&[planned("jar", &[])]).unwrap().tasks
+    );
+
+    // The invalid custom tasks are skipped, but the rest of them are registered.
+    project
+        .custom_tasks
+        .insert("build".to_string(), custom_task(&[], &[], &[]));
+    let mut task_manager = JabuTaskManager::top_level_default();
+    assert!(matches!(
+        task_manager.register_custom_tasks(&project),
+        Err(TaskError::InvalidConfig(_))
+    ));
+    assert!(task_manager.contains_task_with_name("stamp"));
+    assert_eq!(
+        vec![planned("generate", &[]), planned("build", &[])],
+        task_manager.plan_all(&[planned("build", &[])]).unwrap().tasks
+    );
+
+    project.custom_tasks.remove("build");
+    project
+        .custom_tasks
+        .insert("lint".to_string(), custom_task(&[], &["missing"], &[]));
+    let mut task_manager = JabuTaskManager::top_level_default();
+    assert!(matches!(
+        task_manager.register_custom_tasks(&project),
+        Err(TaskError::InvalidConfig(_))
+    ));
+    assert!(task_manager.contains_task_with_name("lint"));
+    assert!(!task_manager.before_hooks.contains_key("missing"));
+}
+
+#[test]
+fn custom_task_options() {
+    let option = |name: &str| CustomTaskOption {
+        name: name.to_string(),
+        description: None,
+        has_arg: false,
+        required: false,
+        default_value: None,
+    };
+    let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+    let mut lint = custom_task(&[], &[], &[]);
+    lint.options = vec![option("verbose"), option("version")];
+    project.custom_tasks.insert("lint".to_string(), lint.clone());
+    let mut task_manager = JabuTaskManager::top_level_default();
+    task_manager.register_custom_tasks(&project).unwrap();
+
+    // Options starting with the same letter don't replace each other.
+    let options = task_manager.get_task("lint").unwrap().options().unwrap();
+    assert!(options.has_option_with_name("verbose"));
+    assert!(options.has_option_with_name("version"));
+
+    lint.options.push(option("verbose"));
+    project.custom_tasks.insert("lint".to_string(), lint);
+    let mut task_manager = JabuTaskManager::top_level_default();
+    assert!(matches!(
+        task_manager.register_custom_tasks(&project),
+        Err(TaskError::InvalidConfig(_))
+    ));
+    assert!(!task_manager.contains_task_with_name("lint"));
+}
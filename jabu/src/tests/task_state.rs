@@ -0,0 +1,93 @@
+use crate::{
+    tasks::{inputs_fingerprint, project_settings, TaskStates},
+    tests::TemporaryDirectory,
+};
+use jabu_config::model::{JabuProject, JarManifest, Profile, ProjectType};
+use std::{collections::HashMap, path::Path};
+
+fn directory(name: &str) -> TemporaryDirectory {
+    let directory = TemporaryDirectory::new(name);
+    std::fs::create_dir_all(directory.join("src/com")).unwrap();
+    std::fs::create_dir_all(directory.join("out")).unwrap();
+    directory
+}
+
+#[test]
+fn inputs_fingerprints() {
+    let directory = directory("inputs-fingerprints");
+    let inputs = [directory.join("src")];
+    std::fs::write(directory.join("src/com/A.java"), "class A {}").unwrap();
+
+    let fingerprint = inputs_fingerprint("settings", &inputs).unwrap();
+    assert_eq!(
+        fingerprint,
+        inputs_fingerprint("settings", &inputs).unwrap()
+    );
+    assert_ne!(
+        fingerprint,
+        inputs_fingerprint("other settings", &inputs).unwrap()
+    );
+
+    std::fs::write(directory.join("src/com/A.java"), "class A { }").unwrap();
+    let modified = inputs_fingerprint("settings", &inputs).unwrap();
+    assert_ne!(fingerprint, modified);
+
+    std::fs::write(directory.join("src/com/B.java"), "class B {}").unwrap();
+    assert_ne!(modified, inputs_fingerprint("settings", &inputs).unwrap());
+}
+
+#[test]
+fn up_to_date_tasks() {
+    let directory = directory("up-to-date-tasks");
+    let outputs = [directory.join("out")];
+    std::fs::write(directory.join("out/A.class"), "a").unwrap();
+
+    let mut states = TaskStates::default();
+    assert!(!states.is_up_to_date("build", "inputs"));
+    states
+        .record("build", "inputs".to_string(), &outputs)
+        .unwrap();
+    assert!(states.is_up_to_date("build", "inputs"));
+    assert!(!states.is_up_to_date("build", "other inputs"));
+    assert!(!states.is_up_to_date("build --verbose", "inputs"));
+
+    // The files written by other tasks are ignored.
+    std::fs::write(directory.join("out/app.properties"), "key=value").unwrap();
+    assert!(states.is_up_to_date("build", "inputs"));
+
+    std::fs::write(directory.join("out/A.class"), "modified").unwrap();
+    assert!(!states.is_up_to_date("build", "inputs"));
+    std::fs::remove_file(directory.join("out/A.class")).unwrap();
+    assert!(!states.is_up_to_date("build", "inputs"));
+
+    let path = directory.join("target/.jabu/task-states.ron");
+    states.save(&path).unwrap();
+    assert_eq!(Some(states), TaskStates::load(&path));
+}
+
+#[test]
+fn project_settings_are_stable() {
+    // Every map is created separately, so they iterate in different orders.
+    let project = || {
+        let entries = [("Main-Class", "App"), ("Built-By", "me"), ("Sealed", "true"), ("X-Version", "1")];
+        let manifest = entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<String, String>>();
+        let mut project = JabuProject::default_of_name("app", ProjectType::Application);
+        project.manifest = JarManifest::from(manifest.clone());
+        project.profiles.insert(
+            "release".to_string(),
+            Profile {
+                manifest,
+                ..Profile::default()
+            },
+        );
+        project
+    };
+
+    assert_eq!(
+        project_settings(&project(), Path::new("/jdk")),
+        project_settings(&project(), Path::new("/jdk"))
+    );
+}
@@ -0,0 +1,88 @@
+use crate::{
+    tasks::impls::templates::{Template, TemplateVariables},
+    tests::TemporaryDirectory,
+};
+use jabu_config::{
+    fsutils::project_from_directory,
+    model::{JabuProject, ProjectType, JABU_FILE_NAME},
+};
+
+fn variables() -> TemplateVariables {
+    TemplateVariables {
+        project_name: "my-app".to_string(),
+        author: "me".to_string(),
+        package: "com.example".to_string(),
+    }
+}
+
+#[test]
+fn render_placeholders() {
+    assert_eq!(
+        "package com.example; // my-app by me",
+        variables().render("package {{package}}; // {{project_name}} by {{author}}")
+    );
+    assert_eq!(
+        "src/main/com/example/App.java",
+        variables().render_path("src/main/{{package}}/App.java")
+    );
+}
+
+#[test]
+fn project_from_template_directory() {
+    let directory = TemporaryDirectory::new("template");
+    let template_directory = directory.join("template");
+    let mut skeleton = JabuProject::default_of_name("{{project_name}}", ProjectType::Application);
+    skeleton.header.author = "{{author}}".to_string();
+    std::fs::create_dir_all(template_directory.join("src/main/{{package}}")).unwrap();
+    std::fs::write(
+        template_directory.join(JABU_FILE_NAME),
+        ron::to_string(&skeleton).unwrap(),
+    )
+    .unwrap();
+    std::fs::write(
+        template_directory.join("src/main/{{package}}/App.java"),
+        "package {{package}};",
+    )
+    .unwrap();
+
+    let project_directory = directory.join("my-app");
+    std::fs::create_dir(&project_directory).unwrap();
+    Template::Directory(template_directory)
+        .create_project(&project_directory, &variables())
+        .unwrap();
+
+    let project = project_from_directory(Some(project_directory.clone())).unwrap();
+    assert_eq!("my-app", project.header.project_name);
+    assert_eq!("me", project.header.author);
+    assert_eq!(
+        "package com.example;",
+        std::fs::read_to_string(project_directory.join("src/main/com/example/App.java")).unwrap()
+    );
+    assert!(project_directory.join(&project.fs_schema.lib).is_dir());
+}
+
+#[test]
+fn template_without_jabu_file() {
+    let directory = TemporaryDirectory::new("empty-template");
+    let project_directory = directory.join("my-app");
+    std::fs::create_dir(&project_directory).unwrap();
+
+    assert!(Template::Directory(directory.to_path_buf())
+        .create_project(&project_directory, &variables())
+        .is_err());
+}
+
+#[test]
+fn find_templates() {
+    let directory = TemporaryDirectory::new("find-template");
+
+    assert_eq!(
+        Template::Directory(directory.to_path_buf()),
+        Template::find(&directory.to_string_lossy()).unwrap()
+    );
+    assert!(matches!(
+        Template::find("me_webapp_latest"),
+        Ok(Template::Remote(_))
+    ));
+    assert!(Template::find("no-such-template").is_err());
+}
@@ -23,6 +23,7 @@ mod javac {
             target: 17,
             source: 17,
             java_version: 17,
+            compiler_args: Vec::new(),
         };
         let output_dir = "./target".to_string();
         let javacc_config = JavacConfig::new(sources, Some(output_dir), Some(java_config));
@@ -157,43 +158,6 @@ mod java {
     }
 }
 
-mod jar {
-    use crate::tools::JarToolConfig;
-    use std::path::PathBuf;
-
-    #[test]
-    fn simple_compression() {
-        let expected = vec![
-            "--create".to_string(),
-            "--file".to_string(),
-            "./target.jar".to_string(),
-            "-C".to_string(),
-            "target/classes".to_string(),
-            ".".to_string(),
-        ];
-        let config = JarToolConfig::new("./target.jar".to_string(), "target/classes".to_string());
-        assert_eq!(expected, config.into_args())
-    }
-
-    #[test]
-    fn compression_with_manifest() {
-        let expected = vec![
-            "--create".to_string(),
-            "--file".to_string(),
-            "./target.jar".to_string(),
-            "--manifest".to_string(),
-            "./target/bin/MANIFEST.MF".to_string(),
-            "-C".to_string(),
-            "target/classes".to_string(),
-            ".".to_string(),
-        ];
-        let mut config =
-            JarToolConfig::new("./target.jar".to_string(), "target/classes".to_string());
-        config.manifest_location = Some(PathBuf::from("./target/bin/MANIFEST.MF".to_string()));
-        assert_eq!(expected, config.into_args())
-    }
-}
-
 mod javadoc {
     use crate::tools::{JavaVisibilityLevel, JavadocToolConfig};
     use jabu_config::model::JavaConfig;
@@ -294,3 +258,154 @@ mod jpackage {
         assert_eq!(expected, input.try_into_args().unwrap())
     }
 }
+
+mod junit {
+    use crate::tools::{JUnitLauncherConfig, TestSelector};
+
+    fn to_strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn scan_without_filter() {
+        let mut config = JUnitLauncherConfig::new(
+            to_strings(&["target/test-classes", "target/classes"]),
+            to_strings(&["target/test-classes"]),
+        );
+        config.reports_dir = Some("target/test-reports".to_string());
+
+        let expected = to_strings(&[
+            "--class-path",
+            "target/test-classes:target/classes",
+            "--scan-class-path",
+            "target/test-classes",
+            "--reports-dir",
+            "target/test-reports",
+        ]);
+        assert_eq!(expected, config.into_args());
+    }
+
+    #[test]
+    fn explicit_selectors() {
+        let mut config = JUnitLauncherConfig::new(
+            to_strings(&["target/test-classes"]),
+            to_strings(&["target/test-classes"]),
+        );
+        config.selectors = TestSelector::parse_list("com.example.AppTest, com.example.UtilsTest#sum,");
+
+        let expected = to_strings(&[
+            "--class-path",
+            "target/test-classes",
+            "--select-class",
+            "com.example.AppTest",
+            "--select-method",
+            "com.example.UtilsTest#sum",
+        ]);
+        assert_eq!(expected, config.into_args());
+    }
+
+    #[test]
+    fn class_name_patterns() {
+        let mut config = JUnitLauncherConfig::new(Vec::new(), to_strings(&["target/test-classes"]));
+        config.selectors = TestSelector::parse_list("com.example.*Test");
+
+        let expected = to_strings(&[
+            "--scan-class-path",
+            "target/test-classes",
+            "--include-classname",
+            "com\\.example\\..*Test",
+        ]);
+        assert_eq!(expected, config.into_args());
+    }
+}
+
+mod toolchains {
+    use crate::{
+        tests::TemporaryDirectory,
+        tools::{find_jdks, major_version, select_jdk, Jdk},
+    };
+    use std::path::{Path, PathBuf};
+
+    /// Creates a fake JDK (*a `release` file and an empty `java` binary*) in `home`.
+    fn create_jdk(home: &Path, version: &str) {
+        std::fs::create_dir_all(home.join("bin")).unwrap();
+        std::fs::write(home.join("bin/java"), "").unwrap();
+        std::fs::write(
+            home.join("release"),
+            format!("IMPLEMENTOR=\"Test\"\nJAVA_VERSION=\"{version}\"\n"),
+        )
+        .unwrap();
+    }
+
+    fn jdk(home: &str, version: &str) -> Jdk {
+        Jdk {
+            home: PathBuf::from(home),
+            version: version.to_string(),
+            major_version: major_version(version).unwrap(),
+        }
+    }
+
+    #[test]
+    fn major_versions() {
+        assert_eq!(Some(17), major_version("17.0.8"));
+        assert_eq!(Some(8), major_version("1.8.0_382"));
+        assert_eq!(Some(21), major_version("21-ea"));
+        assert_eq!(None, major_version("unknown"));
+    }
+
+    #[test]
+    fn discovered_jdks() {
+        let directory = TemporaryDirectory::new("jdks");
+        let installations = directory.join("jvm");
+        create_jdk(&installations.join("jdk-17"), "17.0.8");
+        create_jdk(&installations.join("jdk-8/Contents/Home"), "1.8.0_382");
+        create_jdk(&directory.join("custom"), "21.0.1");
+        // Not a JDK, since it has no release file.
+        std::fs::create_dir_all(installations.join("broken/bin")).unwrap();
+
+        let jdks = find_jdks(
+            vec![directory.join("custom"), installations.join("jdk-17")],
+            &[installations.clone(), directory.join("missing")],
+        );
+        assert_eq!(
+            vec![
+                Jdk {
+                    home: directory.join("custom"),
+                    version: "21.0.1".to_string(),
+                    major_version: 21,
+                },
+                Jdk {
+                    home: installations.join("jdk-17"),
+                    version: "17.0.8".to_string(),
+                    major_version: 17,
+                },
+                Jdk {
+                    home: installations.join("jdk-8/Contents/Home"),
+                    version: "1.8.0_382".to_string(),
+                    major_version: 8,
+                },
+            ],
+            jdks
+        );
+    }
+
+    #[test]
+    fn selected_jdk() {
+        let jdks = vec![
+            jdk("/jvm/jdk-21", "21.0.1"),
+            jdk("/jvm/jdk-17.0.2", "17.0.2"),
+            jdk("/jvm/jdk-17.0.10", "17.0.10"),
+            jdk("/jvm/jdk-8", "1.8.0_382"),
+        ];
+
+        // The oldest major version that is recent enough, in its newest release.
+        assert_eq!(Some(&jdks[2]), select_jdk(&jdks, 11, None));
+        assert_eq!(Some(&jdks[3]), select_jdk(&jdks, 8, None));
+        assert_eq!(Some(&jdks[0]), select_jdk(&jdks, 21, None));
+        assert_eq!(None, select_jdk(&jdks, 22, None));
+
+        // The preferred JDK is used if it's recent enough.
+        assert_eq!(Some(&jdks[0]), select_jdk(&jdks, 17, Some(Path::new("/jvm/jdk-21"))));
+        assert_eq!(Some(&jdks[2]), select_jdk(&jdks, 17, Some(Path::new("/jvm/jdk-8"))));
+    }
+}
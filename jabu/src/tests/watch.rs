@@ -0,0 +1,75 @@
+use crate::{
+    tasks::impls::{wait_for_changes, watched_paths},
+    tests::TemporaryDirectory,
+};
+use jabu_config::model::{JabuProject, ProjectType, JABU_FILE_NAME};
+use notify::{
+    event::{AccessKind, CreateKind, ModifyKind},
+    Event, EventKind,
+};
+use std::{path::PathBuf, sync::mpsc::channel, time::Duration};
+
+fn event(kind: EventKind, path: PathBuf) -> notify::Result<Event> {
+    Ok(Event::new(kind).add_path(path))
+}
+
+#[test]
+fn watched_paths_of_project() {
+    let directory = TemporaryDirectory::new("watch");
+    std::fs::create_dir_all(directory.join("src/main")).unwrap();
+    std::fs::create_dir_all(directory.join("lib")).unwrap();
+    std::fs::write(directory.join(JABU_FILE_NAME), "").unwrap();
+    let project = JabuProject::default_of_name("app", ProjectType::Application);
+
+    // The resources and tests don't exist.
+    let directory = directory.canonicalize().unwrap();
+    assert_eq!(
+        vec![
+            directory.join("src/main"),
+            directory.join("lib"),
+            directory.join(JABU_FILE_NAME),
+        ],
+        watched_paths(&directory, &project)
+    );
+}
+
+#[test]
+fn changes_are_debounced() {
+    let watched = [PathBuf::from("/app/src/main"), PathBuf::from("/app/jabu.ron")];
+    let (sender, receiver) = channel();
+
+    // Accesses and changes outside of the watched paths are ignored.
+    sender
+        .send(event(EventKind::Access(AccessKind::Any), "/app/src/main/A.java".into()))
+        .unwrap();
+    sender
+        .send(event(EventKind::Modify(ModifyKind::Any), "/app/target/A.class".into()))
+        .unwrap();
+    sender
+        .send(event(EventKind::Modify(ModifyKind::Any), "/app/src/main/B.java".into()))
+        .unwrap();
+    sender
+        .send(event(EventKind::Create(CreateKind::File), "/app/src/main/A.java".into()))
+        .unwrap();
+    sender
+        .send(event(EventKind::Modify(ModifyKind::Any), "/app/src/main/B.java".into()))
+        .unwrap();
+    sender
+        .send(event(EventKind::Modify(ModifyKind::Any), "/app/jabu.ron".into()))
+        .unwrap();
+
+    assert_eq!(
+        Some(vec![
+            PathBuf::from("/app/jabu.ron"),
+            PathBuf::from("/app/src/main/A.java"),
+            PathBuf::from("/app/src/main/B.java"),
+        ]),
+        wait_for_changes(&receiver, &watched, Duration::from_millis(50))
+    );
+
+    drop(sender);
+    assert_eq!(
+        None,
+        wait_for_changes(&receiver, &watched, Duration::from_millis(50))
+    );
+}
@@ -20,6 +20,7 @@ impl Into<Vec<String>> for JavacConfig {
             result_args.push(java_config.source.to_string());
             result_args.push("--target".to_string());
             result_args.push(java_config.target.to_string());
+            result_args.extend(java_config.compiler_args);
         }
 
         if let Some(output_dir) = self.output_dir {
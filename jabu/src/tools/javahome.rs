@@ -1,3 +1,7 @@
+use crate::{
+    tasks::TaskError,
+    tools::{discover_jdks, select_jdk},
+};
 use std::{collections::HashMap, path::PathBuf};
 use prettytable::{Row, Attr, Cell, color};
 
@@ -96,6 +100,24 @@ impl JavaHome {
         Self::try_from(java_home)
     }
 
+    /// Returns the java home of the installed JDK that is used for a project whose
+    /// minimum java version is `java_version` (*see [`crate::tools::select_jdk`]*).
+    pub fn for_java_version(java_version: u8) -> Result<Self, TaskError> {
+        let jdks = discover_jdks();
+        let preferred = std::env::var_os("JAVA_HOME").map(PathBuf::from);
+
+        match select_jdk(&jdks, java_version, preferred.as_deref()) {
+            Some(jdk) => Ok(Self::try_from(jdk.home.clone())?),
+            None => Err(TaskError::UnsatisfiedJavaVersion {
+                java_version,
+                found: jdks
+                    .iter()
+                    .map(|jdk| format!("{} ({})", jdk.version, jdk.home.to_string_lossy()))
+                    .collect(),
+            }),
+        }
+    }
+
     /// Path to the 'java' tool.
     pub fn get_java(&self) -> &Option<PathBuf> {
         &self.java
@@ -0,0 +1,120 @@
+/// Represents a selection of the tests to be executed by the
+/// JUnit Platform console launcher.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TestSelector {
+    /// A fully qualified class name (*i.e. `com.example.AppTest`*).
+    Class(String),
+
+    /// A fully qualified method name (*i.e. `com.example.AppTest#sum`*).
+    Method(String),
+
+    /// A pattern matching class names, where `*` matches any sequence
+    /// of characters (*i.e. `com.example.*Test`*).
+    ClassPattern(String),
+}
+
+impl From<&str> for TestSelector {
+    fn from(value: &str) -> Self {
+        if value.contains('#') {
+            Self::Method(value.to_string())
+        } else if value.contains('*') {
+            Self::ClassPattern(value.to_string())
+        } else {
+            Self::Class(value.to_string())
+        }
+    }
+}
+
+impl TestSelector {
+    /// Parses a comma separated list of selectors (*i.e. `AppTest,UtilsTest#sum`*),
+    /// ignoring empty entries.
+    pub fn parse_list(value: &str) -> Vec<Self> {
+        value
+            .split(',')
+            .map(|selector| selector.trim())
+            .filter(|selector| !selector.is_empty())
+            .map(Self::from)
+            .collect()
+    }
+}
+
+/// API for generating the arguments of the JUnit Platform console launcher
+/// (*`junit-platform-console-standalone`*).
+#[derive(Debug, PartialEq)]
+pub struct JUnitLauncherConfig {
+    /// Classpath containing the tests and everything required to run them.
+    pub classpath: Vec<String>,
+
+    /// Directories or jars in which to look for tests when there are no
+    /// explicit class or method selectors.
+    pub scan_roots: Vec<String>,
+
+    /// Tests to execute, if empty, every test found in the scan roots is executed.
+    pub selectors: Vec<TestSelector>,
+
+    /// Directory where to write the XML reports, if any.
+    pub reports_dir: Option<String>,
+}
+
+impl From<JUnitLauncherConfig> for Vec<String> {
+    fn from(config: JUnitLauncherConfig) -> Self {
+        let mut args = Vec::new();
+        let delimiter = if cfg!(windows) { ";" } else { ":" };
+
+        if !config.classpath.is_empty() {
+            args.push("--class-path".to_string());
+            args.push(config.classpath.join(delimiter));
+        }
+
+        let has_explicit_selectors = config
+            .selectors
+            .iter()
+            .any(|selector| !matches!(selector, TestSelector::ClassPattern(_)));
+
+        if !has_explicit_selectors {
+            args.push("--scan-class-path".to_string());
+            if !config.scan_roots.is_empty() {
+                args.push(config.scan_roots.join(delimiter));
+            }
+        }
+
+        config.selectors.into_iter().for_each(|selector| match selector {
+            TestSelector::Class(class) => {
+                args.push("--select-class".to_string());
+                args.push(class);
+            }
+            TestSelector::Method(method) => {
+                args.push("--select-method".to_string());
+                args.push(method);
+            }
+            TestSelector::ClassPattern(pattern) => {
+                args.push("--include-classname".to_string());
+                args.push(pattern.replace('.', "\\.").replace('*', ".*"));
+            }
+        });
+
+        if let Some(reports_dir) = config.reports_dir {
+            args.push("--reports-dir".to_string());
+            args.push(reports_dir);
+        }
+
+        args
+    }
+}
+
+impl JUnitLauncherConfig {
+    pub fn new(classpath: Vec<String>, scan_roots: Vec<String>) -> Self {
+        Self {
+            classpath,
+            scan_roots,
+            selectors: Vec::new(),
+            reports_dir: None,
+        }
+    }
+
+    /// Generates the arguments for the console launcher,
+    /// consuming itself.
+    pub fn into_args(self) -> Vec<String> {
+        self.into()
+    }
+}
@@ -1,18 +1,20 @@
 mod java;
-mod jar;
 mod javac;
 mod javahome;
 mod javadoc;
 mod jpackage;
+mod junit;
+mod toolchains;
 
 use std::path::PathBuf;
 
 pub use java::*;
 pub use javadoc::*;
-pub use jar::*;
 pub use javac::*;
 pub use javahome::*;
 pub use jpackage::*;
+pub use junit::*;
+pub use toolchains::*;
 
 
 /// Returns the java home, if `$JAVA_HOME` is defined,
@@ -23,6 +25,8 @@ pub use jpackage::*;
 /// be returned wrapped in a `Some()` variant, if not, `None` will be 
 /// returned.
 pub fn get_java_home_path() -> Option<PathBuf> {
+    const JAVA_BINARY_NAME: &str = if cfg!(windows) { "java.exe" } else { "java" };
+
     match std::env::var("JAVA_HOME") {
         Ok(java_home) => {
             // If the $JAVA_HOME var exists, return it. 
@@ -39,22 +43,13 @@ pub fn get_java_home_path() -> Option<PathBuf> {
 
             let path = std::env::var("PATH").unwrap();
 
-            let java_home = path.split(sep).find(|p| {
-                let java_path = PathBuf::from(p).join("java");
-                match std::fs::metadata(&java_path) {
-                    Ok(j) => {
-                        j.is_file()
-                    }
-                    Err(_) => {
-                        false
-                    }
-                }
-            });
-
-            match java_home {
-                Some(path) => Some(PathBuf::from(path)),
-                None => None
-            }
+            // The java home is the parent of the 'bin' directory containing the
+            // binary, which is usually a symlink (*i.e. `/usr/bin/java`*).
+            path.split(sep)
+                .map(|p| PathBuf::from(p).join(JAVA_BINARY_NAME))
+                .find(|java_path| java_path.is_file())
+                .and_then(|java_path| java_path.canonicalize().ok())
+                .and_then(|java_path| Some(java_path.parent()?.parent()?.to_path_buf()))
         }
     }
 }
@@ -0,0 +1,176 @@
+use crate::utils::user_config_directory;
+use home::home_dir;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Name of the file (*inside of [`user_config_directory`]*) where the user registers
+/// the JDKs installed outside of the usual directories.
+pub const JDKS_CONFIG_FILE_NAME: &str = "jdks.ron";
+
+/// A JDK installed in the system.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Jdk {
+    /// Directory where the JDK is installed (*its java home*).
+    pub home: PathBuf,
+
+    /// Version of the JDK, as written in its `release` file (*i.e. `17.0.8`*).
+    pub version: String,
+
+    /// Major version of the JDK (*i.e. `17`, or `8` for `1.8.0_382`*).
+    pub major_version: u8,
+}
+
+impl Jdk {
+    /// Reads the JDK installed in `home`, returning `None` if it doesn't contain the
+    /// `java` tool, or a `release` file with its version.
+    pub fn from_home(home: &Path) -> Option<Self> {
+        let java = if cfg!(windows) { "java.exe" } else { "java" };
+        if !home.join("bin").join(java).is_file() {
+            return None;
+        }
+
+        let release = std::fs::read_to_string(home.join("release")).ok()?;
+        let version = release
+            .lines()
+            .find_map(|line| line.strip_prefix("JAVA_VERSION="))
+            .map(|version| version.trim().trim_matches('"').to_string())?;
+        Some(Self {
+            home: home.to_path_buf(),
+            major_version: major_version(&version)?,
+            version,
+        })
+    }
+}
+
+/// Returns the major version of a java version, taking into account the old
+/// versioning scheme (*i.e. `1.8.0_382` is `8`*).
+pub fn major_version(version: &str) -> Option<u8> {
+    let mut numbers = version.split(|c: char| !c.is_ascii_digit());
+    match numbers.next()?.parse().ok()? {
+        1 => numbers.next()?.parse().ok(),
+        major => Some(major),
+    }
+}
+
+/// JDKs registered by the user in [`JDKS_CONFIG_FILE_NAME`].
+#[derive(Debug, Default, Deserialize)]
+struct JdksConfig {
+    #[serde(default)]
+    jdks: Vec<PathBuf>,
+}
+
+/// Returns the directories where JDKs are usually installed, every subdirectory of
+/// them being a JDK: the system ones, and the ones of SDKMAN and asdf.
+pub fn jdk_installation_directories() -> Vec<PathBuf> {
+    let home = home_dir();
+    let tool_directory = |variable: &str, default: &str| {
+        std::env::var_os(variable)
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(default)))
+    };
+
+    let mut directories = vec![
+        PathBuf::from("/usr/lib/jvm"),
+        PathBuf::from("/Library/Java/JavaVirtualMachines"),
+    ];
+    directories
+        .extend(tool_directory("SDKMAN_DIR", ".sdkman").map(|dir| dir.join("candidates/java")));
+    directories
+        .extend(tool_directory("ASDF_DATA_DIR", ".asdf").map(|dir| dir.join("installs/java")));
+    directories
+}
+
+/// Returns the homes of the JDKs registered by the user in [`JDKS_CONFIG_FILE_NAME`]
+/// (*i.e. `(jdks: ["/opt/jdk-21"])`*).
+pub fn registered_jdk_homes() -> Vec<PathBuf> {
+    let Some(path) = user_config_directory().map(|config| config.join(JDKS_CONFIG_FILE_NAME))
+    else {
+        return Vec::new();
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    match ron::from_str::<JdksConfig>(&contents) {
+        Ok(config) => config.jdks,
+        Err(e) => {
+            eprintln!(
+                "Ignoring '{}', since it's invalid: {e}",
+                path.to_string_lossy()
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Finds the JDKs installed in the given homes, and in the subdirectories of the
+/// installation directories (*or their `Contents/Home`, as in macOS*). The JDKs
+/// found through more than one path are only returned once.
+pub fn find_jdks(homes: Vec<PathBuf>, installation_directories: &[PathBuf]) -> Vec<Jdk> {
+    let installed = installation_directories.iter().flat_map(|directory| {
+        let mut homes = std::fs::read_dir(directory)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .flat_map(|entry| [entry.path(), entry.path().join("Contents/Home")])
+            .collect::<Vec<PathBuf>>();
+        homes.sort();
+        homes
+    });
+
+    let mut jdks: Vec<Jdk> = Vec::new();
+    let mut canonical_homes = Vec::new();
+    for home in homes.into_iter().chain(installed) {
+        let Some(jdk) = Jdk::from_home(&home) else {
+            continue;
+        };
+        let canonical_home = home.canonicalize().unwrap_or(home);
+        if !canonical_homes.contains(&canonical_home) {
+            canonical_homes.push(canonical_home);
+            jdks.push(jdk);
+        }
+    }
+    jdks
+}
+
+/// Discovers every JDK installed in the system: the one of `$JAVA_HOME`, the one
+/// of the `java` found in the `PATH`, the ones registered by the user and the ones
+/// of the [`jdk_installation_directories`].
+pub fn discover_jdks() -> Vec<Jdk> {
+    let mut homes = Vec::new();
+    homes.extend(std::env::var_os("JAVA_HOME").map(PathBuf::from));
+    homes.extend(super::get_java_home_path());
+    homes.extend(registered_jdk_homes());
+    find_jdks(homes, &jdk_installation_directories())
+}
+
+/// Selects, among the given JDKs, the one used for a project whose minimum java
+/// version is `java_version`: the `preferred` one (*usually `$JAVA_HOME`*) if it's
+/// recent enough, or else the one with the lowest major version that is recent
+/// enough, taking the newest release of that major version.
+pub fn select_jdk<'a>(
+    jdks: &'a [Jdk],
+    java_version: u8,
+    preferred: Option<&Path>,
+) -> Option<&'a Jdk> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or(path.to_path_buf());
+    let suitable = jdks.iter().filter(|jdk| jdk.major_version >= java_version);
+
+    if let Some(preferred) = preferred.map(canonical) {
+        if let Some(jdk) = suitable
+            .clone()
+            .find(|jdk| canonical(&jdk.home) == preferred)
+        {
+            return Some(jdk);
+        }
+    }
+    let major_version = suitable.clone().map(|jdk| jdk.major_version).min()?;
+    suitable
+        .filter(|jdk| jdk.major_version == major_version)
+        .max_by_key(|jdk| {
+            jdk.version
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|number| number.parse::<u32>().ok())
+                .collect::<Vec<u32>>()
+        })
+}
@@ -0,0 +1,402 @@
+use std::{collections::BTreeSet, path::Path};
+
+const CLASS_FILE_MAGIC: u32 = 0xCAFEBABE;
+const ACC_PRIVATE: u16 = 0x0002;
+
+/// Information extracted from a compiled `.class` file, used for
+/// tracking the dependencies between the classes of a project.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ClassFileInfo {
+    /// Internal name of the class (*i.e. `com/example/App`*).
+    pub class_name: String,
+
+    /// Name of the source file that produced the class, as stored in
+    /// its `SourceFile` attribute (*i.e. `App.java`*), if present.
+    pub source_file: Option<String>,
+
+    /// Internal names of all the other classes referenced by this class,
+    /// either through its constant pool or through the descriptors of its
+    /// fields and methods.
+    pub referenced_classes: BTreeSet<String>,
+
+    /// `true` if the class declares a non-private field with a `ConstantValue`
+    /// attribute (*a `static final` constant*). The compiler inlines the value of
+    /// such fields in the classes using them, which therefore don't reference
+    /// this class at all.
+    pub declares_constants: bool,
+}
+
+impl ClassFileInfo {
+    /// Reads and parses the class file located at `path`.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::parse(&std::fs::read(path)?)
+    }
+
+    /// Parses the contents of a class file.
+    pub fn parse(bytes: &[u8]) -> std::io::Result<Self> {
+        let mut reader = ClassReader { bytes, pos: 0 };
+        let constant_pool = parse_constant_pool(&mut reader)?;
+
+        // access_flags
+        reader.u2()?;
+        let this_class = reader.u2()?;
+        let class_name = constant_pool
+            .class_name(this_class)
+            .ok_or_else(|| invalid_data("'this_class' doesn't point to a class constant"))?
+            .to_string();
+
+        // super_class + interfaces
+        reader.u2()?;
+        let interfaces_count = reader.u2()?;
+        reader.skip(interfaces_count as usize * 2)?;
+
+        let mut declares_constants = false;
+        let fields_count = reader.u2()?;
+        for _ in 0..fields_count {
+            let access_flags = reader.u2()?;
+            // name_index, descriptor_index
+            reader.skip(4)?;
+            let attributes_count = reader.u2()?;
+            for _ in 0..attributes_count {
+                let name_index = reader.u2()?;
+                let length = reader.u4()? as usize;
+                reader.skip(length)?;
+                if access_flags & ACC_PRIVATE == 0
+                    && constant_pool.utf8(name_index) == Some("ConstantValue")
+                {
+                    declares_constants = true;
+                }
+            }
+        }
+
+        let methods_count = reader.u2()?;
+        for _ in 0..methods_count {
+            // access_flags, name_index, descriptor_index
+            reader.skip(6)?;
+            skip_attributes(&mut reader)?;
+        }
+
+        let mut source_file = None;
+        let attributes_count = reader.u2()?;
+        for _ in 0..attributes_count {
+            let name_index = reader.u2()?;
+            let length = reader.u4()? as usize;
+            if constant_pool.utf8(name_index) == Some("SourceFile") && length == 2 {
+                source_file = constant_pool.utf8(reader.u2()?).map(|s| s.to_string());
+            } else {
+                reader.skip(length)?;
+            }
+        }
+
+        let mut referenced_classes = constant_pool.referenced_classes();
+        referenced_classes.remove(&class_name);
+
+        Ok(Self {
+            class_name,
+            source_file,
+            referenced_classes,
+            declares_constants,
+        })
+    }
+
+    /// Returns the internal name of the package of the class (*i.e. `com/example`*),
+    /// which is empty for classes in the default package.
+    pub fn package(&self) -> &str {
+        self.class_name
+            .rfind('/')
+            .map(|index| &self.class_name[..index])
+            .unwrap_or_default()
+    }
+}
+
+/// Entries of the constant pool relevant for the dependency analysis.
+enum Constant {
+    Utf8(String),
+    Class(u16),
+    Other,
+}
+
+struct ConstantPool {
+    entries: Vec<Constant>,
+}
+
+impl ConstantPool {
+    fn utf8(&self, index: u16) -> Option<&str> {
+        match self.entries.get(index as usize) {
+            Some(Constant::Utf8(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn class_name(&self, index: u16) -> Option<&str> {
+        match self.entries.get(index as usize) {
+            Some(Constant::Class(name_index)) => self.utf8(*name_index),
+            _ => None,
+        }
+    }
+
+    /// Collects the names of every class referenced in the pool, both from
+    /// `CONSTANT_Class` entries and from the `L...;` types found in descriptors.
+    fn referenced_classes(&self) -> BTreeSet<String> {
+        let mut classes = BTreeSet::new();
+
+        self.entries.iter().for_each(|entry| match entry {
+            Constant::Class(name_index) => {
+                if let Some(name) = self.utf8(*name_index) {
+                    if name.starts_with('[') {
+                        classes.extend(descriptor_classes(name));
+                    } else {
+                        classes.insert(name.to_string());
+                    }
+                }
+            }
+            Constant::Utf8(value) if value.starts_with('(') || value.starts_with('L') => {
+                classes.extend(descriptor_classes(value));
+            }
+            _ => (),
+        });
+
+        classes
+    }
+}
+
+/// Extracts the class names referenced in a field or method descriptor
+/// (*i.e. `(Ljava/lang/String;I)Lcom/example/App;`*).
+fn descriptor_classes(descriptor: &str) -> Vec<String> {
+    let mut classes = Vec::new();
+    let mut rest = descriptor;
+
+    while let Some(start) = rest.find('L') {
+        let after = &rest[start + 1..];
+        match after.find(';') {
+            Some(end) => {
+                let name = &after[..end];
+                if !name.is_empty() && !name.contains(['(', ')', '<', '>', '[', ' ']) {
+                    classes.push(name.to_string());
+                }
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    classes
+}
+
+fn parse_constant_pool(reader: &mut ClassReader) -> std::io::Result<ConstantPool> {
+    if reader.u4()? != CLASS_FILE_MAGIC {
+        return Err(invalid_data("not a class file (wrong magic number)"));
+    }
+    // minor_version + major_version
+    reader.skip(4)?;
+
+    let count = reader.u2()? as usize;
+    let mut entries = Vec::with_capacity(count);
+    // The constant pool is indexed from 1.
+    entries.push(Constant::Other);
+
+    while entries.len() < count {
+        let tag = reader.u1()?;
+        let constant = match tag {
+            1 => {
+                let length = reader.u2()? as usize;
+                Constant::Utf8(String::from_utf8_lossy(reader.take(length)?).to_string())
+            }
+            7 => Constant::Class(reader.u2()?),
+            3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => {
+                reader.skip(4)?;
+                Constant::Other
+            }
+            5 | 6 => {
+                // Longs and doubles take two entries of the pool.
+                reader.skip(8)?;
+                entries.push(Constant::Other);
+                Constant::Other
+            }
+            8 | 16 | 19 | 20 => {
+                reader.skip(2)?;
+                Constant::Other
+            }
+            15 => {
+                reader.skip(3)?;
+                Constant::Other
+            }
+            _ => return Err(invalid_data(format!("unknown constant pool tag {tag}"))),
+        };
+        entries.push(constant);
+    }
+
+    Ok(ConstantPool { entries })
+}
+
+/// Relocation of the classes of a package (*and its subpackages*) to another
+/// package, used for shading the dependencies bundled in a fat jar.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Relocation {
+    /// Internal name of the relocated package, with a trailing `/` (*i.e. `com/google/`*).
+    from: String,
+
+    /// Internal name of the destination package, with a trailing `/`.
+    to: String,
+}
+
+impl Relocation {
+    /// Creates a relocation from the package `from` to the package `to`, both
+    /// of them given with their binary names (*i.e. `com.google.common`*).
+    pub fn new(from: &str, to: &str) -> Self {
+        let internal = |package: &str| format!("{}/", package.trim_end_matches('.').replace('.', "/"));
+        Self {
+            from: internal(from),
+            to: internal(to),
+        }
+    }
+
+    /// Relocates an internal name (*i.e. `com/google/common/Lists`*) or a path
+    /// inside of a jar (*i.e. `com/google/common/Lists.class`*), returning `None`
+    /// if it isn't inside of the relocated package.
+    pub fn relocate_path(&self, path: &str) -> Option<String> {
+        path.strip_prefix(&self.from)
+            .map(|rest| format!("{}{rest}", self.to))
+    }
+
+    /// Relocates a binary class name (*i.e. `com.google.common.Lists`*), returning
+    /// `None` if it isn't inside of the relocated package.
+    pub fn relocate_binary_name(&self, name: &str) -> Option<String> {
+        self.relocate_path(&name.replace('.', "/"))
+            .map(|relocated| relocated.replace('/', "."))
+    }
+
+    /// Rewrites the occurrences of the package inside of a `CONSTANT_Utf8` of the
+    /// constant pool: class names (*at the start of the constant*), the `L...;`
+    /// types of descriptors and signatures, and binary names used by reflection.
+    fn relocate_constant(&self, value: &[u8]) -> Option<Vec<u8>> {
+        let from = self.from.as_bytes();
+        let from_binary = self.from.replace('/', ".");
+        let mut relocated = Vec::with_capacity(value.len());
+        let mut changed = false;
+        let mut i = 0;
+
+        if value.starts_with(from_binary.as_bytes()) {
+            relocated.extend_from_slice(self.to.replace('/', ".").as_bytes());
+            i = from_binary.len();
+            changed = true;
+        }
+        while i < value.len() {
+            if (i == 0 || value[i - 1] == b'L') && value[i..].starts_with(from) {
+                relocated.extend_from_slice(self.to.as_bytes());
+                i += from.len();
+                changed = true;
+            } else {
+                relocated.push(value[i]);
+                i += 1;
+            }
+        }
+        changed.then_some(relocated)
+    }
+}
+
+/// Applies the relocations to the class file, rewriting the constants of its
+/// pool that reference the relocated packages. The rest of the class file is
+/// kept as it is, since it only references the pool through indexes.
+pub fn relocate_class(bytes: &[u8], relocations: &[Relocation]) -> std::io::Result<Vec<u8>> {
+    let mut reader = ClassReader { bytes, pos: 0 };
+    if reader.u4()? != CLASS_FILE_MAGIC {
+        return Err(invalid_data("not a class file (wrong magic number)"));
+    }
+    // minor_version + major_version
+    reader.skip(4)?;
+
+    let count = reader.u2()? as usize;
+    let mut relocated = bytes[..reader.pos].to_vec();
+    let mut index = 1;
+
+    while index < count {
+        let start = reader.pos;
+        let tag = reader.u1()?;
+        match tag {
+            1 => {
+                let length = reader.u2()? as usize;
+                let value = reader.take(length)?;
+                match relocations
+                    .iter()
+                    .find_map(|relocation| relocation.relocate_constant(value))
+                {
+                    Some(value) => {
+                        let length = u16::try_from(value.len())
+                            .map_err(|_| invalid_data("relocated constant is too long"))?;
+                        relocated.push(tag);
+                        relocated.extend_from_slice(&length.to_be_bytes());
+                        relocated.extend_from_slice(&value);
+                    }
+                    None => relocated.extend_from_slice(&bytes[start..reader.pos]),
+                }
+            }
+            _ => {
+                match tag {
+                    7 | 8 | 16 | 19 | 20 => reader.skip(2)?,
+                    3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => reader.skip(4)?,
+                    5 | 6 => {
+                        reader.skip(8)?;
+                        // Longs and doubles take two entries of the pool.
+                        index += 1;
+                    }
+                    15 => reader.skip(3)?,
+                    _ => return Err(invalid_data(format!("unknown constant pool tag {tag}"))),
+                }
+                relocated.extend_from_slice(&bytes[start..reader.pos]);
+            }
+        }
+        index += 1;
+    }
+
+    relocated.extend_from_slice(&bytes[reader.pos..]);
+    Ok(relocated)
+}
+
+fn skip_attributes(reader: &mut ClassReader) -> std::io::Result<()> {
+    let attributes_count = reader.u2()?;
+    for _ in 0..attributes_count {
+        reader.u2()?;
+        let length = reader.u4()? as usize;
+        reader.skip(length)?;
+    }
+    Ok(())
+}
+
+fn invalid_data(msg: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Big-endian reader over the bytes of a class file.
+struct ClassReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ClassReader<'a> {
+    fn take(&mut self, length: usize) -> std::io::Result<&'a [u8]> {
+        if self.pos + length > self.bytes.len() {
+            return Err(invalid_data("unexpected end of the class file"));
+        }
+        let slice = &self.bytes[self.pos..self.pos + length];
+        self.pos += length;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, length: usize) -> std::io::Result<()> {
+        self.take(length).map(|_| ())
+    }
+
+    fn u1(&mut self) -> std::io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u2(&mut self) -> std::io::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u4(&mut self) -> std::io::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
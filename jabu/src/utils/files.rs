@@ -1,5 +1,15 @@
+use home::home_dir;
 use std::path::PathBuf;
 
+/// Returns the directory containing the user's jabu configuration (*`~/.config/jabu`*),
+/// honoring `XDG_CONFIG_HOME` if it's set.
+pub fn user_config_directory() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".config")))
+        .map(|config| config.join("jabu"))
+}
+
 /// Represents the different types of files in the 
 /// file system.
 pub enum FSNodeType {
@@ -66,10 +76,6 @@ pub fn walkdir_find<D, F>(directory: D, predicate: F, target_file_types: &[FSNod
         })
         .filter(|entry| FSNodeType::check_match_multiple_types(target_file_types, &entry))
         .map(|entry| entry.into_path().to_path_buf())
+        .filter(|entry_path| predicate(entry_path))
         .collect::<Vec<PathBuf>>()
-            /*
-        .filter(|e| e.file_type().is_file())
-        .map(|e| e.path().to_path_buf())
-        .filter(|e| predicate(e))
-        .collect::<Vec<PathBuf>>();*/
 }
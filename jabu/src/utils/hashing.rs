@@ -0,0 +1,20 @@
+use sha2::{Digest, Sha256};
+use std::{fs::File, io::copy, path::Path};
+
+/// Returns the SHA-256 digest of the given bytes as a lowercase
+/// hexadecimal string.
+pub fn sha256_hex(bytes: impl AsRef<[u8]>) -> String {
+    to_hex(&Sha256::digest(bytes.as_ref()))
+}
+
+/// Returns the SHA-256 digest of the contents of the file located at
+/// `path` as a lowercase hexadecimal string.
+pub fn sha256_file(path: impl AsRef<Path>) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,134 @@
+use jabu_config::model::JarManifest;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Cursor, Write},
+    path::Path,
+};
+use zip::{write::FileOptions, CompressionMethod, DateTime, ZipWriter};
+
+/// Path of the manifest inside of a jar.
+pub const MANIFEST_PATH: &str = "META-INF/MANIFEST.MF";
+
+/// Returns the modification time given to the entries of the jars, so that
+/// building the same sources always produces the same jar: the one specified by
+/// `SOURCE_DATE_EPOCH` if it's set, or the earliest date of a zip archive
+/// (*1980-01-01*) otherwise.
+pub fn entries_modification_time() -> DateTime {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<i64>().ok())
+        .and_then(date_time_from_epoch)
+        .unwrap_or_default()
+}
+
+/// Converts the seconds since the unix epoch into a (*UTC*) date of a zip archive,
+/// returning `None` if they are outside of the range a zip can store (*1980-2107*).
+pub fn date_time_from_epoch(seconds: i64) -> Option<DateTime> {
+    let days = seconds.div_euclid(86_400);
+    let seconds_of_day = seconds.rem_euclid(86_400);
+
+    // Civil date from the days since the epoch, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    DateTime::from_date_and_time(
+        u16::try_from(year).ok()?,
+        month as u8,
+        day as u8,
+        (seconds_of_day / 3_600) as u8,
+        (seconds_of_day % 3_600 / 60) as u8,
+        (seconds_of_day % 60) as u8,
+    )
+    .ok()
+}
+
+/// Returns the files inside of the directory (*i.e. the compiled classes of a
+/// project*), with their path relative to it as key and their contents as value.
+pub fn directory_entries(directory: &Path) -> std::io::Result<BTreeMap<String, Vec<u8>>> {
+    walkdir::WalkDir::new(directory)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let name = entry
+                .path()
+                .strip_prefix(directory)
+                .unwrap_or(entry.path())
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            Ok((name, std::fs::read(entry.path())?))
+        })
+        .collect()
+}
+
+/// Writes a jar at `jar_path` with the given manifest and entries.
+///
+/// The manifest is always the first entry, followed by the rest of the entries
+/// (*and the directories containing them*) sorted by their path. Every entry is
+/// given the same modification time and permissions, so that writing the same
+/// contents always produces the same bytes.
+pub fn write_jar(
+    jar_path: &Path,
+    manifest: &JarManifest,
+    entries: &BTreeMap<String, Vec<u8>>,
+    modification_time: DateTime,
+) -> std::io::Result<()> {
+    let mut manifest = manifest.clone();
+    manifest
+        .contents
+        .entry("Created-By".to_string())
+        .or_insert_with(|| "jabu".to_string());
+
+    let directories = entries
+        .keys()
+        .flat_map(|name| {
+            name.match_indices('/')
+                .map(|(index, _)| name[..=index].to_string())
+                .collect::<Vec<String>>()
+        })
+        .collect::<BTreeSet<String>>();
+    let files = entries
+        .iter()
+        .filter(|(name, _)| name.as_str() != MANIFEST_PATH)
+        .map(|(name, contents)| (name.clone(), Some(contents)));
+    let entries = directories
+        .into_iter()
+        .map(|directory| (directory, None))
+        .chain(files)
+        .collect::<BTreeMap<String, Option<&Vec<u8>>>>();
+
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(modification_time);
+    let mut jar = ZipWriter::new(Cursor::new(Vec::new()));
+
+    jar.start_file(MANIFEST_PATH, options.unix_permissions(0o644))?;
+    jar.write_all(manifest.to_string().as_bytes())?;
+    for (name, contents) in entries {
+        match contents {
+            Some(contents) => {
+                jar.start_file(name, options.unix_permissions(0o644))?;
+                jar.write_all(contents)?;
+            }
+            None => jar.add_directory(name, options.unix_permissions(0o755))?,
+        }
+    }
+
+    std::fs::create_dir_all(jar_path.parent().unwrap_or(Path::new(".")))?;
+    std::fs::write(jar_path, jar.finish()?.into_inner())
+}
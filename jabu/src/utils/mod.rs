@@ -1,5 +1,8 @@
 mod cmd;
 mod files;
+pub mod classfile;
+pub mod hashing;
+pub mod jar;
 
 pub use cmd::*;
 pub use files::*;